@@ -0,0 +1,66 @@
+//! Run with `cargo bench --features bench`
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use shapes_rs::base::*;
+use shapes_rs::buffer::*;
+use shapes_rs::components::Camera;
+use shapes_rs::demos;
+use shapes_rs::generators::{CubeGenerator, TorusGenerator};
+use shapes_rs::renderer::project_points;
+use shapes_rs::renderer::{pers_proj, pers_proj_color, raymarch_proj};
+use shapes_rs::testing::fixture_scene;
+use shapes_rs::Object;
+
+fn generation(c: &mut Criterion) {
+    let torusgen = TorusGenerator::new(10.0, 50.0);
+    let cubegen = CubeGenerator::new(40.0, 40.0, 40.0);
+    c.bench_function("generate torus", |b| b.iter(|| Shape::new(&torusgen)));
+    c.bench_function("generate cube", |b| b.iter(|| Shape::new(&cubegen)));
+}
+
+fn transform(c: &mut Criterion) {
+    let torusgen = TorusGenerator::new(10.0, 50.0);
+    let torus = Shape::new(&torusgen);
+    let rotator = Rotator::new(Vector3D::new(1.0, 2.0, 3.0), 0.1);
+    c.bench_function("rotate torus", |b| {
+        b.iter_batched(
+            || torus.clone(),
+            |mut shape| shape.rotate_self(&rotator),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn projection(c: &mut Criterion) {
+    let torusgen = TorusGenerator::new(10.0, 50.0);
+    let mut torus = Shape::new(&torusgen);
+    torus.translate_self(Coord::new(150.0, 0.0, 0.0));
+    let camera = Camera::new(Coord::default(), Vector3D::new(1.0, 0.0, 0.0), Angle::from_degree(60.0));
+    let mut zbuffer = ZBuffer::new(80, 40);
+    let mut buffer = SimpleTerminalBuffer::new(80, 40);
+    c.bench_function("project_points torus 80x40", |b| {
+        b.iter(|| {
+            zbuffer.clear();
+            project_points(&torus.points, &camera, &mut zbuffer, &mut buffer);
+        })
+    });
+}
+
+fn renderers(c: &mut Criterion) {
+    let scene = demos::spinning_donut(80, 40).scene;
+    let snapshot = scene.snapshot();
+    c.bench_function("pers_proj donut 80x40", |b| b.iter(|| pers_proj(&snapshot)));
+    c.bench_function("pers_proj_color donut 80x40", |b| b.iter(|| pers_proj_color(&snapshot)));
+
+    let cubegen = CubeGenerator::new(40.0, 40.0, 40.0);
+    let mut cube = fixture_scene(40, 20);
+    cube.add_object(
+        Object::new(Coord::new(120.0, 0.0, 0.0), &cubegen, Rotator::identity()).with_sdf(&cubegen),
+    );
+    let snapshot = cube.snapshot();
+    c.bench_function("pers_proj cube 40x20", |b| b.iter(|| pers_proj(&snapshot)));
+    c.bench_function("raymarch_proj cube 40x20", |b| b.iter(|| raymarch_proj(&snapshot)));
+}
+
+criterion_group!(benches, generation, transform, projection, renderers);
+criterion_main!(benches);
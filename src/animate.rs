@@ -0,0 +1,4 @@
+pub mod animator;
+pub mod input;
+pub mod replay;
+pub mod stats;
@@ -0,0 +1,1134 @@
+use std::collections::VecDeque;
+use std::hint;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, sleep};
+use std::time::{Duration, Instant};
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "terminal")]
+use super::input::{pending_keys, RawModeGuard, TerminalGuard};
+use super::input::{Key, SignalSource};
+use super::replay::TransformPlayer;
+use super::stats::FrameStats;
+use crate::basetype::Coord;
+use crate::math::projection::{fragments_to_buffer, pers_proj_hdr, project_fragments, tone_map};
+use crate::math::temporal::TemporalFilter;
+use crate::out::luminance::LuminanceBuffer;
+use crate::out::{Buffer, SizedBuffer};
+use crate::out::png::encode_png;
+use crate::out::rgba::terminal_to_rgba;
+use crate::out::sink::FrameSink;
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::error::{Result, ShapesError};
+use crate::scene::manager::{SceneManager, Transition};
+use crate::scene::Scene;
+use crate::shape::rotator::Rotator;
+use crate::shape::shape_base::Point;
+
+/// Pixels every cell of the frame becomes when it is saved as a PNG
+const SCREENSHOT_CELL_SIZE: usize = 8;
+/// Sleeping tends to overshoot by a millisecond or two, this much before a frame is due the
+/// animator stops sleeping and spins instead
+const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+/// Called with the animator before every frame, or whenever a bound key is pressed
+type Hook<'a> = Box<dyn FnMut(&mut Animator<'a>) + 'a>;
+/// Called with the animator and the frame after every frame was handed to the sinks
+type PostFrameHook<'a> = Box<dyn FnMut(&mut Animator<'a>, &SimpleTerminalBuffer) + 'a>;
+
+/// Blends every frame with the ones before it, see Animator::set_motion_blur
+struct MotionBlur {
+    frames: usize,
+    decay: f64,
+    /// Most recent frame first
+    history: VecDeque<LuminanceBuffer>,
+}
+
+impl MotionBlur {
+    /// Remembers the frame and returns the weighted average of the remembered ones. Cells
+    /// which are empty in a frame count as dark in it, so moving objects leave fading trails
+    fn blend(&mut self, frame: LuminanceBuffer) -> LuminanceBuffer {
+        if self.history.front().is_some_and(|last| last.size() != frame.size()) {
+            self.history.clear();
+        }
+        self.history.push_front(frame);
+        self.history.truncate(self.frames.max(1));
+
+        let (size_x, size_y) = self.history[0].size();
+        let mut weight = 1.0;
+        let mut total_weight = 0.0;
+        let mut sum: Vec<Option<f64>> = vec![None; size_x * size_y];
+        for frame in self.history.iter() {
+            for (cell, luminance) in sum.iter_mut().zip(frame.buffer.iter()) {
+                if let Some(luminance) = luminance {
+                    *cell = Some(cell.unwrap_or(0.0) + luminance * weight);
+                }
+            }
+            total_weight += weight;
+            weight *= self.decay;
+        }
+        for cell in sum.iter_mut().flatten() {
+            *cell /= total_weight;
+        }
+        LuminanceBuffer::new_with_buffer(size_x, size_y, sum)
+    }
+}
+
+/// Drives a scene frame by frame and pushes every rendered frame to all subscribed sinks.
+/// Rendering does not care where the frames end up, so the same animation can be printed,
+/// saved to a file or inspected from a test.
+/// ### Example:
+/// ```
+/// use shapes_rs::animation::Animator;
+/// use shapes_rs::base::*;
+/// use shapes_rs::buffer::*;
+/// use shapes_rs::components::*;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::renderer;
+/// use shapes_rs::{Object, Scene};
+///
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let my_torus_object = Object::new(Coord::new(70.0, 0.0, 0.0), &torusgen, Rotator::default());
+/// let camera = Camera::new(
+///     Coord::new(0.0, 0.0, 0.0),
+///     Vector3D::new(1.0, 0.0, 0.0),
+///     Angle::from_degree(60.0),
+/// );
+/// let mut scene = Scene::new(camera, SimpleTerminalBuffer::new(40, 20), renderer::pers_proj);
+/// scene.add_light(Light3D::new(Coord::new(100.0, 0.0, 500.0)));
+/// scene.add_object(my_torus_object);
+///
+/// let (sink, receiver) = ChannelSink::new();
+/// let mut animator = Animator::new(scene);
+/// animator.subscribe(sink);
+/// animator.run(3);
+///
+/// assert_eq!(receiver.try_iter().count(), 3);
+/// ```
+pub struct Animator<'a> {
+    pub scene: Scene<'a>,
+    /// Time to wait between two frames, unless a target frame rate is set
+    pub frame_time: Duration,
+    /// Simulated time between two updates of the scene when running with a fixed timestep
+    pub timestep: Duration,
+    sinks: Vec<Box<dyn FrameSink + 'a>>,
+    /// Location and orientation of every object before the last update
+    previous: Vec<(Coord, Rotator)>,
+    /// Time which has passed but was not simulated yet
+    accumulator: Duration,
+    pre_frame: Vec<Hook<'a>>,
+    post_frame: Vec<PostFrameHook<'a>>,
+    key_handlers: Vec<(Key, Hook<'a>)>,
+    /// Set by stop, run functions return once it is set
+    stopped: bool,
+    /// Can be set from anywhere, even a signal handler, and stops the animation before the
+    /// next frame
+    interrupted: Arc<AtomicBool>,
+    shutdown: Vec<Hook<'a>>,
+    /// Set once the shutdown hooks ran, so they only ever run once
+    shut_down: bool,
+    /// Frames keep being rendered while paused, but the scene is not updated
+    paused: bool,
+    /// How much faster than real time the scene moves
+    time_scale: f64,
+    /// Frame most recently handed to the sinks
+    last_frame: Option<SimpleTerminalBuffer>,
+    motion_blur: Option<MotionBlur>,
+    temporal_filter: Option<TemporalFilter>,
+    /// Time between two frames going by the target frame rate
+    frame_period: Option<Duration>,
+    /// When the next frame is due with a target frame rate
+    next_deadline: Option<Instant>,
+    stats: FrameStats,
+    /// When the last frame was handed to the sinks, for measuring frame times
+    last_frame_at: Option<Instant>,
+    /// Draw the frame statistics into the top left corner of every frame
+    stats_overlay: bool,
+    /// The other scenes, while cycling through scenes
+    scenes: Option<SceneManager<'a>>,
+    /// Held while keys are read from the terminal
+    #[cfg(feature = "terminal")]
+    raw_mode: Option<RawModeGuard>,
+    /// Held while the animation has the whole terminal, keys are read then as well
+    #[cfg(feature = "terminal")]
+    screen: Option<TerminalGuard>,
+}
+
+impl<'a> Animator<'a> {
+    /// Creates an animator for the given scene, without any sinks and without any delay
+    /// between frames
+    pub fn new(scene: Scene<'a>) -> Self {
+        Self {
+            scene,
+            frame_time: Duration::ZERO,
+            timestep: Duration::from_secs(1) / 30,
+            sinks: Vec::new(),
+            previous: Vec::new(),
+            accumulator: Duration::ZERO,
+            pre_frame: Vec::new(),
+            post_frame: Vec::new(),
+            key_handlers: Vec::new(),
+            stopped: false,
+            interrupted: Arc::new(AtomicBool::new(false)),
+            shutdown: Vec::new(),
+            shut_down: false,
+            paused: false,
+            time_scale: 1.0,
+            last_frame: None,
+            motion_blur: None,
+            temporal_filter: None,
+            frame_period: None,
+            next_deadline: None,
+            stats: FrameStats::default(),
+            last_frame_at: None,
+            stats_overlay: false,
+            scenes: None,
+            #[cfg(feature = "terminal")]
+            raw_mode: None,
+            #[cfg(feature = "terminal")]
+            screen: None,
+        }
+    }
+
+    /// Creates an animator which cycles through the scenes of the manager, starting with its
+    /// current one. `scene` is always the scene shown, see switch_scene.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::animation::Animator;
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::demos;
+    /// use shapes_rs::{SceneManager, Transition};
+    ///
+    /// let reel = SceneManager::new(demos::spinning_donut(40, 20).scene)
+    ///     .with_scene(demos::tumbling_cube(40, 20).scene);
+    /// let mut animator = Animator::with_scenes(reel);
+    /// let (sink, frames) = ChannelSink::new();
+    /// animator.subscribe(sink);
+    ///
+    /// // A new scene every 10 frames, faded in over 4 of them
+    /// let mut shown = 0;
+    /// animator.on_pre_frame(move |animator| {
+    ///     shown += 1;
+    ///     if shown % 10 == 0 {
+    ///         animator.next_scene(Transition::Fade, 4);
+    ///     }
+    /// });
+    /// animator.run(15);
+    ///
+    /// assert_eq!(animator.current_scene(), Some(1));
+    /// assert_eq!(frames.try_iter().count(), 15);
+    /// ```
+    pub fn with_scenes(mut scenes: SceneManager<'a>) -> Self {
+        let scene = scenes.take_current();
+        let mut animator = Self::new(scene);
+        animator.scenes = Some(scenes);
+        animator
+    }
+
+    /// Switches to the scene of the manager with the given index, the transition starting from
+    /// the frame shown last and taking this many frames. Fails if the animator was not created
+    /// with with_scenes or the manager has no such scene
+    pub fn switch_scene(
+        &mut self,
+        index: usize,
+        transition: Transition,
+        frames: usize,
+    ) -> Result<()> {
+        let Some(scenes) = self.scenes.as_mut() else {
+            return Err(ShapesError::UnknownScene(index));
+        };
+        let from = match &self.last_frame {
+            Some(frame) => frame.clone(),
+            None => self.scene.render(),
+        };
+        let old = scenes.current;
+        scenes.begin_switch(index, transition, frames, from)?;
+        // The shown scene goes back into its slot, the placeholder is swapped around until it
+        // ends up in the slot of the new one
+        mem::swap(&mut self.scene, &mut scenes.scenes[old]);
+        mem::swap(&mut self.scene, &mut scenes.scenes[index]);
+        // The objects are different ones now, nothing to interpolate from
+        self.previous.clear();
+        Ok(())
+    }
+
+    /// Switches to the scene after the current one, the first one after the last. Does nothing
+    /// if the animator was not created with with_scenes
+    pub fn next_scene(&mut self, transition: Transition, frames: usize) {
+        if let Some(scenes) = self.scenes.as_ref() {
+            let index = (scenes.current + 1) % scenes.scene_count();
+            // Can't fail, the index is always in range
+            let _ = self.switch_scene(index, transition, frames);
+        }
+    }
+
+    /// Index of the scene shown, None if the animator was not created with with_scenes
+    pub fn current_scene(&self) -> Option<usize> {
+        self.scenes.as_ref().map(|scenes| scenes.current)
+    }
+
+    /// Sets the time to wait between two frames
+    pub fn with_frame_time(mut self, frame_time: Duration) -> Self {
+        self.frame_time = frame_time;
+        self
+    }
+
+    /// Sets the simulated time between two updates used by advance and run_fixed
+    pub fn with_timestep(mut self, timestep: Duration) -> Self {
+        self.timestep = timestep;
+        self
+    }
+
+    /// Adds a sink which will receive every frame rendered from now on
+    pub fn subscribe(&mut self, sink: impl FrameSink + 'a) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Adds a function which is called before every frame is rendered, after pressed keys were
+    /// handled. It may change the scene or stop the animation, in which case the frame is not
+    /// rendered.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::animation::{Animator, Key};
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::testing::fixture_scene;
+    ///
+    /// let (sink, receiver) = ChannelSink::new();
+    /// let mut animator = Animator::new(fixture_scene(20, 10));
+    /// animator.subscribe(sink);
+    ///
+    /// // Quit after the second frame, or as soon as q is pressed
+    /// let mut frames = 0;
+    /// animator.on_post_frame(move |animator, _frame| {
+    ///     frames += 1;
+    ///     if frames == 2 {
+    ///         animator.stop();
+    ///     }
+    /// });
+    /// animator.on_key(Key::Char('q'), |animator| animator.stop());
+    ///
+    /// animator.run(10);
+    /// assert_eq!(receiver.try_iter().count(), 2);
+    /// assert!(animator.is_stopped());
+    /// ```
+    pub fn on_pre_frame(&mut self, hook: impl FnMut(&mut Animator<'a>) + 'a) {
+        self.pre_frame.push(Box::new(hook));
+    }
+
+    /// Adds a function which is called with every frame once it was handed to the sinks
+    pub fn on_post_frame(&mut self, hook: impl FnMut(&mut Animator<'a>, &SimpleTerminalBuffer) + 'a) {
+        self.post_frame.push(Box::new(hook));
+    }
+
+    /// Adds a function which is called whenever the given key is pressed
+    pub fn on_key(&mut self, key: Key, handler: impl FnMut(&mut Animator<'a>) + 'a) {
+        self.key_handlers.push((key, Box::new(handler)));
+    }
+
+    /// Reads a value from the source before every frame and calls the function with it, for
+    /// example to let the loudness of music drive how fast an object spins. Frames the source
+    /// has no value for are left alone.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::animation::{Animator, SampleSignal};
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::{Object, ObjectId};
+    ///
+    /// let torusgen = TorusGenerator::new(10.0, 50.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// scene.add_object(Object::new(Coord::new(70.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+    ///
+    /// let mut animator = Animator::new(scene);
+    /// let amplitudes = SampleSignal::new(vec![0.1, 0.5, 1.0]);
+    /// animator.bind_signal(amplitudes, |animator, amplitude| {
+    ///     let torus = animator.scene.object_mut(ObjectId(0)).unwrap();
+    ///     torus.spin(Vector3D::new(0.0, 0.0, 1.0), Angle::from_degree(360.0 * amplitude));
+    /// });
+    /// animator.run(3);
+    ///
+    /// // The last amplitude decides the rate
+    /// let (_, rate) = animator.scene.object(ObjectId(0)).unwrap().spin.unwrap();
+    /// assert!((rate.get() - Angle::from_degree(360.0).get()).abs() < 1e-9);
+    /// ```
+    pub fn bind_signal(
+        &mut self,
+        mut source: impl SignalSource + 'a,
+        mut apply: impl FnMut(&mut Animator<'a>, f64) + 'a,
+    ) {
+        self.on_pre_frame(move |animator| {
+            if let Some(value) = source.next_value() {
+                apply(animator, value);
+            }
+        });
+    }
+
+    /// Plays a recording of object transforms back instead of simulating the scene. The
+    /// animation is paused, so only the recording moves objects, and stops after its last frame
+    pub fn play_transforms(&mut self, mut player: TransformPlayer) {
+        self.pause();
+        self.on_pre_frame(move |animator| {
+            if !player.apply_next(&mut animator.scene) {
+                animator.stop();
+            }
+        });
+    }
+
+    /// Calls every handler bound to the given key, as if it was pressed
+    pub fn press(&mut self, key: Key) {
+        let mut handlers = mem::take(&mut self.key_handlers);
+        for (_, handler) in handlers.iter_mut().filter(|(bound, _)| *bound == key) {
+            handler(self);
+        }
+        // Keep handlers which were bound while handling the key
+        handlers.append(&mut self.key_handlers);
+        self.key_handlers = handlers;
+    }
+
+    /// Starts reading keys from the terminal before every frame. The terminal stays in raw mode
+    /// until the animator is dropped.
+    #[cfg(feature = "terminal")]
+    pub fn capture_keys(&mut self) -> io::Result<()> {
+        if self.raw_mode.is_none() && self.screen.is_none() {
+            self.raw_mode = Some(RawModeGuard::new()?);
+        }
+        Ok(())
+    }
+
+    /// Switches the terminal to the alternate screen with the cursor hidden and starts reading
+    /// keys from it, see TerminalGuard. The terminal is restored when the animator is dropped,
+    /// when the program panics, or when Ctrl-C stops the animation.
+    #[cfg(feature = "terminal")]
+    pub fn enter_fullscreen(&mut self) -> io::Result<()> {
+        if self.screen.is_none() {
+            // Raw mode is part of the screen guard
+            self.raw_mode = None;
+            self.screen = Some(TerminalGuard::new()?);
+        }
+        Ok(())
+    }
+
+    /// Makes every run function return after the current frame
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    /// Flag which stops the animation before its next frame once it is set. Unlike stop it can
+    /// be set from another thread or a signal handler, see handle_signals.
+    /// ### Example:
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    /// use std::sync::atomic::Ordering;
+    /// use shapes_rs::animation::Animator;
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::testing::fixture_scene;
+    ///
+    /// let (sink, receiver) = ChannelSink::new();
+    /// let mut animator = Animator::new(fixture_scene(20, 10));
+    /// animator.subscribe(sink);
+    ///
+    /// let shut_down = Rc::new(Cell::new(false));
+    /// let flag = shut_down.clone();
+    /// animator.on_shutdown(move |_animator| flag.set(true));
+    ///
+    /// // As if Ctrl-C was pressed while the first frame was rendered
+    /// let interrupt = animator.interrupt_handle();
+    /// animator.on_post_frame(move |_animator, _frame| interrupt.store(true, Ordering::SeqCst));
+    /// animator.run(10);
+    ///
+    /// // The frame in progress still made it out
+    /// assert_eq!(receiver.try_iter().count(), 1);
+    /// assert!(animator.is_stopped());
+    /// assert!(shut_down.get());
+    /// ```
+    pub fn interrupt_handle(&self) -> Arc<AtomicBool> {
+        self.interrupted.clone()
+    }
+
+    /// Stops the animation on Ctrl-C or a termination signal instead of killing the process,
+    /// so the frame in progress is finished, sinks are flushed, shutdown hooks run and the
+    /// terminal is restored. A second Ctrl-C before the animation stopped exits right away.
+    #[cfg(feature = "signals")]
+    pub fn handle_signals(&mut self) -> io::Result<()> {
+        use signal_hook::consts::{SIGINT, SIGTERM};
+        use signal_hook::flag;
+
+        for signal in [SIGINT, SIGTERM] {
+            // Registered first, so it sees the flag before this signal sets it
+            flag::register_conditional_shutdown(signal, 1, self.interrupted.clone())?;
+            flag::register(signal, self.interrupted.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Adds a function which is called once when a run function returns because the animation
+    /// was stopped, after every sink was flushed
+    pub fn on_shutdown(&mut self, hook: impl FnMut(&mut Animator<'a>) + 'a) {
+        self.shutdown.push(Box::new(hook));
+    }
+
+    /// Flushes the sinks, gives the terminal back and calls the shutdown hooks, once the
+    /// animation is stopped
+    fn shut_down_if_stopped(&mut self) {
+        if !self.stopped || self.shut_down {
+            return;
+        }
+        self.shut_down = true;
+        for sink in self.sinks.iter_mut() {
+            sink.flush();
+        }
+        #[cfg(feature = "terminal")]
+        {
+            self.screen = None;
+            self.raw_mode = None;
+        }
+        let mut hooks = mem::take(&mut self.shutdown);
+        for hook in hooks.iter_mut() {
+            hook(self);
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Handles pressed keys and calls the pre-frame hooks
+    fn begin_frame(&mut self) {
+        if self.interrupted.load(Ordering::SeqCst) {
+            self.stop();
+            return;
+        }
+        #[cfg(feature = "terminal")]
+        if self.raw_mode.is_some() || self.screen.is_some() {
+            // A terminal which can't be read from just doesn't press any keys
+            for key in pending_keys().unwrap_or_default() {
+                self.press(key);
+                if key == Key::Interrupt {
+                    self.stop();
+                }
+            }
+        }
+        let mut hooks = mem::take(&mut self.pre_frame);
+        for hook in hooks.iter_mut() {
+            hook(self);
+        }
+        hooks.append(&mut self.pre_frame);
+        self.pre_frame = hooks;
+    }
+
+    /// Hands the frame to every sink and calls the post-frame hooks
+    fn finish_frame(&mut self, frame: &SimpleTerminalBuffer) {
+        phase_span!("output", sinks = self.sinks.len());
+        let now = Instant::now();
+        if let Some(last) = self.last_frame_at {
+            self.stats.record(now - last);
+        }
+        self.last_frame_at = Some(now);
+        let overlaid;
+        let frame = match self.stats_overlay {
+            true => {
+                overlaid = with_overlay(frame, &self.stats.to_string());
+                &overlaid
+            }
+            false => frame,
+        };
+        for sink in self.sinks.iter_mut() {
+            sink.submit(frame);
+        }
+        let mut hooks = mem::take(&mut self.post_frame);
+        for hook in hooks.iter_mut() {
+            hook(self, frame);
+        }
+        hooks.append(&mut self.post_frame);
+        self.post_frame = hooks;
+        self.last_frame = Some(frame.clone());
+    }
+
+    /// Writes the frame most recently handed to the sinks into a file, or the current state of
+    /// the scene if nothing was rendered yet. Paths ending in `.png` get an image with every
+    /// cell as a gray block, anything else the frame as text. Images of empty frames fail with
+    /// invalid input.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::animation::{Animator, Key};
+    /// use shapes_rs::demos;
+    ///
+    /// let dir = std::env::temp_dir().join("shapes_rs_dump_frame");
+    /// std::fs::create_dir_all(&dir).unwrap();
+    ///
+    /// let mut animator = Animator::new(demos::spinning_donut(40, 20).scene);
+    /// animator.run(2);
+    /// animator.dump_frame(dir.join("donut.txt")).unwrap();
+    /// let text = std::fs::read_to_string(dir.join("donut.txt")).unwrap();
+    /// assert!(text.contains('@'));
+    ///
+    /// // Every press of p saves another numbered image
+    /// animator.screenshot_on(Key::Char('p'), dir.join("shot.png"));
+    /// animator.press(Key::Char('p'));
+    /// animator.press(Key::Char('p'));
+    /// assert!(dir.join("shot-1.png").exists());
+    /// assert!(dir.join("shot-2.png").exists());
+    /// ```
+    pub fn dump_frame<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let rendered;
+        let frame = match &self.last_frame {
+            Some(frame) => frame,
+            None => {
+                rendered = self.scene.render();
+                &rendered
+            }
+        };
+        let path = path.as_ref();
+        let is_png = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+        match is_png {
+            true => {
+                let rgba = terminal_to_rgba(frame, SCREENSHOT_CELL_SIZE);
+                let png = encode_png(
+                    frame.size_x * SCREENSHOT_CELL_SIZE,
+                    frame.size_y * SCREENSHOT_CELL_SIZE,
+                    &rgba,
+                )
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+                fs::write(path, png)
+            }
+            false => fs::write(path, format!("{}\n", frame)),
+        }
+    }
+
+    /// Binds a key which dumps the current frame every time it is pressed. Screenshots are
+    /// numbered, `shot.png` becomes `shot-1.png`, `shot-2.png` and so on. Screenshots which
+    /// can't be written are skipped so the animation keeps going.
+    pub fn screenshot_on(&mut self, key: Key, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let mut count = 0;
+        self.on_key(key, move |animator| {
+            count += 1;
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let name = match path.extension() {
+                Some(extension) => format!("{}-{}.{}", stem, count, extension.to_string_lossy()),
+                None => format!("{}-{}", stem, count),
+            };
+            let _ = animator.dump_frame(path.with_file_name(name));
+        });
+    }
+
+    /// Renders the current state of the scene and hands the frame to every sink
+    pub fn render_frame(&mut self) {
+        self.begin_frame();
+        if self.stopped {
+            return;
+        }
+        let frame = self.render_scene();
+        self.finish_frame(&frame);
+    }
+
+    /// Renders the scene, blended with or smoothed against the frames before it if either is
+    /// turned on, and with the scene before it while switching scenes
+    fn render_scene(&mut self) -> SimpleTerminalBuffer {
+        let frame = if let Some(blur) = self.motion_blur.as_mut() {
+            self.scene
+                .render_with(|view| tone_map(view, &blur.blend(pers_proj_hdr(view))))
+        } else if let Some(filter) = self.temporal_filter.as_mut() {
+            self.scene.render_with(|view| {
+                let mut fragments = project_fragments(view);
+                filter.apply(&mut fragments);
+                fragments_to_buffer(view, &fragments)
+            })
+        } else {
+            self.scene.render()
+        };
+        match self.scenes.as_mut() {
+            Some(scenes) => scenes.transition_frame(frame),
+            None => frame,
+        }
+    }
+
+    /// Blends every frame with up to `frames - 1` frames before it, each one weighted `decay`
+    /// times as much as the one after it, so fast objects leave trails. Frames are rendered in
+    /// linear space with pers_proj_hdr and the scene's render options instead of its renderer.
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::animation::Animator;
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::demos;
+    ///
+    /// let lit = |frame: &SimpleTerminalBuffer| frame.buffer.iter().filter(|ch| **ch != ' ').count();
+    /// // Large steps, so the cube turns a lot between two frames
+    /// let timestep = Duration::from_millis(500);
+    ///
+    /// let mut sharp = Animator::new(demos::tumbling_cube(40, 20).scene).with_timestep(timestep);
+    /// let (sink, sharp_frames) = ChannelSink::new();
+    /// sharp.subscribe(sink);
+    /// sharp.run(4);
+    ///
+    /// let mut blurred = Animator::new(demos::tumbling_cube(40, 20).scene).with_timestep(timestep);
+    /// blurred.set_motion_blur(4, 0.5);
+    /// let (sink, blurred_frames) = ChannelSink::new();
+    /// blurred.subscribe(sink);
+    /// blurred.run(4);
+    ///
+    /// // The trail covers cells the cube has left already
+    /// let sharp = sharp_frames.try_iter().last().unwrap();
+    /// let blurred = blurred_frames.try_iter().last().unwrap();
+    /// assert!(lit(&blurred) > lit(&sharp));
+    /// ```
+    pub fn set_motion_blur(&mut self, frames: usize, decay: f64) {
+        self.motion_blur = Some(MotionBlur {
+            frames,
+            decay,
+            history: VecDeque::new(),
+        });
+    }
+
+    /// Same as set_motion_blur, for building animators
+    pub fn with_motion_blur(mut self, frames: usize, decay: f64) -> Self {
+        self.set_motion_blur(frames, decay);
+        self
+    }
+
+    /// Turns motion blur off and forgets the frames it remembered
+    pub fn clear_motion_blur(&mut self) {
+        self.motion_blur = None;
+    }
+
+    /// Smooths every frame against the ones before it to stop point clouds from shimmering,
+    /// see TemporalFilter. Frames are rendered with pers_proj instead of the scene's renderer.
+    /// Motion blur takes precedence while it is on
+    pub fn set_temporal_smoothing(&mut self, filter: TemporalFilter) {
+        self.temporal_filter = Some(filter);
+    }
+
+    /// Turns temporal smoothing off
+    pub fn clear_temporal_smoothing(&mut self) {
+        self.temporal_filter = None;
+    }
+
+    /// Runs at the given amount of frames per second instead of waiting `frame_time` after
+    /// every frame. The time spent rendering counts towards the wait, and the last bit of it
+    /// is spun instead of slept so frames are handed out on time. Frames which come too late
+    /// are counted as dropped in the frame statistics rather than caught up with.
+    /// Zero or negative rates remove the target again, and so do rates so low that a single
+    /// frame would last longer than a Duration can hold.
+    /// ### Example:
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use shapes_rs::animation::Animator;
+    /// use shapes_rs::testing::fixture_scene;
+    ///
+    /// let mut animator = Animator::new(fixture_scene(20, 10));
+    /// animator.target_fps(50.0);
+    /// let start = Instant::now();
+    /// animator.run(5);
+    ///
+    /// assert!(start.elapsed() >= Duration::from_millis(80));
+    /// let stats = animator.frame_stats();
+    /// assert_eq!(stats.frames(), 4);
+    /// assert!(stats.average().unwrap() >= Duration::from_millis(20));
+    ///
+    /// assert_eq!(animator.frame_period(), Some(Duration::from_millis(20)));
+    /// animator.target_fps(1e-310);
+    /// assert_eq!(animator.frame_period(), None);
+    /// ```
+    pub fn target_fps(&mut self, fps: f64) {
+        self.frame_period = match fps > 0.0 {
+            true => Duration::try_from_secs_f64(1.0 / fps).ok(),
+            false => None,
+        };
+        self.next_deadline = None;
+    }
+
+    /// Time between two frames going by the target frame rate, None without a target
+    pub fn frame_period(&self) -> Option<Duration> {
+        self.frame_period
+    }
+
+    /// Time between every frame and the one before it over the last frames, and how many
+    /// frames were dropped for missing the target frame rate
+    pub fn frame_stats(&self) -> &FrameStats {
+        &self.stats
+    }
+
+    /// Forgets the frame times measured so far, the next frame starts measuring anew
+    pub fn reset_frame_stats(&mut self) {
+        self.stats.reset();
+        self.last_frame_at = None;
+    }
+
+    /// Draws the frame statistics over the top left corner of every frame handed to the sinks
+    pub fn show_frame_stats(&mut self, show: bool) {
+        self.stats_overlay = show;
+    }
+
+    /// Waits after a frame, either `frame_time` or until the next frame is due. Stopped
+    /// animations don't wait for a frame which will never come
+    fn wait_for_next_frame(&mut self) {
+        if self.stopped {
+            return;
+        }
+        let Some(period) = self.frame_period else {
+            if !self.frame_time.is_zero() {
+                sleep(self.frame_time);
+            }
+            return;
+        };
+        let now = Instant::now();
+        // Nothing to pace against after the first frame, it gets a whole period
+        let deadline = *self.next_deadline.get_or_insert(now + period);
+        match now.checked_duration_since(deadline) {
+            Some(late) => {
+                // Start over from now instead of rushing out the frames that were missed
+                self.stats.record_dropped((late.as_nanos() / period.as_nanos().max(1)) as usize);
+                self.next_deadline = Some(now + period);
+            }
+            None => {
+                sleep_until(deadline);
+                self.next_deadline = Some(deadline + period);
+            }
+        }
+    }
+
+    /// Stops updating the scene, frames are still rendered and hooks and keys still work
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pauses a running animation or resumes a paused one, handy to bind to a key
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Sets how fast the scene moves compared to real time, 0.5 being half speed. Negative
+    /// scales count as zero.
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::animation::Animator;
+    /// use shapes_rs::demos;
+    ///
+    /// let mut animator = Animator::new(demos::tumbling_cube(40, 20).scene)
+    ///     .with_timestep(Duration::from_millis(100));
+    /// animator.set_time_scale(0.5);
+    /// animator.run(4);
+    /// assert!((animator.scene.time() - 0.2).abs() < 1e-9);
+    ///
+    /// // Frames are still drawn while paused, stepping moves on by one timestep
+    /// animator.pause();
+    /// animator.run(4);
+    /// animator.step_frame();
+    /// assert!((animator.scene.time() - 0.3).abs() < 1e-9);
+    /// ```
+    pub fn set_time_scale(&mut self, scale: f64) {
+        self.time_scale = scale.max(0.0);
+    }
+
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Simulated time which passes during one update at the current time scale
+    fn scaled_timestep(&self) -> Duration {
+        self.timestep.mul_f64(self.time_scale)
+    }
+
+    /// Part of an object's rotation applied when the scene moves by the given time, the whole
+    /// rotation being applied once per timestep
+    fn rotation_over(&self, rotation: &Rotator, dt: Duration) -> Rotator {
+        if self.timestep.is_zero() || dt == self.timestep {
+            return rotation.clone();
+        }
+        let fraction = dt.as_secs_f64() / self.timestep.as_secs_f64();
+        Rotator::identity().slerp(rotation, fraction)
+    }
+
+    /// Moves the scene forward by the given time, applying as much of every object's rotation
+    fn simulate(&mut self, dt: Duration) {
+        for index in 0..self.scene.objects.len() {
+            let rotation = self.rotation_over(&self.scene.objects[index].rotation, dt);
+            self.scene.objects[index].shape.rotate_self(&rotation);
+        }
+        self.scene.advance(dt);
+    }
+
+    /// Moves the scene one update forward by applying the rotation of every object and
+    /// advancing it by one timestep, scaled by the time scale. Does nothing while paused.
+    pub fn update(&mut self) {
+        if self.paused {
+            return;
+        }
+        self.simulate(self.scaled_timestep());
+    }
+
+    /// Moves the scene forward by exactly one timestep, even while paused, and renders the
+    /// result. Meant for going through a paused animation frame by frame.
+    pub fn step_frame(&mut self) {
+        self.remember_previous();
+        self.simulate(self.timestep);
+        self.render_frame();
+    }
+
+    /// Stores where every object is before an update, for render_interpolated
+    fn remember_previous(&mut self) {
+        self.previous = self
+            .scene
+            .objects
+            .iter()
+            .map(|obj| (obj.location, obj.shape.orientation.clone()))
+            .collect();
+    }
+
+    /// Renders one frame and then updates the scene, unless the animation was stopped while
+    /// rendering
+    pub fn step(&mut self) {
+        self.render_frame();
+        if !self.stopped {
+            self.update();
+        }
+    }
+
+    /// Lets the given amount of time pass. The scene is updated once for every full timestep
+    /// that has passed, then a frame is rendered in between the last two updates, so the
+    /// animation stays smooth when frames are rendered more often than the scene is updated.
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::animation::Animator;
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::testing::fixture_scene;
+    ///
+    /// let (sink, receiver) = ChannelSink::new();
+    /// let mut animator = Animator::new(fixture_scene(20, 10)).with_timestep(Duration::from_millis(100));
+    /// animator.subscribe(sink);
+    ///
+    /// // Renders a frame without updating the scene
+    /// animator.advance(Duration::from_millis(50));
+    /// assert_eq!(receiver.try_iter().count(), 1);
+    /// assert_eq!(animator.alpha(), 0.5);
+    /// ```
+    pub fn advance(&mut self, elapsed: Duration) {
+        if !self.paused {
+            self.accumulator += elapsed.mul_f64(self.time_scale);
+        }
+        while !self.timestep.is_zero() && self.accumulator >= self.timestep {
+            self.remember_previous();
+            self.simulate(self.timestep);
+            self.accumulator -= self.timestep;
+        }
+        self.render_interpolated(self.alpha());
+    }
+
+    /// How far the time not simulated yet is into the next timestep, between 0 and 1
+    pub fn alpha(&self) -> f64 {
+        if self.timestep.is_zero() {
+            return 1.0;
+        }
+        self.accumulator.as_secs_f64() / self.timestep.as_secs_f64()
+    }
+
+    /// Renders the scene as it was at the given point between the last two updates, 0 being
+    /// the state before and 1 the state after the last update, and hands the frame to every
+    /// sink. Objects added since the last update are drawn as they are.
+    pub fn render_interpolated(&mut self, alpha: f64) {
+        self.begin_frame();
+        if self.stopped {
+            return;
+        }
+        let interpolated = self
+            .scene
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(index, obj)| match self.previous.get(index) {
+                Some((location, orientation)) => {
+                    let current = &obj.shape.orientation;
+                    // Undo part of the last update on the already rotated points
+                    let correction = orientation
+                        .slerp(current, alpha)
+                        .multiply(&current.conjugate());
+                    let mut obj = obj.new_from_rotated(&correction);
+                    obj.location = Coord::new(
+                        location.x + (obj.location.x - location.x) * alpha,
+                        location.y + (obj.location.y - location.y) * alpha,
+                        location.z + (obj.location.z - location.z) * alpha,
+                    );
+                    obj
+                }
+                None => obj.clone(),
+            })
+            .collect();
+        let current = mem::replace(&mut self.scene.objects, interpolated);
+        let frame = self.render_scene();
+        self.scene.objects = current;
+        self.finish_frame(&frame);
+    }
+
+    /// Steps through the given amount of frames, waiting `frame_time` after each one or until
+    /// the next frame is due if a target frame rate is set.
+    /// Returns early once the animation is stopped.
+    pub fn run(&mut self, frames: usize) {
+        for _ in 0..frames {
+            if self.stopped {
+                break;
+            }
+            self.step();
+            self.wait_for_next_frame();
+        }
+        self.shut_down_if_stopped();
+    }
+
+    /// Same as run, until the animation is stopped
+    pub fn run_forever(&mut self) {
+        while !self.stopped {
+            self.run(1);
+        }
+    }
+
+    /// Same as run, but the next update of the scene is computed on another thread while the
+    /// current frame is rendered and handed to the sinks. The worker gets its own copy of
+    /// every object's points, which replace the points of the scene once both are done. If a
+    /// hook switched scenes or added or removed objects while the frame was made, the update
+    /// is thrown away and the scene is updated the next frame instead.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::animation::Animator;
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::demos;
+    ///
+    /// let mut pipelined = Animator::new(demos::tumbling_cube(40, 20).scene);
+    /// let (sink, pipelined_frames) = ChannelSink::new();
+    /// pipelined.subscribe(sink);
+    /// pipelined.run_pipelined(3);
+    ///
+    /// let mut stepped = Animator::new(demos::tumbling_cube(40, 20).scene);
+    /// let (sink, stepped_frames) = ChannelSink::new();
+    /// stepped.subscribe(sink);
+    /// stepped.run(3);
+    ///
+    /// let pipelined: Vec<_> = pipelined_frames.try_iter().map(|f| f.hash()).collect();
+    /// let stepped: Vec<_> = stepped_frames.try_iter().map(|f| f.hash()).collect();
+    /// assert_eq!(pipelined, stepped);
+    /// ```
+    pub fn run_pipelined(&mut self, frames: usize) {
+        for _ in 0..frames {
+            if self.stopped {
+                break;
+            }
+            if self.paused {
+                self.render_frame();
+                self.wait_for_next_frame();
+                continue;
+            }
+            // Hooks may switch scenes or add and remove objects, so they run before the jobs
+            // are made from the objects
+            self.begin_frame();
+            if self.stopped {
+                break;
+            }
+            let dt = self.scaled_timestep();
+            let layout = (self.current_scene(), self.scene.objects.len());
+            // Everything the worker needs is copied out, the scene itself stays on this thread
+            let jobs: Vec<(Vec<Point>, Rotator)> = self
+                .scene
+                .objects
+                .iter()
+                .map(|obj| {
+                    let rotation = self.rotation_over(&obj.rotation, dt);
+                    let rotator = match obj.spin_rotator(dt) {
+                        Some(spin) => spin.multiply(&rotation),
+                        None => rotation,
+                    };
+                    (obj.shape.points.clone(), rotator)
+                })
+                .collect();
+
+            let updated = thread::scope(|scope| {
+                let worker = scope.spawn(move || {
+                    jobs.into_iter()
+                        .map(|(mut points, rotator)| {
+                            for point in points.iter_mut() {
+                                point.rotate_self(&rotator);
+                            }
+                            (points, rotator)
+                        })
+                        .collect::<Vec<_>>()
+                });
+                let frame = self.render_scene();
+                self.finish_frame(&frame);
+                worker.join().expect("Update thread panicked")
+            });
+
+            // Post-frame hooks may have changed the scene as well, the points don't belong to
+            // its objects anymore then
+            if layout != (self.current_scene(), self.scene.objects.len()) {
+                self.wait_for_next_frame();
+                continue;
+            }
+            for (obj, (points, rotator)) in self.scene.objects.iter_mut().zip(updated) {
+                let shape = &mut obj.shape;
+                shape.points = points;
+                shape.orientation = rotator.multiply(&shape.orientation);
+                shape.bounds = shape.bounds.map(|bounds| bounds.rotated(&rotator));
+            }
+            // Spinning already happened on the worker
+            self.scene.run_updates(dt);
+            self.wait_for_next_frame();
+        }
+        self.shut_down_if_stopped();
+    }
+
+    /// Renders the given amount of frames, waiting between them like run, while the scene
+    /// is updated every `timestep` of real time, independently of how fast frames are rendered
+    pub fn run_fixed(&mut self, frames: usize) {
+        let mut last = Instant::now();
+        for _ in 0..frames {
+            if self.stopped {
+                break;
+            }
+            let now = Instant::now();
+            self.advance(now - last);
+            last = now;
+            self.wait_for_next_frame();
+        }
+        self.shut_down_if_stopped();
+    }
+}
+
+/// Sleeps until shortly before the deadline and spins for the rest
+fn sleep_until(deadline: Instant) {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if remaining > SPIN_MARGIN {
+        sleep(remaining - SPIN_MARGIN);
+    }
+    while Instant::now() < deadline {
+        hint::spin_loop();
+    }
+}
+
+/// Copy of the frame with the text written over the start of its first row
+fn with_overlay(frame: &SimpleTerminalBuffer, text: &str) -> SimpleTerminalBuffer {
+    let mut overlaid = frame.clone();
+    if overlaid.size_y == 0 {
+        return overlaid;
+    }
+    for (x, ch) in text.chars().take(overlaid.size_x).enumerate() {
+        overlaid.set(0, x, ch);
+    }
+    overlaid
+}
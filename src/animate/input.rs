@@ -0,0 +1,323 @@
+//! Keys and signals that animations can react to. Key handlers can be triggered by hand with
+//! `Animator::press`, with the terminal feature they are also read from the terminal.
+//! Signal sources hand out a value every frame, like the loudness of music, see
+//! `Animator::bind_signal`.
+
+use std::io::{self, BufRead, BufReader, Read};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+#[cfg(feature = "terminal")]
+use std::io::Write;
+#[cfg(feature = "terminal")]
+use std::panic;
+#[cfg(feature = "terminal")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "terminal")]
+use std::sync::Once;
+#[cfg(feature = "terminal")]
+use std::time::Duration;
+
+#[cfg(feature = "terminal")]
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+#[cfg(feature = "terminal")]
+use crossterm::{cursor, execute, terminal};
+
+/// Set while a guard has the terminal in raw mode
+#[cfg(feature = "terminal")]
+static RAW_MODE: AtomicBool = AtomicBool::new(false);
+/// Set while a TerminalGuard has switched to the alternate screen
+#[cfg(feature = "terminal")]
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+#[cfg(feature = "terminal")]
+static PANIC_HOOK: Once = Once::new();
+
+/// A key which can be bound on an Animator
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Tab,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Ctrl-C, which raw mode turns into a key press instead of a signal. Animators reading
+    /// keys from the terminal stop once it is pressed, after calling its handlers
+    Interrupt,
+}
+
+#[cfg(feature = "terminal")]
+impl Key {
+    fn from_code(code: KeyCode) -> Option<Self> {
+        match code {
+            KeyCode::Char(ch) => Some(Key::Char(ch)),
+            KeyCode::Enter => Some(Key::Enter),
+            KeyCode::Esc => Some(Key::Esc),
+            KeyCode::Tab => Some(Key::Tab),
+            KeyCode::Backspace => Some(Key::Backspace),
+            KeyCode::Up => Some(Key::Up),
+            KeyCode::Down => Some(Key::Down),
+            KeyCode::Left => Some(Key::Left),
+            KeyCode::Right => Some(Key::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Returns every key pressed in the terminal since the last call, without waiting for any
+#[cfg(feature = "terminal")]
+pub fn pending_keys() -> io::Result<Vec<Key>> {
+    let mut keys = Vec::new();
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match (key.code, key.modifiers.contains(KeyModifiers::CONTROL)) {
+                (KeyCode::Char('c'), true) => keys.push(Key::Interrupt),
+                (code, _) => keys.extend(Key::from_code(code)),
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// Puts the terminal into raw mode so single key presses can be read, and puts it back when
+/// dropped or when the program panics
+#[cfg(feature = "terminal")]
+pub struct RawModeGuard {
+    _private: (),
+}
+
+#[cfg(feature = "terminal")]
+impl RawModeGuard {
+    pub fn new() -> io::Result<Self> {
+        install_panic_hook();
+        terminal::enable_raw_mode()?;
+        RAW_MODE.store(true, Ordering::SeqCst);
+        Ok(Self { _private: () })
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Takes over the whole terminal for an animation: switches to the alternate screen, hides the
+/// cursor and turns on raw mode. Everything is put back when the guard is dropped, and also
+/// when the program panics, before the panic message is printed, so a crashing animation
+/// leaves a usable shell with the message readable in it.
+#[cfg(feature = "terminal")]
+pub struct TerminalGuard {
+    _private: (),
+}
+
+#[cfg(feature = "terminal")]
+impl TerminalGuard {
+    pub fn new() -> io::Result<Self> {
+        install_panic_hook();
+        // Flags are set first, so whatever part succeeded is undone if a later one fails
+        ALTERNATE_SCREEN.store(true, Ordering::SeqCst);
+        let guard = Self { _private: () };
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        RAW_MODE.store(true, Ordering::SeqCst);
+        terminal::enable_raw_mode()?;
+        Ok(guard)
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Undoes whatever RawModeGuard or TerminalGuard did to the terminal. Safe to call any number
+/// of times, and from anywhere, for example right before exiting the process
+#[cfg(feature = "terminal")]
+pub fn restore_terminal() {
+    // Nothing sensible left to do if any of this fails, the terminal is gone or unusable
+    if RAW_MODE.swap(false, Ordering::SeqCst) {
+        let _ = terminal::disable_raw_mode();
+    }
+    if ALTERNATE_SCREEN.swap(false, Ordering::SeqCst) {
+        let mut stdout = io::stdout();
+        let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = stdout.flush();
+    }
+}
+
+/// Restores the terminal before the panic message is printed. Without it the message would
+/// land on the alternate screen and vanish, and an aborting panic would skip the guards' drops
+#[cfg(feature = "terminal")]
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            previous(info);
+        }));
+    });
+}
+
+/// Something outside of the animation which hands out a value every frame, like the amplitude
+/// of audio. Bind one to an animator with Animator::bind_signal
+pub trait SignalSource {
+    /// Value for the coming frame, None if there is none
+    fn next_value(&mut self) -> Option<f64>;
+}
+
+/// Hands out recorded values one per frame, for example amplitudes prepared from an audio file.
+/// ### Example:
+/// ```
+/// use shapes_rs::animation::{SampleSignal, SignalSource};
+///
+/// let mut signal = SampleSignal::from_reader("0.5\n# a comment\n1 0.25".as_bytes()).unwrap();
+/// assert_eq!(signal.next_value(), Some(0.5));
+/// assert_eq!(signal.next_value(), Some(1.0));
+/// assert_eq!(signal.next_value(), Some(0.25));
+/// assert_eq!(signal.next_value(), None);
+///
+/// let mut looped = SampleSignal::new(vec![1.0, 2.0]).looped();
+/// let values: Vec<_> = (0..3).filter_map(|_| looped.next_value()).collect();
+/// assert_eq!(values, [1.0, 2.0, 1.0]);
+/// ```
+pub struct SampleSignal {
+    values: Vec<f64>,
+    next: usize,
+    looped: bool,
+}
+
+impl SampleSignal {
+    pub fn new(values: Vec<f64>) -> Self {
+        Self {
+            values,
+            next: 0,
+            looped: false,
+        }
+    }
+
+    /// Reads every number of the text, separated by whitespace. Anything after a `#` on a line
+    /// is a comment, other words which are not numbers are skipped
+    pub fn from_reader(reader: impl Read) -> io::Result<Self> {
+        let mut values = Vec::new();
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            let line = line.split('#').next().unwrap_or_default();
+            values.extend(line.split_whitespace().filter_map(|word| word.parse::<f64>().ok()));
+        }
+        Ok(Self::new(values))
+    }
+
+    /// Starts over from the first value once every value was handed out
+    pub fn looped(mut self) -> Self {
+        self.looped = true;
+        self
+    }
+}
+
+impl SignalSource for SampleSignal {
+    fn next_value(&mut self) -> Option<f64> {
+        if self.looped && self.next >= self.values.len() {
+            self.next = 0;
+        }
+        let value = self.values.get(self.next).copied();
+        self.next += 1;
+        value
+    }
+}
+
+/// Reads numbers from a live stream on a thread of its own, so frames never wait for it. Every
+/// frame gets the most recent value, or the mean of every value which arrived since the frame
+/// before if averaging is turned on. Frames keep the last value while nothing new arrives.
+/// ### Example:
+/// ```
+/// use std::io::Read;
+/// use std::sync::mpsc::{self, Sender};
+/// use shapes_rs::animation::{SignalSource, StreamSignal};
+///
+/// // Tells once the stream ran out, by then every value of it was read
+/// struct Watched(&'static [u8], Sender<()>);
+/// impl Read for Watched {
+///     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+///         let read = self.0.read(buf)?;
+///         if read == 0 {
+///             let _ = self.1.send(());
+///         }
+///         Ok(read)
+///     }
+/// }
+///
+/// // Usually stdin with something like an audio analyser piped in
+/// let (done, ended) = mpsc::channel();
+/// let mut signal = StreamSignal::spawn(Watched(b"0.2\n0.4\n", done)).averaged();
+/// ended.recv().unwrap();
+///
+/// assert!((signal.next_value().unwrap() - 0.3).abs() < 1e-9);
+/// assert!((signal.next_value().unwrap() - 0.3).abs() < 1e-9);
+/// ```
+pub struct StreamSignal {
+    receiver: Receiver<f64>,
+    last: Option<f64>,
+    averaged: bool,
+    pending: Vec<f64>,
+}
+
+impl StreamSignal {
+    /// Starts reading whitespace separated numbers from the reader, words which are not
+    /// numbers are skipped. The thread ends with the stream
+    pub fn spawn(reader: impl Read + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else {
+                    return;
+                };
+                for value in line.split_whitespace().filter_map(|word| word.parse().ok()) {
+                    if sender.send(value).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Self {
+            receiver,
+            last: None,
+            averaged: false,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Reads the values from standard input
+    pub fn stdin() -> Self {
+        Self::spawn(io::stdin())
+    }
+
+    /// Hands out the mean of the values which arrived since the frame before, instead of just
+    /// the latest one. Smooths out streams which are much faster than the frame rate
+    pub fn averaged(mut self) -> Self {
+        self.averaged = true;
+        self
+    }
+}
+
+impl SignalSource for StreamSignal {
+    fn next_value(&mut self) -> Option<f64> {
+        self.pending.extend(self.receiver.try_iter());
+        if !self.pending.is_empty() {
+            self.last = match self.averaged {
+                true => Some(self.pending.iter().sum::<f64>() / self.pending.len() as f64),
+                false => self.pending.last().copied(),
+            };
+            self.pending.clear();
+        }
+        self.last
+    }
+}
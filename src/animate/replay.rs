@@ -0,0 +1,230 @@
+//! Recording where every object of a scene is on every frame, and playing it back. A costly
+//! simulation can be run once and replayed deterministically, for example rendered again at a
+//! different size.
+//!
+//! Recordings are stored in a small binary format, all numbers little endian: the magic bytes
+//! `SHTF` and a u32 version, then for every frame its time as f64 and the number of objects as
+//! u32, followed by the location (3 f64), orientation quaternion (4 f64) and a visibility byte
+//! of every object.
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::basetype::Coord;
+use crate::binary::{invalid_data, read_f64, read_or_end, read_u32};
+use crate::scene::Scene;
+use crate::shape::rotator::Rotator;
+
+const MAGIC: &[u8; 4] = b"SHTF";
+const VERSION: u32 = 1;
+
+/// Transform of a single object on one frame
+#[derive(Clone)]
+pub struct ObjectTransform {
+    pub location: Coord,
+    /// How far the points are turned away from how they were generated
+    pub orientation: Rotator,
+    pub visible: bool,
+}
+
+/// Transforms of every object of a scene on one frame, in the order of the objects
+#[derive(Clone)]
+pub struct TransformFrame {
+    /// Clock of the scene
+    pub time: f64,
+    pub objects: Vec<ObjectTransform>,
+}
+
+/// Logs the transforms of every object of a scene, one frame per capture.
+/// ### Example:
+/// ```
+/// use shapes_rs::animation::{Animator, TransformPlayer, TransformRecorder};
+/// use shapes_rs::demos;
+///
+/// let mut animator = Animator::new(demos::tumbling_cube(40, 20).scene);
+/// let mut recorder = TransformRecorder::new();
+/// for _ in 0..5 {
+///     animator.render_frame();
+///     recorder.capture(&animator.scene);
+///     animator.update();
+/// }
+/// let mut file = Vec::new();
+/// recorder.write(&mut file).unwrap();
+///
+/// // Played back into a bigger scene which does not simulate anything itself
+/// let player = TransformPlayer::read(file.as_slice()).unwrap();
+/// assert_eq!(player.frame_count(), 5);
+/// let mut replay = Animator::new(demos::tumbling_cube(120, 60).scene);
+/// replay.play_transforms(player);
+/// replay.run(10);
+///
+/// let last = &recorder.frames()[4].objects[0].orientation;
+/// let replayed = &replay.scene.objects().next().unwrap().shape.orientation;
+/// assert_eq!((replayed.x, replayed.y, replayed.z, replayed.w), (last.x, last.y, last.z, last.w));
+/// assert!(replay.is_stopped());
+/// ```
+#[derive(Default)]
+pub struct TransformRecorder {
+    frames: Vec<TransformFrame>,
+}
+
+impl TransformRecorder {
+    pub fn new() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    /// Logs where every object of the scene is right now as the next frame
+    pub fn capture(&mut self, scene: &Scene) {
+        self.frames.push(TransformFrame {
+            time: scene.time,
+            objects: scene
+                .objects
+                .iter()
+                .map(|object| ObjectTransform {
+                    location: object.location,
+                    orientation: object.shape.orientation.clone(),
+                    visible: object.visible,
+                })
+                .collect(),
+        });
+    }
+
+    pub fn frames(&self) -> &[TransformFrame] {
+        &self.frames
+    }
+
+    /// Writes the recording in the format described in the module documentation
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        for frame in self.frames.iter() {
+            out.write_all(&frame.time.to_le_bytes())?;
+            out.write_all(&(frame.objects.len() as u32).to_le_bytes())?;
+            for object in frame.objects.iter() {
+                let (location, rotation) = (object.location, &object.orientation);
+                for value in [location.x, location.y, location.z] {
+                    out.write_all(&value.to_le_bytes())?;
+                }
+                for value in [rotation.x, rotation.y, rotation.z, rotation.w] {
+                    out.write_all(&value.to_le_bytes())?;
+                }
+                out.write_all(&[object.visible as u8])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the recording into a file, creating or truncating it
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+}
+
+/// Puts the objects of a scene where a recording says, one frame at a time. Replaces the
+/// simulation of the scene, see Animator::play_transforms
+pub struct TransformPlayer {
+    frames: Vec<TransformFrame>,
+    next: usize,
+}
+
+impl TransformPlayer {
+    pub fn new(frames: Vec<TransformFrame>) -> Self {
+        Self { frames, next: 0 }
+    }
+
+    /// Reads a recording written by TransformRecorder::write, fails with invalid data if it is
+    /// not one
+    pub fn read(reader: impl Read) -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC || read_u32(&mut reader)? != VERSION {
+            return Err(invalid_data("not a transform recording of a known version"));
+        }
+        let mut frames = Vec::new();
+        let mut time = [0; 8];
+        // Running out of data is only fine in between two frames
+        while read_or_end(&mut reader, &mut time)? {
+            let count = read_u32(&mut reader)?;
+            let objects = (0..count)
+                .map(|_| {
+                    let mut values = [0.0; 7];
+                    for value in values.iter_mut() {
+                        *value = read_f64(&mut reader)?;
+                    }
+                    let [x, y, z, rx, ry, rz, rw] = values;
+                    let mut visible = [0];
+                    reader.read_exact(&mut visible)?;
+                    Ok(ObjectTransform {
+                        location: Coord::new(x, y, z),
+                        orientation: Rotator {
+                            x: rx,
+                            y: ry,
+                            z: rz,
+                            w: rw,
+                        },
+                        visible: visible[0] != 0,
+                    })
+                })
+                .collect::<io::Result<_>>()?;
+            frames.push(TransformFrame {
+                time: f64::from_le_bytes(time),
+                objects,
+            });
+        }
+        Ok(Self::new(frames))
+    }
+
+    /// Reads a recording from a file
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read(File::open(path)?)
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Index of the frame applied next
+    pub fn position(&self) -> usize {
+        self.next
+    }
+
+    /// Makes the frame with the given index the next one, for rewinding or skipping ahead
+    pub fn seek(&mut self, frame: usize) {
+        self.next = frame.min(self.frames.len());
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.frames.len()
+    }
+
+    /// Puts the objects of the scene where the next frame says and sets the clock, false once
+    /// every frame was applied. Objects the frame knows nothing about are left alone
+    pub fn apply_next(&mut self, scene: &mut Scene) -> bool {
+        let Some(frame) = self.frames.get(self.next) else {
+            return false;
+        };
+        self.next += 1;
+        scene.time = frame.time;
+        for (object, transform) in scene.objects.iter_mut().zip(frame.objects.iter()) {
+            let correction = transform
+                .orientation
+                .multiply(&object.shape.orientation.conjugate());
+            object.shape.rotate_self(&correction);
+            // Set directly, so rounding errors don't pile up over a long recording
+            object.shape.orientation = transform.orientation.clone();
+            object.location = transform.location;
+            object.visible = transform.visible;
+        }
+        true
+    }
+}
+
+impl From<TransformRecorder> for TransformPlayer {
+    fn from(recorder: TransformRecorder) -> Self {
+        Self::new(recorder.frames)
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// Frames the statistics are computed over by default, a few seconds at usual frame rates
+const DEFAULT_WINDOW: usize = 120;
+
+/// Time between consecutive frames over the most recent frames, along with how many frames
+/// were dropped for missing the target frame rate. Printing it gives a single line fit for an
+/// overlay.
+/// ### Example:
+/// ```
+/// use std::time::Duration;
+/// use shapes_rs::animation::FrameStats;
+///
+/// let mut stats = FrameStats::new(4);
+/// for ms in [10, 10, 10, 30, 10] {
+///     stats.record(Duration::from_millis(ms));
+/// }
+///
+/// // The oldest frame was pushed out of the window
+/// assert_eq!(stats.frames(), 5);
+/// assert_eq!(stats.average(), Some(Duration::from_millis(15)));
+/// assert_eq!(stats.percentile(0.95), Some(Duration::from_millis(30)));
+/// assert_eq!(stats.to_string(), "15.0ms avg  30.0ms p95  66.7 fps  0 dropped");
+/// ```
+#[derive(Clone)]
+pub struct FrameStats {
+    window: usize,
+    /// Most recent frame last
+    samples: VecDeque<Duration>,
+    frames: usize,
+    dropped: usize,
+}
+
+impl FrameStats {
+    /// Keeps the frame times of the given amount of frames, at least one
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: VecDeque::new(),
+            frames: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Adds the time a frame took, forgetting the oldest one once the window is full
+    pub fn record(&mut self, frame_time: Duration) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(frame_time);
+        self.frames += 1;
+    }
+
+    /// Counts frames which were skipped because the animation fell behind its frame rate
+    pub fn record_dropped(&mut self, frames: usize) {
+        self.dropped += frames;
+    }
+
+    /// Forgets every frame recorded so far
+    pub fn reset(&mut self) {
+        *self = Self::new(self.window);
+    }
+
+    /// Frames recorded since the statistics were created or reset
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Frames dropped since the statistics were created or reset
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Time of the most recent frame
+    pub fn last(&self) -> Option<Duration> {
+        self.samples.back().copied()
+    }
+
+    /// Mean frame time over the window, None before the first frame
+    pub fn average(&self) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().sum::<Duration>() / self.samples.len() as u32)
+    }
+
+    /// Frame time which the given fraction of frames in the window stay at or below, 0.95
+    /// being the 95th percentile. None before the first frame
+    pub fn percentile(&self, fraction: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (fraction.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    /// Frames per second going by the average frame time
+    pub fn fps(&self) -> Option<f64> {
+        self.average()
+            .filter(|average| !average.is_zero())
+            .map(|average| 1.0 / average.as_secs_f64())
+    }
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl fmt::Display for FrameStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = |duration: Option<Duration>| duration.unwrap_or_default().as_secs_f64() * 1000.0;
+        write!(
+            f,
+            "{:.1}ms avg  {:.1}ms p95  {:.1} fps  {} dropped",
+            ms(self.average()),
+            ms(self.percentile(0.95)),
+            self.fps().unwrap_or(0.0),
+            self.dropped
+        )
+    }
+}
@@ -1,8 +1,10 @@
+use crate::error::{Result, ShapesError};
 use crate::{math::trig::get_distance, shape::rotator::Rotator};
-use core::panic;
-use std::{
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+use core::{
     f64::consts::PI,
-    ops::{Add, Div, Mul, Sub}
+    ops::{Add, Div, Mul, Neg, Sub}
 };
 
 /// Basic type which represents a given location in cartesian coordinates
@@ -63,7 +65,12 @@ impl Coord {
     /// assert!(my_coord_rotated.x  < 0.00001);
     /// ```
     pub fn to_vector(&self) -> Vector3D {
-        Vector3D::new(self.x, self.y, self.z)
+        // The origin is a valid coordinate, so this can't go through Vector3D::new
+        Vector3D {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
     }
 
     /// Multiplies all coordinates with a given scalar
@@ -114,7 +121,7 @@ impl Add for Coord {
 /// ### Example:
 /// ```
 /// use shapes_rs::base::{Angle3D, Angle};
-/// use std::f64::consts::PI;
+/// use core::f64::consts::PI;
 ///
 /// let angle = Angle3D::new(Angle::from_degree(180.0), Angle::from_radian(PI), Angle::default());
 ///
@@ -136,7 +143,7 @@ impl Angle3D {
     /// ### Example:
     /// ```
     /// use shapes_rs::base::{Angle3D, Angle};
-    /// use std::f64::consts::PI;
+    /// use core::f64::consts::PI;
     ///
     /// let my_angle = Angle3D::new(Angle::default(), Angle::from_degree(180.0), Angle::from_radian(1.0));
     /// let (roll, pitch, yaw) = my_angle.get();
@@ -172,6 +179,22 @@ impl Angle3D {
             yaw: Angle::from_radian(0.0),
         }
     }
+
+    /// Rotator which applies roll, pitch and yaw in this order, same as Rotator::from_global
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    ///
+    /// let angle = Angle3D::new(Angle::from_degree(10.0), Angle::from_degree(20.0), Angle::from_degree(30.0));
+    /// let back = angle.to_rotator().to_angle3d();
+    ///
+    /// assert!((back.roll.get() - angle.roll.get()).abs() < 1e-9);
+    /// assert!((back.pitch.get() - angle.pitch.get()).abs() < 1e-9);
+    /// assert!((back.yaw.get() - angle.yaw.get()).abs() < 1e-9);
+    /// ```
+    pub fn to_rotator(&self) -> Rotator {
+        Rotator::from_global(*self)
+    }
 }
 
 impl Add for Angle3D {
@@ -185,6 +208,50 @@ impl Add for Angle3D {
     }
 }
 
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+///
+/// let a = Angle3D::new(Angle::from_radian(1.0), Angle::from_radian(2.0), Angle::from_radian(3.0));
+/// let b = Angle3D::new(Angle::from_radian(0.5), Angle::from_radian(0.5), Angle::from_radian(0.5));
+///
+/// assert_eq!((a - b).yaw.get(), 2.5);
+/// assert_eq!((-a).pitch.get(), -2.0);
+/// assert_eq!((a * 2.0).roll.get(), 2.0);
+/// assert_eq!((0.5 * a).yaw.get(), 1.5);
+/// ```
+impl Sub for Angle3D {
+    type Output = Angle3D;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.roll - rhs.roll,
+            self.pitch - rhs.pitch,
+            self.yaw - rhs.yaw,
+        )
+    }
+}
+
+impl Neg for Angle3D {
+    type Output = Angle3D;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.roll, -self.pitch, -self.yaw)
+    }
+}
+
+impl Mul<f64> for Angle3D {
+    type Output = Angle3D;
+    fn mul(self, rhs: f64) -> Self::Output {
+        Angle3D::mul(&self, rhs)
+    }
+}
+
+impl Mul<Angle3D> for f64 {
+    type Output = Angle3D;
+    fn mul(self, rhs: Angle3D) -> Self::Output {
+        rhs.mul(self)
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct Vector3D {
     pub x: f64,
@@ -209,6 +276,7 @@ impl Vector3D {
     }
 
     /// Creates a new Vector from given parameters
+    /// Panics if all of them are zero, see try_new for a non panicking version
     /// ### Example:
     /// ```
     /// use shapes_rs::base::Vector3D;
@@ -220,10 +288,26 @@ impl Vector3D {
     /// ```
     ///
     pub fn new(x: f64, y: f64, z: f64) -> Self {
+        match Self::try_new(x, y, z) {
+            Ok(vector) => vector,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Creates a new Vector from given parameters, fails if all of them are zero
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::Vector3D;
+    /// use shapes_rs::ShapesError;
+    ///
+    /// assert!(Vector3D::try_new(0.0, 1.0, 0.0).is_ok());
+    /// assert_eq!(Vector3D::try_new(0.0, 0.0, 0.0).err(), Some(ShapesError::ZeroVector));
+    /// ```
+    pub fn try_new(x: f64, y: f64, z: f64) -> Result<Self> {
         if x == 0.0 && y == 0.0 && z == 0.0 {
-            panic!("Uhh, idk if this should be a panic but you cannot initiate a vector with all zeros")
+            return Err(ShapesError::ZeroVector);
         }
-        Self { x, y, z }
+        Ok(Self { x, y, z })
     }
 
     /// Returns a default vector, pointing towards X as the default direction.
@@ -305,11 +389,40 @@ impl Vector3D {
         x + y + z
     }
 
+    /// Mirrors the vector on a surface with the given normal, which has to be normalised
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::Vector3D;
+    ///
+    /// let falling = Vector3D::new(1.0, 0.0, -1.0);
+    /// let floor = Vector3D::new(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(falling.reflect(floor).get(), (1.0, 0.0, 1.0));
+    /// ```
+    pub fn reflect(&self, normal: Self) -> Self {
+        let d = 2.0 * self.dot(normal);
+        Self {
+            x: self.x - d * normal.x,
+            y: self.y - d * normal.y,
+            z: self.z - d * normal.z,
+        }
+    }
+
+    /// Returns the vector perpendicular to both, following the right hand rule
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::Vector3D;
+    ///
+    /// let x = Vector3D::new(1.0, 0.0, 0.0);
+    /// let y = Vector3D::new(0.0, 1.0, 0.0);
+    /// assert_eq!(x.cross(y).get(), (0.0, 0.0, 1.0));
+    /// assert_eq!(y.cross(x).get(), (0.0, 0.0, -1.0));
+    /// ```
     pub fn cross(&self, other: Self) -> Self {
         Self {
-            x: self.y * other.z,
-            y: self.z * other.x,
-            z: self.x * other.y
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
         }
     }
 
@@ -346,7 +459,7 @@ impl Vector3D {
     /// ### Example:
     /// ```
     /// use shapes_rs::base::Vector3D;
-    /// use std::f64::consts::PI;
+    /// use core::f64::consts::PI;
     ///
     /// let my_vec1 = Vector3D::new(3.0, 0.0, 0.0);
     /// let my_vec2 = Vector3D::new(0.0, 0.0, 3.0);
@@ -368,7 +481,7 @@ impl Vector3D {
     /// ### Example:
     /// ```
     /// use shapes_rs::base::Vector3D;
-    /// use std::f64::consts::PI;
+    /// use core::f64::consts::PI;
     ///
     /// let x_vector = Vector3D::new(3.0, 0.0, 0.0);
     /// let x_vector2 = Vector3D::new(9.0, 0.0, 0.0);
@@ -461,7 +574,7 @@ impl Angle {
 
     /// Converts the angle to float as degrees
     pub fn get_degrees(&self) -> f64 {
-        use std::f64::consts::PI;
+        use core::f64::consts::PI;
         self.angle * PI
     }
 
@@ -498,6 +611,13 @@ impl Add for Angle {
     }
 }
 
+impl Neg for Angle {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self { angle: -self.angle }
+    }
+}
+
 impl Sub for Angle {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
@@ -0,0 +1,133 @@
+//! Plays a scene script in the terminal, see the scripting module for the language.
+//! Runs until the duration is over, or with the signals feature until Ctrl-C is pressed.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use shapes_rs::buffer::*;
+use shapes_rs::scripting::Script;
+
+const USAGE: &str = "usage: shapes-play SCRIPT [--fps N] [--size WIDTHxHEIGHT] [--duration SECONDS] [--record FILE]
+
+  --fps N             frames per second, 30 by default. Scripts play at the same speed at
+                      any frame rate, they are updated 30 times a second
+  --size WxH          size of the frame in cells, overrides the size set by the script
+  --duration SECONDS  stop after this long instead of running until interrupted
+  --record FILE       save the frames, as an asciinema cast if FILE ends in .cast and as
+                      plain ANSI text otherwise";
+
+/// Updates every script makes in a second, whatever the frame rate
+const UPDATES_PER_SECOND: f64 = 30.0;
+/// Slowest frame rate, slower ones would stretch a frame over hours
+const MIN_FPS: f64 = 0.01;
+
+struct Options {
+    script: PathBuf,
+    fps: f64,
+    size: Option<(usize, usize)>,
+    duration: Option<f64>,
+    record: Option<PathBuf>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut script = None;
+    let mut options = Options {
+        script: PathBuf::new(),
+        fps: 30.0,
+        size: None,
+        duration: None,
+        record: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or(format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--fps" => {
+                options.fps = value("--fps")?
+                    .parse()
+                    .ok()
+                    .filter(|fps: &f64| *fps >= MIN_FPS && fps.is_finite())
+                    .ok_or("--fps has to be a number of at least 0.01")?;
+            }
+            "--size" => {
+                let size = value("--size")?;
+                let parsed = size
+                    .split_once('x')
+                    .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)));
+                options.size = Some(parsed.ok_or("--size has to look like 80x40")?);
+            }
+            "--duration" => {
+                options.duration = Some(
+                    value("--duration")?
+                        .parse()
+                        .ok()
+                        .filter(|secs: &f64| *secs >= 0.0 && secs.is_finite())
+                        .ok_or("--duration has to be a number of seconds")?,
+                );
+            }
+            "--record" => options.record = Some(PathBuf::from(value("--record")?)),
+            flag if flag.starts_with("--") => return Err(format!("unknown flag {}", flag)),
+            path if script.is_none() => script = Some(PathBuf::from(path)),
+            _ => return Err(String::from("only one script can be played")),
+        }
+    }
+    options.script = script.ok_or("no script given")?;
+    Ok(options)
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let script = match Script::load(&options.script) {
+        Ok(script) => script,
+        Err(err) => {
+            eprintln!("can't load {}: {}", options.script.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Frames go straight into the recorder as they are rendered, timed as they were shown
+    let mut recorder = Recorder::new();
+    let mut animator = script.animator();
+    if let Some((size_x, size_y)) = options.size {
+        animator.scene.set_buffer(SimpleTerminalBuffer::new(size_x, size_y));
+    }
+    animator.target_fps(options.fps);
+    // Every frame moves the scene by the time it is shown for, as a fraction of an update at
+    // the fixed update rate. Spins and script moves keep their speed at any frame rate
+    animator.timestep = Duration::from_secs_f64(1.0 / UPDATES_PER_SECOND);
+    animator.set_time_scale(UPDATES_PER_SECOND / options.fps);
+    animator.subscribe(StdoutSink::new());
+    if options.record.is_some() {
+        animator.subscribe(&mut recorder);
+    }
+    #[cfg(feature = "signals")]
+    if let Err(err) = animator.handle_signals() {
+        eprintln!("can't handle Ctrl-C, it will end the program right away: {}", err);
+    }
+
+    match options.duration {
+        Some(seconds) => animator.run((seconds * options.fps).round() as usize),
+        None => animator.run_forever(),
+    }
+
+    drop(animator);
+
+    if let Some(path) = options.record {
+        let saved = match path.extension().is_some_and(|extension| extension == "cast") {
+            true => recorder.save_cast(&path),
+            false => recorder.save_ansi(&path),
+        };
+        if let Err(err) = saved {
+            eprintln!("can't save the recording to {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,29 @@
+//! Reading the little endian binary formats of recordings and voxel files
+
+use std::io::{self, Read};
+
+pub(crate) fn invalid_data(reason: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+pub(crate) fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub(crate) fn read_f64(reader: &mut impl Read) -> io::Result<f64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(f64::from_le_bytes(bytes))
+}
+
+/// Fills the buffer, false if the reader was already at its end
+pub(crate) fn read_or_end(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<bool> {
+    let read = reader.read(buffer)?;
+    if read == 0 {
+        return Ok(false);
+    }
+    reader.read_exact(&mut buffer[read..])?;
+    Ok(true)
+}
@@ -1,7 +1,33 @@
 mod camera;
+mod clip_plane;
+mod label;
 mod light;
+mod material;
 mod object;
+mod orbit;
+mod shake;
+mod skeleton;
+mod tween;
 
 pub use camera::Camera;
+pub use camera::FovAxis;
+pub use clip_plane::ClipPlane;
+pub use label::Label;
+pub use light::Falloff;
 pub use light::Light3D;
-pub use object::Object;
\ No newline at end of file
+pub use light::LightUpdate;
+pub use light::SpotLight;
+pub use material::Environment;
+pub use material::Material;
+pub use material::Shading;
+pub use object::Object;
+pub use object::ObjectUpdate;
+pub use orbit::Orbit;
+pub use shake::Shake;
+pub use skeleton::Joint;
+pub use skeleton::Skeleton;
+pub use skeleton::Skin;
+pub use tween::Lerp;
+pub use tween::Repeat;
+pub use tween::RunningTween;
+pub use tween::Tween;
\ No newline at end of file
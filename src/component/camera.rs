@@ -1,21 +1,64 @@
 use crate::basetype::*;
+use crate::shape::rotator::Rotator;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+/// Which side of the buffer the field of view of a camera spans
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum FovAxis {
+    /// Fov spans the height, wider buffers show more to the sides
+    #[default]
+    Vertical,
+    /// Fov spans the width, taller buffers show more above and below
+    Horizontal,
+}
+
 #[derive(Clone)]
 pub struct Camera {
     pub coord: Coord,
     pub facing: Vector3D,
-    /// Vertical fov
+    /// Field of view across the side given by fov_axis
     pub fov: Angle,
+    pub fov_axis: FovAxis,
+    /// Height of a cell divided by its width. Cells are treated as square by default, most
+    /// terminal fonts are about twice as high as they are wide
+    pub cell_aspect: f64,
+    /// Only objects on at least one of these layers are rendered, every layer by default
+    pub layers: u32,
 }
 
 impl Camera {
-    /// NOTE: Fov is in radians!!! Also is vertical FOV.
+    /// NOTE: Fov is in radians!!! Also is vertical FOV unless changed with with_fov_axis.
     pub fn new(coord: Coord, facing: Vector3D, fov: Angle) -> Self {
         let normalised_facing = facing.normalise();
         Self {
             coord,
             facing: normalised_facing,
             fov,
+            fov_axis: FovAxis::Vertical,
+            cell_aspect: 1.0,
+            layers: u32::MAX,
+        }
+    }
+
+    /// Sets which side of the buffer the field of view spans
+    pub fn with_fov_axis(mut self, fov_axis: FovAxis) -> Self {
+        self.fov_axis = fov_axis;
+        self
+    }
+
+    /// Sets the height of a cell divided by its width, so shapes keep their proportions on
+    /// screen. Non positive aspects are ignored
+    pub fn with_cell_aspect(mut self, cell_aspect: f64) -> Self {
+        if cell_aspect > 0.0 {
+            self.cell_aspect = cell_aspect;
         }
+        self
+    }
+
+    /// Sets the layers this camera renders, one bit per layer
+    pub fn with_layers(mut self, layers: u32) -> Self {
+        self.layers = layers;
+        self
     }
 
     /// For now, since roll is not implemented yet it will simply return the angle function of its
@@ -23,4 +66,142 @@ impl Camera {
     pub fn angle(&self) -> Angle3D {
         self.facing.angle()
     }
+
+    /// Rotation which transforms world directions into camera space, where the camera looks
+    /// towards positive X
+    pub fn view_rotator(&self) -> Rotator {
+        Rotator::from_global(self.angle().mul(-1.0))
+    }
+
+    /// Narrows the field of view so everything appears the given factor larger, factors below
+    /// 1 zoom out. Non positive factors are ignored
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::Camera;
+    ///
+    /// let mut camera = Camera::new(Coord::default(), Vector3D::new(1.0, 0.0, 0.0), Angle::from_degree(90.0));
+    /// let (before, _) = camera.projection_scale(80, 40);
+    /// camera.zoom(2.0);
+    /// let (after, _) = camera.projection_scale(80, 40);
+    /// assert!((after - 2.0 * before).abs() < 1e-9);
+    /// ```
+    pub fn zoom(&mut self, factor: f64) {
+        if factor <= 0.0 {
+            return;
+        }
+        let half = f64::atan(f64::tan(self.fov.get() / 2.0) / factor);
+        self.fov = Angle::from_radian(2.0 * half);
+    }
+
+    /// Direction which is to the right on screen
+    pub fn right(&self) -> Vector3D {
+        self.view_rotator().conjugate().apply(Vector3D::new(0.0, 1.0, 0.0))
+    }
+
+    /// Direction which is up on screen
+    pub fn up(&self) -> Vector3D {
+        self.view_rotator().conjugate().apply(Vector3D::new(0.0, 0.0, 1.0))
+    }
+
+    /// Moves the camera forwards along the direction it faces, backwards for negative distances
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::Camera;
+    ///
+    /// let mut camera = Camera::new(Coord::default(), Vector3D::new(1.0, 0.0, 0.0), Angle::from_degree(60.0));
+    /// camera.dolly(10.0);
+    /// camera.truck(2.0);
+    /// camera.pedestal(-3.0);
+    ///
+    /// let Coord { x, y, z } = camera.coord;
+    /// assert!((x - 10.0).abs() < 1e-9 && (y - 2.0).abs() < 1e-9 && (z + 3.0).abs() < 1e-9);
+    /// ```
+    pub fn dolly(&mut self, distance: f64) {
+        self.coord = self.coord + self.facing.normalise().mul(distance).as_coord();
+    }
+
+    /// Moves the camera sideways, to the right on screen for positive distances
+    pub fn truck(&mut self, distance: f64) {
+        self.coord = self.coord + self.right().mul(distance).as_coord();
+    }
+
+    /// Moves the camera up on screen, down for negative distances
+    pub fn pedestal(&mut self, distance: f64) {
+        self.coord = self.coord + self.up().mul(distance).as_coord();
+    }
+
+    /// Distance of the projection plane from the camera, in cells, for a buffer with the given
+    /// height. Only correct for a vertical field of view and square cells, see projection_scale
+    pub fn projection_distance(&self, size_y: usize) -> f64 {
+        1.0 / f64::tan(self.fov.get() / 2.0) * ((size_y as f64) / 2.0)
+    }
+
+    /// Cells per unit of a direction's sideways and upwards offset, over its forward distance,
+    /// as (horizontal, vertical) for a buffer of the given size
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::{Camera, FovAxis};
+    ///
+    /// let camera = Camera::new(Coord::default(), Vector3D::new(1.0, 0.0, 0.0), Angle::from_degree(90.0))
+    ///     .with_cell_aspect(2.0);
+    /// let (x, y) = camera.projection_scale(80, 40);
+    /// // Directions 45° up land on the top edge, a cell being half as wide as high
+    /// assert!((y - 20.0).abs() < 1e-9);
+    /// assert!((x - 40.0).abs() < 1e-9);
+    ///
+    /// // Directions 45° to the side land on the side edge instead
+    /// let (x, _) = camera.with_fov_axis(FovAxis::Horizontal).projection_scale(80, 40);
+    /// assert!((x - 40.0).abs() < 1e-9);
+    /// ```
+    pub fn projection_scale(&self, size_x: usize, size_y: usize) -> (f64, f64) {
+        let tan = f64::tan(self.fov.get() / 2.0);
+        match self.fov_axis {
+            FovAxis::Vertical => {
+                let scale_y = size_y as f64 / 2.0 / tan;
+                (scale_y * self.cell_aspect, scale_y)
+            }
+            FovAxis::Horizontal => {
+                let scale_x = size_x as f64 / 2.0 / tan;
+                (scale_x, scale_x / self.cell_aspect)
+            }
+        }
+    }
+
+    /// Returns the origin and direction of the viewing ray going through the center of the
+    /// given cell of a buffer with the given size (x, y)
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::Camera;
+    ///
+    /// let camera = Camera::new(Coord::default(), Vector3D::new(0.0, 1.0, 0.0), Angle::from_degree(90.0));
+    ///
+    /// let (origin, direction) = camera.ray_through(50, 25, (100, 50));
+    /// assert_eq!(origin.y, 0.0);
+    /// assert!((direction.y - 1.0).abs() < 0.01);
+    ///
+    /// // Cells further left look further to the left
+    /// let (_, left) = camera.ray_through(0, 25, (100, 50));
+    /// assert!(left.x > 0.5);
+    /// ```
+    pub fn ray_through(
+        &self,
+        buffer_x: usize,
+        buffer_y: usize,
+        buffer_size: (usize, usize),
+    ) -> (Coord, Vector3D) {
+        let (size_x, size_y) = buffer_size;
+        let (scale_x, scale_y) = self.projection_scale(size_x, size_y);
+        let cam_y = (buffer_x as f64 + 0.5 - size_x as f64 / 2.0) / scale_x;
+        let cam_z = -(buffer_y as f64 + 0.5 - size_y as f64 / 2.0) / scale_y;
+        let direction = self
+            .view_rotator()
+            .conjugate()
+            .apply(Vector3D::new(1.0, cam_y, cam_z))
+            .normalise();
+        (self.coord, direction)
+    }
 }
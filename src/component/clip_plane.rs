@@ -0,0 +1,41 @@
+use crate::basetype::*;
+
+/// Plane which removes everything on one of its sides from the rendered image.
+/// The plane consists of all points `p` where `normal.dot(p) == offset`, points on the side the
+/// normal points towards are kept.
+#[derive(Clone)]
+pub struct ClipPlane {
+    pub normal: Vector3D,
+    pub offset: f64,
+}
+
+impl ClipPlane {
+    pub fn new(normal: Vector3D, offset: f64) -> Self {
+        Self {
+            normal: normal.normalise(),
+            offset,
+        }
+    }
+
+    /// Whether a point in world coordinates is removed by this plane
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::ClipPlane;
+    ///
+    /// // Removes everything below z = 5
+    /// let plane = ClipPlane::new(Vector3D::new(0.0, 0.0, 1.0), 5.0);
+    ///
+    /// assert!(plane.clips(Coord::new(0.0, 0.0, 4.0)));
+    /// assert!(!plane.clips(Coord::new(0.0, 0.0, 6.0)));
+    /// ```
+    pub fn clips(&self, point: Coord) -> bool {
+        self.distance(point) < 0.0
+    }
+
+    /// Signed distance of a point to the plane, positive on the side that is kept
+    pub fn distance(&self, point: Coord) -> f64 {
+        let (x, y, z) = point.get();
+        self.normal.x * x + self.normal.y * y + self.normal.z * z - self.offset
+    }
+}
@@ -0,0 +1,35 @@
+use alloc::string::String;
+
+use crate::basetype::Coord;
+use crate::scene::ObjectId;
+
+/// Text drawn next to an object after the scene is rendered, see Scene::add_label
+#[derive(Clone)]
+pub struct Label {
+    /// Object the label follows
+    pub anchor: ObjectId,
+    /// Lines of text, each centered on the anchor point
+    pub text: String,
+    /// Where the anchor point lies relative to the object's location, in world coordinates
+    pub offset: Coord,
+    /// Characters covered by something closer to the camera than the anchor point are left
+    /// out. Labels without it are always on top
+    pub depth_test: bool,
+}
+
+impl Label {
+    pub fn new(anchor: ObjectId, text: &str, offset: Coord) -> Self {
+        Self {
+            anchor,
+            text: String::from(text),
+            offset,
+            depth_test: true,
+        }
+    }
+
+    /// Sets whether objects in front of the anchor point cover the label
+    pub fn with_depth_test(mut self, depth_test: bool) -> Self {
+        self.depth_test = depth_test;
+        self
+    }
+}
@@ -1,11 +1,216 @@
 use crate::basetype::*;
+use crate::out::color::Rgb;
+use crate::scene::ObjectId;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Behavior of a single light, called with the light, the time of the scene's clock and the
+/// time since the last update, both in seconds
+pub type LightUpdate = fn(light: &mut Light3D, t: f64, dt: f64);
+
 #[derive(Clone)]
 pub struct Light3D {
     pub coord: Coord,
+    /// Only used by color renderers, white by default
+    pub color: Rgb,
+    /// Restricts the light to a cone, shines in every direction if not set
+    pub spot: Option<SpotLight>,
+    /// Object the light rides on and where on it, relative to its location and turned along
+    /// with it. The scene moves the light there whenever it is advanced
+    pub parent: Option<(ObjectId, Coord)>,
+    /// Called whenever the scene is advanced, before the light is moved to its parent
+    pub update: Option<LightUpdate>,
+    /// How the light gets weaker with distance, not at all by default
+    pub falloff: Falloff,
+}
+
+/// How the light of a point light gets weaker the farther it has to travel
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Falloff {
+    /// Lights reach everything at full strength
+    #[default]
+    None,
+    /// Fades out evenly, reaching nothing farther away than the range
+    Linear { range: f64 },
+    /// Falls off with the square of the distance like real light. Full strength up to the
+    /// radius, and faded out smoothly so nothing farther away than the range is lit
+    InverseSquare { radius: f64, range: f64 },
+}
+
+impl Falloff {
+    /// How much of the light is left after the given distance, between 0 and 1
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::components::Falloff;
+    ///
+    /// let linear = Falloff::Linear { range: 100.0 };
+    /// assert_eq!(linear.attenuation(25.0), 0.75);
+    /// assert_eq!(linear.attenuation(150.0), 0.0);
+    ///
+    /// let inverse_square = Falloff::InverseSquare { radius: 10.0, range: 1000.0 };
+    /// assert!((inverse_square.attenuation(5.0) - 1.0).abs() < 1e-6);
+    /// assert!((inverse_square.attenuation(20.0) - 0.25).abs() < 1e-6);
+    /// assert_eq!(inverse_square.attenuation(1000.0), 0.0);
+    /// assert_eq!(Falloff::None.attenuation(1e9), 1.0);
+    /// ```
+    pub fn attenuation(&self, distance: f64) -> f64 {
+        match *self {
+            Falloff::None => 1.0,
+            Falloff::Linear { range } => (1.0 - distance / range).clamp(0.0, 1.0),
+            Falloff::InverseSquare { radius, range } => {
+                let falloff = match distance > radius {
+                    true => (radius / distance).powi(2),
+                    false => 1.0,
+                };
+                // Without the window the light would never quite reach zero
+                let window = (1.0 - (distance / range).powi(4)).clamp(0.0, 1.0).powi(2);
+                falloff * window
+            }
+        }
+    }
 }
 
 impl Light3D {
     pub fn new(coord: Coord) -> Self {
-        Light3D { coord }
+        Light3D {
+            coord,
+            color: Rgb::new(255, 255, 255),
+            spot: None,
+            parent: None,
+            update: None,
+            falloff: Falloff::None,
+        }
+    }
+
+    /// Creates a light which only shines inside of a cone around the given direction.
+    /// Points within the inner angle are fully lit, between the inner and outer angle the
+    /// light fades out.
+    pub fn new_spot(coord: Coord, direction: Vector3D, inner: Angle, outer: Angle) -> Self {
+        Light3D {
+            spot: Some(SpotLight::new(direction, inner, outer)),
+            ..Self::new(coord)
+        }
+    }
+
+    /// Sets the color of the light
+    pub fn with_color(mut self, color: Rgb) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets how the light gets weaker with distance
+    pub fn with_falloff(mut self, falloff: Falloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Lets the light ride on the object, at the given offset from its location. The offset
+    /// turns along with the object, so a light on a spinning object circles around it.
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::Light3D;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// let cube = scene.add_object(
+    ///     Object::new(Coord::new(100.0, 0.0, 0.0), &cubegen, Rotator::identity())
+    ///         .with_spin(Vector3D::new(1.0, 0.0, 0.0), Angle::from_degree(90.0)),
+    /// );
+    /// scene.clear_lights();
+    /// scene.add_light(Light3D::new(Coord::new(0.0, 0.0, 50.0)).attached_to(cube));
+    /// assert_eq!(scene.lights().next().unwrap().coord.get(), (100.0, 0.0, 50.0));
+    ///
+    /// // A quarter turn later the light has gone around with the cube
+    /// scene.advance(Duration::from_secs(1));
+    /// let (x, y, z) = scene.lights().next().unwrap().coord.get();
+    /// assert!((x - 100.0).abs() < 1e-9 && (y + 50.0).abs() < 1e-9 && z.abs() < 1e-9);
+    /// ```
+    pub fn attached_to(mut self, object: ObjectId) -> Self {
+        self.parent = Some((object, self.coord));
+        self
+    }
+
+    /// Sets the function the light is updated with whenever the scene is advanced, for
+    /// example to let it orbit the scene
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::Light3D;
+    ///
+    /// let mut light = Light3D::new(Coord::new(100.0, 0.0, 0.0)).with_update(|light, t, _| {
+    ///     light.coord = Coord::new(100.0 * t.cos(), 100.0 * t.sin(), 0.0);
+    /// });
+    ///
+    /// // Called by the scene with its time, here a quarter of the way around
+    /// let update = light.update.unwrap();
+    /// update(&mut light, std::f64::consts::FRAC_PI_2, 0.1);
+    /// let (x, y, _) = light.coord.get();
+    /// assert!(x.abs() < 1e-6 && (y - 100.0).abs() < 1e-6);
+    /// ```
+    pub fn with_update(mut self, update: LightUpdate) -> Self {
+        self.update = Some(update);
+        self
+    }
+
+    /// How much of the light reaches a point in the given direction from the light, between 0
+    /// and 1. Always 1 for lights that are not spot lights.
+    pub fn cone_factor(&self, to_point: Vector3D) -> f64 {
+        match &self.spot {
+            Some(spot) => spot.factor(to_point),
+            None => 1.0,
+        }
+    }
+}
+
+/// Cone of a spot light. Both angles are measured from the direction to the edge of the cone.
+#[derive(Clone)]
+pub struct SpotLight {
+    pub direction: Vector3D,
+    pub inner: Angle,
+    pub outer: Angle,
+}
+
+impl SpotLight {
+    pub fn new(direction: Vector3D, inner: Angle, outer: Angle) -> Self {
+        Self {
+            direction: direction.normalise(),
+            inner,
+            outer,
+        }
+    }
+
+    /// Returns 1 inside of the inner cone, 0 outside of the outer cone and fades linearly in
+    /// between
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::SpotLight;
+    ///
+    /// let spot = SpotLight::new(
+    ///     Vector3D::new(1.0, 0.0, 0.0),
+    ///     Angle::from_degree(10.0),
+    ///     Angle::from_degree(20.0),
+    /// );
+    ///
+    /// assert_eq!(spot.factor(Vector3D::new(5.0, 0.0, 0.0)), 1.0);
+    /// assert_eq!(spot.factor(Vector3D::new(0.0, 5.0, 0.0)), 0.0);
+    /// assert_eq!(spot.factor(Vector3D::new(-5.0, 0.0, 0.0)), 0.0);
+    /// ```
+    pub fn factor(&self, to_point: Vector3D) -> f64 {
+        let cos_angle = self.direction.dot(to_point.normalise());
+        let cos_inner = f64::cos(self.inner.get());
+        let cos_outer = f64::cos(self.outer.get());
+        if cos_angle >= cos_inner {
+            return 1.0;
+        }
+        if cos_angle <= cos_outer || cos_inner <= cos_outer {
+            return 0.0;
+        }
+        (cos_angle - cos_outer) / (cos_inner - cos_outer)
     }
 }
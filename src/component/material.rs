@@ -0,0 +1,208 @@
+use crate::basetype::Vector3D;
+use crate::out::color::{Colormap, Rgb};
+
+/// How the luminance of an object's points is decided
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Shading {
+    /// Shaded by the lights of the scene
+    #[default]
+    Lit,
+    /// Ignores lights and always renders with the given luminance between 0 and 1
+    Emissive(f64),
+    /// Ignores lights and always renders with the given character at full brightness
+    Glyph(char),
+    /// Ignores lights and mirrors the environment, which is sampled in the direction the view
+    /// is reflected in. Looks like chrome, especially on moving shapes
+    Reflective(Environment),
+}
+
+/// What reflective materials mirror, gives a color for every direction
+#[derive(Copy, Clone, Debug)]
+pub enum Environment {
+    /// Blends from the ground color straight down to the sky color straight up, +Z being up
+    Gradient { sky: Rgb, ground: Rgb },
+    /// Any function of the normalised direction
+    Custom(fn(Vector3D) -> Rgb),
+}
+
+impl PartialEq for Environment {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Environment::Gradient { sky, ground },
+                Environment::Gradient {
+                    sky: other_sky,
+                    ground: other_ground,
+                },
+            ) => sky == other_sky && ground == other_ground,
+            // Same function, going by its address. core::ptr::fn_addr_eq needs a newer compiler
+            (Environment::Custom(a), Environment::Custom(b)) => *a as usize == *b as usize,
+            _ => false,
+        }
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Environment::Gradient {
+            sky: Rgb::new(200, 220, 255),
+            ground: Rgb::new(20, 15, 10),
+        }
+    }
+}
+
+impl Environment {
+    /// Color seen in the given direction
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::Vector3D;
+    /// use shapes_rs::color::Rgb;
+    /// use shapes_rs::components::Environment;
+    ///
+    /// let white = Rgb::new(255, 255, 255);
+    /// let black = Rgb::new(0, 0, 0);
+    /// let env = Environment::Gradient { sky: white, ground: black };
+    ///
+    /// assert_eq!(env.sample(Vector3D::new(0.0, 0.0, 5.0)), white);
+    /// assert_eq!(env.sample(Vector3D::new(1.0, 0.0, 0.0)), Rgb::new(128, 128, 128));
+    /// ```
+    pub fn sample(&self, direction: Vector3D) -> Rgb {
+        let direction = direction.normalise();
+        match self {
+            Environment::Gradient { sky, ground } => ground.lerp(*sky, (direction.z + 1.0) / 2.0),
+            Environment::Custom(environment) => environment(direction),
+        }
+    }
+}
+
+/// Describes how an object is shaded by the renderer
+#[derive(Clone)]
+pub struct Material {
+    pub shading: Shading,
+    /// Between 0 (invisible) and 1 (solid). Partially transparent objects are drawn with a
+    /// dithering pattern, letting whatever is behind them show through the skipped cells.
+    pub opacity: f64,
+    /// Colormap used by color renderers, falls back to the scene's colormap if not set
+    pub colormap: Option<Colormap>,
+    /// Color of the surface itself. When set, color renderers light the object with the colors
+    /// of the lights instead of using a colormap
+    pub base_color: Option<Rgb>,
+    /// Lights both sides of the surface alike, for open surfaces which would otherwise be dark
+    /// from behind, where their normals point away from the light
+    pub two_sided: bool,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            shading: Shading::Lit,
+            opacity: 1.0,
+            colormap: None,
+            base_color: None,
+            two_sided: false,
+        }
+    }
+}
+
+impl Material {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Material which ignores lights and is drawn at the given luminance
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::components::*;
+    ///
+    /// let marker = Material::emissive(1.0);
+    /// assert_eq!(marker.fixed_luminance(), Some(1.0));
+    /// assert_eq!(Material::new().fixed_luminance(), None);
+    /// ```
+    pub fn emissive(luminance: f64) -> Self {
+        Self::new().with_shading(Shading::Emissive(luminance))
+    }
+
+    /// Material which mirrors the given environment
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let torusgen = TorusGenerator::new(10.0, 50.0);
+    /// let chrome = Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity())
+    ///     .with_material(Material::reflective(Environment::default()));
+    /// let mut scene = fixture_scene(60, 30);
+    /// scene.add_object(chrome);
+    ///
+    /// let before = scene.render();
+    /// // Lights don't matter, only where the camera looks from
+    /// scene.clear_lights();
+    /// assert_eq!(scene.render().hash(), before.hash());
+    /// scene.camera_mut().coord = Coord::new(0.0, 0.0, 20.0);
+    /// assert_ne!(scene.render().hash(), before.hash());
+    /// ```
+    pub fn reflective(environment: Environment) -> Self {
+        Self::new().with_shading(Shading::Reflective(environment))
+    }
+
+    pub fn with_shading(mut self, shading: Shading) -> Self {
+        self.shading = shading;
+        self
+    }
+
+    /// Lights the back of the surface like its front
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::*;
+    /// use shapes_rs::generators::ReliefGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// // A flat sheet facing the camera, lit from behind
+    /// let sheet = ReliefGenerator::new(20, 10, &[128; 200]).unwrap().with_cell_size(2.0, 2.0);
+    /// let lit_chars = |material: Material| {
+    ///     let mut scene = fixture_scene(40, 20);
+    ///     scene.clear_lights();
+    ///     scene.add_light(Light3D::new(Coord::new(500.0, 0.0, 0.0)));
+    ///     let mut object = Object::new(Coord::new(60.0, 0.0, 0.0), &sheet, Rotator::identity());
+    ///     object.material = material;
+    ///     scene.add_object(object);
+    ///     scene.render().to_string().matches('@').count()
+    /// };
+    ///
+    /// assert_eq!(lit_chars(Material::new()), 0);
+    /// assert!(lit_chars(Material::new().with_two_sided(true)) > 0);
+    /// ```
+    pub fn with_two_sided(mut self, two_sided: bool) -> Self {
+        self.two_sided = two_sided;
+        self
+    }
+
+    /// The luminance the object is always drawn with, if it ignores lights
+    pub fn fixed_luminance(&self) -> Option<f64> {
+        match self.shading {
+            Shading::Lit | Shading::Reflective(_) => None,
+            Shading::Emissive(luminance) => Some(luminance.clamp(0.0, 1.0)),
+            Shading::Glyph(_) => Some(1.0),
+        }
+    }
+
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = Some(colormap);
+        self
+    }
+
+    pub fn with_base_color(mut self, color: Rgb) -> Self {
+        self.base_color = Some(color);
+        self
+    }
+}
@@ -1,13 +1,54 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
+
 use crate::basetype::*;
+use crate::component::{Material, Orbit, Skin};
+use crate::error::{Result, ShapesError};
+use crate::scene::snapshot::ObjectSnapshot;
+use crate::shape::sdf::Sdf;
 use crate::shape::shape_base::Shape;
 use crate::shape::shape_gen::ShapeGen;
+use crate::shape::texture::Texture;
 use crate::shape::rotator::Rotator;
 
+/// Behavior of a single object, called with the object, the time of the scene's clock and the
+/// time since the last update, both in seconds
+pub type ObjectUpdate = fn(object: &mut Object<'_>, t: f64, dt: f64);
+
+/// Shape placed in a scene. More fields may be added, so build objects with Object::new or
+/// Object::new_with_shape and the with_ functions
 #[derive(Clone)]
+#[non_exhaustive]
 pub struct Object<'a> {
     pub location: Coord,
     pub shape: Shape<'a>,
     pub rotation: Rotator,
+    pub material: Material,
+    /// Distance function used by raymarching renderers, the object is invisible to them without
+    /// one
+    pub sdf: Option<&'a dyn Sdf>,
+    /// Sampled with the UV coordinates of the points, see Texture
+    pub texture: Option<&'a dyn Texture>,
+    /// Axis and rate per second the object keeps rotating around, see spin
+    pub spin: Option<(Vector3D, Angle)>,
+    /// Path the scene keeps the object on, see with_orbit
+    pub orbit: Option<Orbit>,
+    /// Skeleton posing the points of the shape, see with_skin
+    pub skin: Option<Skin>,
+    /// Hidden objects stay in the scene but are skipped by renderers
+    pub visible: bool,
+    /// Layers the object is on, one bit per layer. Cameras only render objects sharing at least
+    /// one layer with them. Objects start out on layer 0
+    pub layer_mask: u32,
+    /// Called whenever the scene is advanced, after the object has spun
+    pub update: Option<ObjectUpdate>,
+    /// Objects without a depth test are drawn over every object which has one, like overlays
+    pub depth_test: bool,
+    /// Lets scripts and tools refer to the object by name, see Scene::find_by_name
+    pub name: Option<String>,
+    /// Groups the object belongs to, see Scene::query
+    pub tags: Vec<String>,
 }
 
 impl<'a> Object<'a> {
@@ -17,6 +58,18 @@ impl<'a> Object<'a> {
             location,
             shape,
             rotation,
+            material: Material::default(),
+            sdf: None,
+            texture: None,
+            spin: None,
+            orbit: None,
+            skin: None,
+            visible: true,
+            layer_mask: 1,
+            update: None,
+            depth_test: true,
+            name: None,
+            tags: Vec::new(),
         }
     }
     pub fn new_with_shape(location: Coord, shape: Shape<'a>, rotation: Rotator) -> Self {
@@ -24,25 +77,313 @@ impl<'a> Object<'a> {
             location,
             shape,
             rotation,
+            material: Material::default(),
+            sdf: None,
+            texture: None,
+            spin: None,
+            orbit: None,
+            skin: None,
+            visible: true,
+            layer_mask: 1,
+            update: None,
+            depth_test: true,
+            name: None,
+            tags: Vec::new(),
         }
     }
     pub fn new_from_rotate_around(&self, around: Coord, rotator: &Rotator) -> Self {
         let loc_sub = self.location - around;
         let rotated_loc = loc_sub.to_vector().rotate(rotator).as_coord();
         let new_loc = self.location + rotated_loc;
-        Self::new_with_shape(new_loc, self.shape.rotate(rotator), self.rotation.clone())
+        self.derive(new_loc, self.shape.rotate(rotator))
     }
     pub fn new_from_rotated(&self, rotator: &Rotator) -> Self {
-        Self::new_with_shape(
-            self.location,
-            self.shape.rotate(rotator),
-            self.rotation.clone(),
-        )
+        self.derive(self.location, self.shape.rotate(rotator))
     }
     pub fn new_from_self_rotate(&self) -> Self {
-        Self::new_with_shape(self.location, self.shape.rotate(&self.rotation), self.rotation.clone())
+        self.derive(self.location, self.shape.rotate(&self.rotation))
+    }
+    /// Copy of this object with a different location and shape, everything else is kept
+    fn derive(&self, location: Coord, shape: Shape<'a>) -> Self {
+        Self {
+            location,
+            shape,
+            rotation: self.rotation.clone(),
+            material: self.material.clone(),
+            sdf: self.sdf,
+            texture: self.texture,
+            spin: self.spin,
+            orbit: self.orbit,
+            skin: self.skin.clone(),
+            visible: self.visible,
+            layer_mask: self.layer_mask,
+            update: self.update,
+            depth_test: self.depth_test,
+            name: self.name.clone(),
+            tags: self.tags.clone(),
+        }
+    }
+    /// Names the object, names are not checked to be unique
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Adds the object to a group, tagging it twice does nothing
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has_tag(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Same as add_tag, for building objects
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.add_tag(tag);
+        self
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|own| own != tag);
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|own| own == tag)
+    }
+
+    /// Sets the distance function used by raymarching renderers
+    pub fn with_sdf(mut self, sdf: &'a dyn Sdf) -> Self {
+        self.sdf = Some(sdf);
+        self
+    }
+
+    /// Sets the texture drawn on the object. Points without UV coordinates are left as they are
+    pub fn with_texture(mut self, texture: &'a dyn Texture) -> Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Distance from a point in world coordinates to the surface of the object's distance
+    /// function, if it has one
+    pub fn distance(&self, point: Coord) -> Option<f64> {
+        ObjectSnapshot::new(self).distance(point)
+    }
+
+    /// Moves the object onto the given layers, one bit per layer
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// const GIZMOS: u32 = 1 << 1;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut scene = fixture_scene(30, 15);
+    /// let gizmo = scene.add_object(
+    ///     Object::new(Coord::new(30.0, 0.0, 0.0), &cubegen, Rotator::identity()).with_layers(GIZMOS),
+    /// );
+    /// assert!(scene.render().buffer.iter().any(|ch| *ch != ' '));
+    ///
+    /// // Leave out the gizmos
+    /// scene.camera_mut().layers = !GIZMOS;
+    /// assert!(scene.render().buffer.iter().all(|ch| *ch == ' '));
+    ///
+    /// // Or hide a single object
+    /// scene.camera_mut().layers = u32::MAX;
+    /// scene.object_mut(gizmo).unwrap().visible = false;
+    /// assert!(scene.render().buffer.iter().all(|ch| *ch == ' '));
+    /// ```
+    pub fn with_layers(mut self, layer_mask: u32) -> Self {
+        self.layer_mask = layer_mask;
+        self
+    }
+
+    /// Sets whether the object is hidden behind closer objects. Objects without a depth test
+    /// are drawn on top of all others, among each other they still hide their back sides
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::{Material, Shading};
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let front = CubeGenerator::new(20.0, 20.0, 20.0);
+    /// let back = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let marker = Material::new().with_shading(Shading::Glyph('X'));
+    ///
+    /// let mut scene = fixture_scene(40, 20);
+    /// scene.add_object(Object::new(Coord::new(40.0, 0.0, 0.0), &front, Rotator::identity()));
+    /// let hidden = scene.add_object(
+    ///     Object::new(Coord::new(80.0, 0.0, 0.0), &back, Rotator::identity()).with_material(marker),
+    /// );
+    /// assert!(!scene.render().to_string().contains('X'));
+    ///
+    /// scene.object_mut(hidden).unwrap().depth_test = false;
+    /// assert!(scene.render().to_string().contains('X'));
+    /// ```
+    pub fn with_depth_test(mut self, depth_test: bool) -> Self {
+        self.depth_test = depth_test;
+        self
+    }
+
+    /// Replaces the material of the object
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
     }
     pub fn apply_rotation(&mut self) {
         self.shape.rotate_self(&self.rotation);
     }
+
+    /// Keeps the object rotating around the given axis through its location at the given rate
+    /// per second, whenever the scene is advanced
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let torusgen = TorusGenerator::new(10.0, 50.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// let torus = scene.add_object(Object::new(Coord::new(70.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+    ///
+    /// // Rotate the torus at 45° per second
+    /// scene.object_mut(torus).unwrap().spin(Vector3D::new(0.0, 0.0, 1.0), Angle::from_degree(45.0));
+    ///
+    /// scene.advance(Duration::from_secs(2));
+    /// let orientation = &scene.object(torus).unwrap().shape.orientation;
+    /// let facing = orientation.apply(Vector3D::new(1.0, 0.0, 0.0));
+    /// assert!((facing.y - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn spin(&mut self, axis: Vector3D, rate: Angle) {
+        self.spin = Some((axis, rate));
+    }
+
+    /// Same as spin, for building objects
+    pub fn with_spin(mut self, axis: Vector3D, rate: Angle) -> Self {
+        self.spin(axis, rate);
+        self
+    }
+
+    /// Puts the object on the given path. Whenever the scene is advanced, the object is moved to
+    /// where on the path it is at the scene's time, before its update function is called.
+    /// Objects going around another object have to be added to the scene after it
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::Orbit;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(4.0, 4.0, 4.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// let planet = scene.add_object(
+    ///     Object::new(Coord::default(), &cubegen, Rotator::identity())
+    ///         .with_orbit(Orbit::circular(Coord::new(200.0, 0.0, 0.0), 80.0, 8.0)),
+    /// );
+    /// let moon = scene.add_object(
+    ///     Object::new(Coord::default(), &cubegen, Rotator::identity())
+    ///         .with_orbit(Orbit::around(planet, 10.0, 2.0)),
+    /// );
+    /// assert_eq!(scene.object(planet).unwrap().location.x, 280.0);
+    ///
+    /// // A quarter around the sun, the moon went around the planet once
+    /// scene.advance(Duration::from_secs(2));
+    /// let planet = scene.object(planet).unwrap().location;
+    /// let moon = scene.object(moon).unwrap().location;
+    /// assert!((planet.x - 200.0).abs() < 1e-9 && (planet.y - 80.0).abs() < 1e-9);
+    /// assert!((moon.x - planet.x - 10.0).abs() < 1e-9);
+    /// ```
+    pub fn with_orbit(mut self, orbit: Orbit) -> Self {
+        self.orbit = Some(orbit);
+        self
+    }
+
+    /// Replaces the points of the shape with the points bound to the skin, in the current
+    /// pose of its skeleton. Pose it further with pose. Rotations applied to the shape are
+    /// kept, and the bounds are dropped as posing moves the points past them
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::{Skeleton, Skin};
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// // Stick person with a body, and an arm hanging from the shoulder
+    /// let skeleton = Skeleton::new()
+    ///     .with_joint("hips", None, Coord::default())
+    ///     .and_then(|s| s.with_joint("shoulder", Some("hips"), Coord::new(0.0, 3.0, 12.0)))
+    ///     .and_then(|s| s.with_joint("hand", Some("shoulder"), Coord::new(0.0, 0.0, -8.0)))
+    ///     .unwrap();
+    /// let body = CubeGenerator::new(2.0, 4.0, 12.0);
+    /// let arm = CubeGenerator::new(1.0, 1.0, 8.0);
+    /// let mut skin = Skin::new(skeleton);
+    /// let body = Shape::new(&body).translate(Coord::new(0.0, 0.0, 6.0));
+    /// skin.bind("hips", &body.points).unwrap();
+    /// let arm = Shape::new(&arm).translate(Coord::new(0.0, 4.0, 8.0));
+    /// skin.bind("shoulder", &arm.points).unwrap();
+    ///
+    /// let mut person = Object::new_with_shape(Coord::new(80.0, 0.0, -6.0), Shape::empty(), Rotator::identity())
+    ///     .with_skin(skin);
+    /// // Waves with the hand up in the air
+    /// person.pose("shoulder", Rotator::new(Vector3D::new(-1.0, 0.0, 0.0), 2.8)).unwrap();
+    /// let points = &person.shape.points;
+    /// assert!(points.iter().any(|p| p.rel_coord.z > 16.0));
+    /// assert!(points.iter().all(|p| p.rel_coord.z <= 21.0));
+    /// ```
+    pub fn with_skin(mut self, skin: Skin) -> Self {
+        self.skin = Some(skin);
+        self.update_skin();
+        self
+    }
+
+    /// Turns the joint of the skin's skeleton away from its rest pose and moves the points
+    /// along. Fails if the object has no skin or its skeleton no joint with the name
+    pub fn pose(&mut self, joint: &str, rotation: Rotator) -> Result<()> {
+        let Some(skin) = self.skin.as_mut() else {
+            return Err(ShapesError::InvalidParameter("object has no skin"));
+        };
+        skin.skeleton.pose(joint, rotation)?;
+        self.update_skin();
+        Ok(())
+    }
+
+    /// Puts the points of the skin into the shape, in the current pose
+    fn update_skin(&mut self) {
+        let Some(skin) = self.skin.as_ref() else {
+            return;
+        };
+        let orientation = self.shape.orientation.clone();
+        self.shape.points = skin.posed();
+        for point in self.shape.points.iter_mut() {
+            point.rotate_self(&orientation);
+        }
+        self.shape.bounds = None;
+    }
+
+    /// Sets the function called whenever the scene is advanced, see Scene::set_update
+    pub fn with_update(mut self, update: ObjectUpdate) -> Self {
+        self.update = Some(update);
+        self
+    }
+
+    /// Rotates the object by as much as it spins in the given time
+    pub fn advance_spin(&mut self, dt: Duration) {
+        if let Some(rotator) = self.spin_rotator(dt) {
+            self.shape.rotate_self(&rotator);
+        }
+    }
+
+    /// Rotation the object spins by in the given time, if it spins at all
+    pub fn spin_rotator(&self, dt: Duration) -> Option<Rotator> {
+        self.spin
+            .map(|(axis, rate)| Rotator::new(axis, rate.get() * dt.as_secs_f64()))
+    }
 }
@@ -0,0 +1,116 @@
+use core::f64::consts::PI;
+
+use crate::basetype::*;
+use crate::scene::ObjectId;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Circular or elliptical path an object keeps going around, taking the period to go around
+/// once. The scene moves the object along it whenever it is advanced, see Object::with_orbit.
+/// Objects go around at an even rate, counterclockwise when looking against the normal of the
+/// orbit's plane.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::components::Orbit;
+///
+/// // Once around every 4 seconds, in the X-Y plane
+/// let orbit = Orbit::circular(Coord::new(100.0, 0.0, 0.0), 50.0, 4.0);
+///
+/// let start = orbit.position(0.0);
+/// let quarter = orbit.position(1.0);
+/// assert_eq!((start.x, start.y), (150.0, 0.0));
+/// assert!((quarter.x - 100.0).abs() < 1e-9 && (quarter.y - 50.0).abs() < 1e-9);
+///
+/// // Without a usable period the object stays where it starts
+/// let stuck = Orbit::circular(Coord::new(100.0, 0.0, 0.0), 50.0, 0.0);
+/// assert_eq!(stuck.position(1.0).x, 150.0);
+/// ```
+#[derive(Copy, Clone)]
+pub struct Orbit {
+    /// Middle of the path. Relative to the location of the parent if there is one
+    pub center: Coord,
+    /// Object the orbit goes around, moving along with it
+    pub parent: Option<ObjectId>,
+    /// Radius along the first axis of the plane, where the object is at the start
+    pub semi_major: f64,
+    /// Radius along the second axis of the plane, the same as semi_major for circles
+    pub semi_minor: f64,
+    /// Seconds it takes to go around once. Objects on orbits with a period which isn't
+    /// positive and finite stay at their phase
+    pub period: f64,
+    /// How far along the path the object is at the start, in radians
+    pub phase: f64,
+    /// Normal of the plane the path lies in, Z by default
+    pub normal: Vector3D,
+}
+
+impl Orbit {
+    /// Circle with the given radius around the center, lying in the X-Y plane
+    pub fn circular(center: Coord, radius: f64, period: f64) -> Self {
+        Self::elliptical(center, radius, radius, period)
+    }
+
+    /// Ellipse with the given radii around the center, lying in the X-Y plane. The center is
+    /// the middle of the ellipse, not one of its foci
+    pub fn elliptical(center: Coord, semi_major: f64, semi_minor: f64, period: f64) -> Self {
+        Self {
+            center,
+            parent: None,
+            semi_major,
+            semi_minor,
+            period,
+            phase: 0.0,
+            normal: Vector3D::new(0.0, 0.0, 1.0),
+        }
+    }
+
+    /// Circle with the given radius around another object of the scene, carried along
+    /// wherever that object goes. For moons going around planets
+    pub fn around(parent: ObjectId, radius: f64, period: f64) -> Self {
+        Self::circular(Coord::default(), radius, period).with_parent(parent)
+    }
+
+    /// Makes the center relative to the given object
+    pub fn with_parent(mut self, parent: ObjectId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Starts the object the given angle along the path
+    pub fn with_phase(mut self, phase: Angle) -> Self {
+        self.phase = phase.get();
+        self
+    }
+
+    /// Tilts the path into the plane with the given normal
+    pub fn with_plane(mut self, normal: Vector3D) -> Self {
+        self.normal = normal.normalise();
+        self
+    }
+
+    /// Where on the path the object is at the given time of the scene's clock, relative to the
+    /// parent if there is one
+    pub fn position(&self, t: f64) -> Coord {
+        let turns = match self.period > 0.0 && self.period.is_finite() {
+            true => t / self.period,
+            false => 0.0,
+        };
+        let angle = self.phase + 2.0 * PI * turns;
+        let (first, second) = self.plane_axes();
+        let offset = first.mul(self.semi_major * f64::cos(angle))
+            + second.mul(self.semi_minor * f64::sin(angle));
+        self.center + offset.as_coord()
+    }
+
+    /// Two axes spanning the plane of the path. X and Y for the default plane
+    fn plane_axes(&self) -> (Vector3D, Vector3D) {
+        let normal = self.normal;
+        let helper = match normal.x.abs() < 0.9 {
+            true => Vector3D::new(1.0, 0.0, 0.0),
+            false => Vector3D::new(0.0, 1.0, 0.0),
+        };
+        let first = (helper + normal.mul(-helper.dot(normal))).normalise();
+        (first, normal.cross(first))
+    }
+}
@@ -0,0 +1,88 @@
+use crate::basetype::Vector3D;
+use crate::math::random::Rng;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Random jitter which dies down over time, for impacts and explosions. Moves the camera or an
+/// object away from where it is by up to the amplitude along every axis, changing direction
+/// about `frequency` times a second and fading out at the rate of `decay`. The scene runs it
+/// off its clock, see Scene::shake_camera and Scene::shake_object.
+/// ### Example:
+/// ```
+/// use shapes_rs::components::Shake;
+///
+/// let shake = Shake::new(2.0, 10.0, 3.0);
+/// let early = shake.offset(0.25);
+/// assert!(early.x.abs() <= 2.0 && early.y.abs() <= 2.0 && early.z.abs() <= 2.0);
+///
+/// // Smooth, close moments are moved by about as much
+/// let next = shake.offset(0.251);
+/// assert!((next.x - early.x).abs() < 0.1);
+///
+/// // Same seed, same shake
+/// assert_eq!(shake.offset(0.5).x, Shake::new(2.0, 10.0, 3.0).offset(0.5).x);
+/// assert!(shake.is_over(5.0) && !shake.is_over(0.5));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shake {
+    /// Largest distance along a single axis, in world units
+    pub amplitude: f64,
+    /// How often the direction changes, per second
+    pub frequency: f64,
+    /// How fast the shake fades out, the amplitude is divided by e every 1 / decay seconds.
+    /// A decay of zero never fades out, the shake has to be stopped then
+    pub decay: f64,
+    /// Picks which random movement is made
+    pub seed: u64,
+}
+
+impl Shake {
+    pub fn new(amplitude: f64, frequency: f64, decay: f64) -> Self {
+        Self {
+            amplitude,
+            frequency,
+            decay,
+            seed: 0,
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Largest distance along a single axis at the given number of seconds after the start
+    pub fn envelope(&self, elapsed: f64) -> f64 {
+        self.amplitude * f64::exp(-self.decay * elapsed.max(0.0))
+    }
+
+    /// Whether the shake has faded out to less than a thousandth of its amplitude
+    pub fn is_over(&self, elapsed: f64) -> bool {
+        self.decay > 0.0 && self.envelope(elapsed) < self.amplitude.abs() * 1e-3
+    }
+
+    /// How far the shake moves its target at the given number of seconds after the start
+    pub fn offset(&self, elapsed: f64) -> Vector3D {
+        let envelope = self.envelope(elapsed);
+        let t = elapsed.max(0.0) * self.frequency;
+        Vector3D {
+            x: envelope * self.noise(0, t),
+            y: envelope * self.noise(1, t),
+            z: envelope * self.noise(2, t),
+        }
+    }
+
+    /// Smooth noise between -1 and 1 for one axis. Random values at every whole number are
+    /// blended into each other in between
+    fn noise(&self, axis: u64, t: f64) -> f64 {
+        let value = |knot: u64| {
+            let seed = self.seed ^ axis.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ knot;
+            Rng::new(seed).range(-1.0, 1.0)
+        };
+        let knot = f64::floor(t);
+        let blend = t - knot;
+        let blend = blend * blend * (3.0 - 2.0 * blend);
+        let (a, b) = (value(knot as u64), value(knot as u64 + 1));
+        a + (b - a) * blend
+    }
+}
@@ -0,0 +1,287 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::basetype::Coord;
+use crate::error::{Result, ShapesError};
+use crate::shape::rotator::Rotator;
+use crate::shape::shape_base::Point;
+
+/// Single joint of a skeleton, see Skeleton
+#[derive(Clone)]
+pub struct Joint {
+    pub name: String,
+    /// Index of the joint this one hangs off, None for roots
+    pub parent: Option<usize>,
+    /// Where the joint is relative to its parent in the rest pose, or to the origin of the
+    /// skeleton for roots
+    pub offset: Coord,
+    /// How far the joint is turned away from the rest pose, turning every joint below it
+    /// along with it
+    pub rotation: Rotator,
+}
+
+/// Hierarchy of named joints for posing articulated figures. Every joint sits at an offset
+/// from its parent and turns around itself, carrying the joints below it along. Joints are
+/// added parents first, in the rest pose with no joint turned.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::components::Skeleton;
+///
+/// let mut arm = Skeleton::new();
+/// arm.add_joint("shoulder", None, Coord::new(0.0, 0.0, 10.0)).unwrap();
+/// arm.add_joint("elbow", Some("shoulder"), Coord::new(0.0, 5.0, 0.0)).unwrap();
+/// arm.add_joint("hand", Some("elbow"), Coord::new(0.0, 5.0, 0.0)).unwrap();
+///
+/// // Raising the upper arm raises the hand with it
+/// let forward = Vector3D::new(1.0, 0.0, 0.0);
+/// arm.pose("shoulder", Rotator::new(forward, Angle::from_degree(90.0).get())).unwrap();
+/// let (hand, _) = arm.transform(arm.joint("hand").unwrap());
+/// assert!(hand.y.abs() < 1e-9 && (hand.z - 20.0).abs() < 1e-9);
+///
+/// // Bending the elbow back turns the forearm out to the side again
+/// arm.pose("elbow", Rotator::new(forward, Angle::from_degree(-90.0).get())).unwrap();
+/// let (hand, _) = arm.transform(arm.joint("hand").unwrap());
+/// assert!((hand.y - 5.0).abs() < 1e-9 && (hand.z - 15.0).abs() < 1e-9);
+///
+/// assert!(arm.pose("tail", Rotator::identity()).is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct Skeleton {
+    joints: Vec<Joint>,
+}
+
+impl Skeleton {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a joint at the given offset from its parent and returns its index. Fails if the
+    /// name is taken or there is no joint with the parent's name
+    pub fn add_joint(
+        &mut self,
+        name: impl Into<String>,
+        parent: Option<&str>,
+        offset: Coord,
+    ) -> Result<usize> {
+        let name = name.into();
+        if self.joint(&name).is_some() {
+            return Err(ShapesError::InvalidParameter(
+                "skeleton already has a joint with that name",
+            ));
+        }
+        let parent = match parent {
+            Some(parent) => Some(self.find(parent)?),
+            None => None,
+        };
+        self.joints.push(Joint {
+            name,
+            parent,
+            offset,
+            rotation: Rotator::identity(),
+        });
+        Ok(self.joints.len() - 1)
+    }
+
+    /// Same as add_joint, for building skeletons
+    pub fn with_joint(
+        mut self,
+        name: impl Into<String>,
+        parent: Option<&str>,
+        offset: Coord,
+    ) -> Result<Self> {
+        self.add_joint(name, parent, offset)?;
+        Ok(self)
+    }
+
+    /// Index of the joint with the given name
+    pub fn joint(&self, name: &str) -> Option<usize> {
+        self.joints.iter().position(|joint| joint.name == name)
+    }
+
+    pub fn joints(&self) -> &[Joint] {
+        &self.joints
+    }
+
+    /// Turns the joint away from the rest pose by the given rotation, replacing how it was
+    /// turned before. Fails if there is no joint with the name
+    pub fn pose(&mut self, name: &str, rotation: Rotator) -> Result<()> {
+        let index = self.find(name)?;
+        self.joints[index].rotation = rotation;
+        Ok(())
+    }
+
+    /// Puts every joint back into the rest pose
+    pub fn reset_pose(&mut self) {
+        for joint in self.joints.iter_mut() {
+            joint.rotation = Rotator::identity();
+        }
+    }
+
+    /// Position and rotation of the joint in the current pose, relative to the origin of the
+    /// skeleton
+    pub fn transform(&self, joint: usize) -> (Coord, Rotator) {
+        self.transforms().swap_remove(joint)
+    }
+
+    /// Where the joint is in the rest pose, relative to the origin of the skeleton
+    pub fn rest_position(&self, joint: usize) -> Coord {
+        let mut position = self.joints[joint].offset;
+        let mut parent = self.joints[joint].parent;
+        while let Some(index) = parent {
+            position = position + self.joints[index].offset;
+            parent = self.joints[index].parent;
+        }
+        position
+    }
+
+    /// Position and rotation of every joint in the current pose. Parents come before their
+    /// children, so each one is worked out from one already known
+    fn transforms(&self) -> Vec<(Coord, Rotator)> {
+        let mut transforms: Vec<(Coord, Rotator)> = Vec::with_capacity(self.joints.len());
+        for joint in self.joints.iter() {
+            let transform = match joint.parent {
+                Some(parent) => {
+                    let (position, rotation) = &transforms[parent];
+                    let offset = rotation.apply(joint.offset.to_vector()).as_coord();
+                    (*position + offset, rotation.multiply(&joint.rotation))
+                }
+                None => (joint.offset, joint.rotation.clone()),
+            };
+            transforms.push(transform);
+        }
+        transforms
+    }
+
+    fn find(&self, name: &str) -> Result<usize> {
+        self.joint(name)
+            .ok_or(ShapesError::InvalidParameter("skeleton has no joint with that name"))
+    }
+}
+
+/// Points bound to the joints of a skeleton, following them as the skeleton is posed. Points
+/// are given in the rest pose and relative to the origin of the skeleton. Give it to an
+/// object with Object::with_skin to pose its shape.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::components::{Skeleton, Skin};
+///
+/// let skeleton = Skeleton::new()
+///     .with_joint("hip", None, Coord::default())
+///     .and_then(|s| s.with_joint("knee", Some("hip"), Coord::new(0.0, 0.0, -10.0)))
+///     .unwrap();
+/// let up = Vector3D::new(0.0, 0.0, 1.0);
+/// let thigh: Vec<Point> = (0..10).map(|z| Point::new(Coord::new(0.0, 0.0, -z as f64), up)).collect();
+///
+/// let mut skin = Skin::new(skeleton);
+/// skin.bind_nearest(&thigh);
+/// // Kicking forward turns the thigh along with the hip
+/// let side = Vector3D::new(0.0, 1.0, 0.0);
+/// skin.skeleton.pose("hip", Rotator::new(side, -core::f64::consts::FRAC_PI_2)).unwrap();
+/// let posed = skin.posed();
+/// assert!((posed[9].rel_coord.x - 9.0).abs() < 1e-9 && posed[9].rel_coord.z.abs() < 1e-9);
+/// assert!((posed[9].normal.x + 1.0).abs() < 1e-9);
+/// ```
+#[derive(Clone)]
+pub struct Skin {
+    pub skeleton: Skeleton,
+    /// Bound points in the rest pose
+    rest: Vec<Point>,
+    /// Joint of every bound point
+    bindings: Vec<usize>,
+}
+
+impl Skin {
+    pub fn new(skeleton: Skeleton) -> Self {
+        Self {
+            skeleton,
+            rest: Vec::new(),
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Binds the points to the joint with the given name, for figures put together from
+    /// parts generated on their own. Fails if there is no joint with the name
+    pub fn bind(&mut self, joint: &str, points: &[Point]) -> Result<()> {
+        let joint = self.skeleton.find(joint)?;
+        self.rest.extend_from_slice(points);
+        self.bindings.resize(self.rest.len(), joint);
+        Ok(())
+    }
+
+    /// Binds every point to the bone it is closest to. Bones run from a joint to each of its
+    /// children and turn with that joint, joints without children are bones of their own.
+    /// Does nothing without joints
+    pub fn bind_nearest(&mut self, points: &[Point]) {
+        let joints = self.skeleton.joints();
+        if joints.is_empty() {
+            return;
+        }
+        let rest: Vec<Coord> = (0..joints.len()).map(|i| self.skeleton.rest_position(i)).collect();
+        // Start and end of every bone along with the joint turning it
+        let mut bones: Vec<(usize, Coord, Coord)> = Vec::new();
+        for (index, joint) in joints.iter().enumerate() {
+            if let Some(parent) = joint.parent {
+                bones.push((parent, rest[parent], rest[index]));
+            }
+            if !joints.iter().any(|other| other.parent == Some(index)) {
+                bones.push((index, rest[index], rest[index]));
+            }
+        }
+        for point in points {
+            let closest = bones
+                .iter()
+                .map(|(joint, start, end)| {
+                    (*joint, segment_distance(point.rel_coord, *start, *end))
+                })
+                .fold((0, f64::INFINITY), |best, bone| match bone.1 < best.1 {
+                    true => bone,
+                    false => best,
+                });
+            self.rest.push(*point);
+            self.bindings.push(closest.0);
+        }
+    }
+
+    /// Number of bound points
+    pub fn len(&self) -> usize {
+        self.rest.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// Every bound point moved along with its joint into the current pose of the skeleton
+    pub fn posed(&self) -> Vec<Point> {
+        let transforms = self.skeleton.transforms();
+        let rest: Vec<Coord> = (0..transforms.len())
+            .map(|joint| self.skeleton.rest_position(joint))
+            .collect();
+        self.rest
+            .iter()
+            .zip(self.bindings.iter())
+            .map(|(point, joint)| {
+                let (position, rotation) = &transforms[*joint];
+                let mut posed = *point;
+                let local = (point.rel_coord - rest[*joint]).to_vector();
+                posed.rel_coord = *position + rotation.apply(local).as_coord();
+                posed.normal = rotation.apply(point.normal);
+                posed
+            })
+            .collect()
+    }
+}
+
+/// Distance from the point to the closest point on the segment between start and end
+fn segment_distance(point: Coord, start: Coord, end: Coord) -> f64 {
+    let along = (end - start).to_vector();
+    let length = along.dot(along);
+    let to_point = (point - start).to_vector();
+    let t = match length > 0.0 {
+        true => (to_point.dot(along) / length).clamp(0.0, 1.0),
+        false => 0.0,
+    };
+    (to_point + along.mul(-t)).magnitude()
+}
@@ -0,0 +1,206 @@
+use alloc::boxed::Box;
+
+use crate::basetype::{Angle, Coord, Vector3D};
+use crate::math::easing::Easing;
+use crate::out::color::Rgb;
+use crate::scene::Scene;
+use crate::shape::rotator::Rotator;
+use crate::shape::shape_base::Point;
+
+/// Values which can be blended into each other, see Tween
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::components::Lerp;
+///
+/// assert_eq!(2.0_f64.lerp(&4.0, 0.25), 2.5);
+/// let middle = Coord::new(0.0, 0.0, 0.0).lerp(&Coord::new(10.0, 20.0, 0.0), 0.5);
+/// assert_eq!((middle.x, middle.y), (5.0, 10.0));
+/// ```
+pub trait Lerp: Sized {
+    /// Value the given fraction of the way from this one to the other, this one at 0 and the
+    /// other at 1
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for Coord {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        *self + (*other - *self).mul(t)
+    }
+}
+
+/// Blends every component on its own, the result is not normalised
+impl Lerp for Vector3D {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Vector3D {
+            x: self.x.lerp(&other.x, t),
+            y: self.y.lerp(&other.y, t),
+            z: self.z.lerp(&other.z, t),
+        }
+    }
+}
+
+impl Lerp for Angle {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Angle::from_radian(self.get().lerp(&other.get(), t))
+    }
+}
+
+/// Turns along the shorter way at an even rate, see Rotator::slerp
+impl Lerp for Rotator {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self.slerp(other, t)
+    }
+}
+
+impl Lerp for Rgb {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Rgb::lerp(self, *other, t)
+    }
+}
+
+/// Blends everything about the points, see Shape::morph. Normals are normalised again, and
+/// whatever only one of them has is taken from the closer one
+impl Lerp for Point {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let closer = match t < 0.5 {
+            true => self,
+            false => other,
+        };
+        let normal = self.normal.lerp(&other.normal, t);
+        let uv = match (self.uv, other.uv) {
+            (Some(a), Some(b)) => Some((a.0.lerp(&b.0, t), a.1.lerp(&b.1, t))),
+            _ => closer.uv,
+        };
+        let color = match (self.color, other.color) {
+            (Some(a), Some(b)) => Some(Lerp::lerp(&a, &b, t)),
+            _ => closer.color,
+        };
+        Point {
+            rel_coord: self.rel_coord.lerp(&other.rel_coord, t),
+            // Opposite normals cancel out halfway
+            normal: match normal.magnitude() > 1e-9 {
+                true => normal.normalise(),
+                false => closer.normal,
+            },
+            color,
+            luminance_bias: self.luminance_bias.lerp(&other.luminance_bias, t),
+            uv,
+            occlusion: self.occlusion.lerp(&other.occlusion, t),
+        }
+    }
+}
+
+/// What a tween does once it reaches its end
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Repeat {
+    /// Stays at the end and is done
+    #[default]
+    Once,
+    /// Jumps back to the start and goes again, forever
+    Loop,
+    /// Goes back to the start the same way it came and again, forever
+    PingPong,
+}
+
+/// Motion from one value to another over the given number of seconds, eased along the way.
+/// Works on anything implementing Lerp, run it on the scene's clock with Scene::add_tween.
+/// ### Example:
+/// ```
+/// use shapes_rs::animation::easing::Easing;
+/// use shapes_rs::components::{Repeat, Tween};
+///
+/// let fade = Tween::new(0.0, 10.0, 2.0).with_easing(Easing::QuadIn);
+/// assert_eq!(fade.value(1.0), 2.5);
+/// assert_eq!(fade.value(3.0), 10.0);
+/// assert!(fade.is_done(2.0) && !fade.is_done(1.9));
+///
+/// let pulse = Tween::new(0.0, 10.0, 2.0).with_repeat(Repeat::PingPong);
+/// assert_eq!(pulse.value(3.0), 5.0);
+/// assert!(!pulse.is_done(100.0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tween<T> {
+    pub from: T,
+    pub to: T,
+    /// Seconds it takes to get from start to end once
+    pub duration: f64,
+    pub easing: Easing,
+    pub repeat: Repeat,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub fn new(from: T, to: T, duration: f64) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing: Easing::Linear,
+            repeat: Repeat::Once,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Value the given number of seconds after the start
+    pub fn value(&self, elapsed: f64) -> T {
+        self.from.lerp(&self.to, self.easing.apply(self.progress(elapsed)))
+    }
+
+    /// Whether the tween has reached its end and stays there
+    pub fn is_done(&self, elapsed: f64) -> bool {
+        self.repeat == Repeat::Once && elapsed >= self.duration
+    }
+
+    /// How far from start to end the tween is, from 0 to 1 before easing
+    fn progress(&self, elapsed: f64) -> f64 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        let runs = elapsed.max(0.0) / self.duration;
+        match self.repeat {
+            Repeat::Once => runs.min(1.0),
+            Repeat::Loop => runs % 1.0,
+            Repeat::PingPong => match runs % 2.0 {
+                part if part > 1.0 => 2.0 - part,
+                part => part,
+            },
+        }
+    }
+}
+
+/// Applies a tween at the given number of seconds after its start, returns whether it is done
+type TweenStep<'a> = Box<dyn FnMut(&mut Scene<'a>, f64) -> bool + 'a>;
+/// Called once a tween is done
+type TweenDone<'a> = Box<dyn FnOnce(&mut Scene<'a>) + 'a>;
+
+/// Tween running on a scene, see Scene::add_tween
+pub struct RunningTween<'a> {
+    /// Scene time the tween started at
+    pub(crate) start: f64,
+    pub(crate) step: TweenStep<'a>,
+    pub(crate) on_complete: Option<TweenDone<'a>>,
+}
+
+impl<'a> RunningTween<'a> {
+    /// Calls the function once the tween is done, with the scene so it can start the next
+    /// one. Tweens which repeat are never done
+    pub fn on_complete(&mut self, done: impl FnOnce(&mut Scene<'a>) + 'a) -> &mut Self {
+        self.on_complete = Some(Box::new(done));
+        self
+    }
+}
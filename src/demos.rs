@@ -0,0 +1,129 @@
+//! Ready made scenes to play around with or to quickly check that everything works.
+//!
+//! Every demo comes with an update function which moves the scene along, on top of what
+//! `Scene::advance` already does.
+//! ### Example:
+//! ```
+//! use std::time::Duration;
+//! use shapes_rs::demos;
+//!
+//! let mut demo = demos::spinning_donut(80, 40);
+//! for _ in 0..3 {
+//!     demo.advance(Duration::from_millis(50));
+//!     let frame = demo.scene.render();
+//!     assert!(frame.buffer.iter().any(|ch| *ch != ' '));
+//! }
+//! ```
+
+use core::f64::consts::PI;
+use core::time::Duration;
+
+use crate::basetype::{Angle, Coord, Vector3D};
+use crate::component::{Camera, Material, Object, Orbit};
+use crate::out::color::Rgb;
+use crate::scene::{ObjectId, Scene, SceneBuilder};
+use crate::shape::rotator::Rotator;
+use crate::shape::shape_gen::{CubeGenerator, TorusGenerator};
+
+static DONUT: TorusGenerator = TorusGenerator::new(10.0, 50.0);
+static CUBE: CubeGenerator = CubeGenerator::new(40.0, 40.0, 40.0);
+static SUN: CubeGenerator = CubeGenerator::new(40.0, 40.0, 40.0);
+static PLANET: CubeGenerator = CubeGenerator::new(16.0, 16.0, 16.0);
+static MOON: CubeGenerator = CubeGenerator::new(6.0, 6.0, 6.0);
+
+/// A scene together with the function which animates it
+pub struct Demo {
+    pub scene: Scene<'static>,
+    /// Called with the scene and the time that passed whenever the demo is advanced
+    pub update: fn(&mut Scene<'static>, Duration),
+}
+
+impl Demo {
+    /// Advances the scene by the given time and then calls the update function
+    pub fn advance(&mut self, dt: Duration) {
+        self.scene.advance(dt);
+        (self.update)(&mut self.scene, dt);
+    }
+}
+
+fn no_update(_scene: &mut Scene<'static>, _dt: Duration) {}
+
+/// The classic, a donut spinning around two axes
+pub fn spinning_donut(width: usize, height: usize) -> Demo {
+    let donut = Object::new(Coord::new(150.0, 0.0, 0.0), &DONUT, Rotator::identity())
+        .with_spin(Vector3D::new(1.0, 0.0, 1.0), Angle::from_degree(90.0));
+    Demo {
+        scene: SceneBuilder::new()
+            .buffer_size(width, height)
+            .add_object(donut)
+            .build(),
+        update: no_update,
+    }
+}
+
+/// A cube tumbling around a slanted axis
+pub fn tumbling_cube(width: usize, height: usize) -> Demo {
+    let cube = Object::new(Coord::new(120.0, 0.0, 0.0), &CUBE, Rotator::identity())
+        .with_spin(Vector3D::new(1.0, 2.0, 3.0), Angle::from_degree(60.0));
+    Demo {
+        scene: SceneBuilder::new()
+            .buffer_size(width, height)
+            .add_object(cube)
+            .build(),
+        update: no_update,
+    }
+}
+
+/// Rate at which the planet goes around the sun, in radians per second
+const PLANET_ORBIT: f64 = 0.5;
+/// Rate at which the moon goes around the planet, in radians per second
+const MOON_ORBIT: f64 = 2.0;
+
+/// A sun with a planet going around it, which in turn has a moon going around it. Seen from
+/// slightly above.
+/// ### Example:
+/// ```
+/// use std::time::Duration;
+/// use shapes_rs::demos;
+/// use shapes_rs::ObjectId;
+///
+/// let mut demo = demos::solar_system(80, 40);
+/// let before = demo.scene.object(ObjectId(1)).unwrap().location;
+/// demo.advance(Duration::from_secs(1));
+/// let after = demo.scene.object(ObjectId(1)).unwrap().location;
+///
+/// assert!((before.x - after.x).abs() > 1.0);
+/// ```
+pub fn solar_system(width: usize, height: usize) -> Demo {
+    let sun_location = Coord::new(250.0, 0.0, 0.0);
+    let camera = Camera::new(
+        Coord::new(80.0, 0.0, 100.0),
+        Vector3D::new(170.0, 0.0, -100.0),
+        Angle::from_degree(60.0),
+    );
+    let sun = Object::new(sun_location, &SUN, Rotator::identity())
+        .with_material(Material::emissive(1.0).with_base_color(Rgb::new(255, 200, 40)))
+        .with_spin(Vector3D::new(0.0, 0.0, 1.0), Angle::from_degree(20.0));
+    // Both start on the side of the sun facing the camera
+    let planet = Object::new(Coord::default(), &PLANET, Rotator::identity())
+        .with_material(Material::default().with_base_color(Rgb::new(60, 120, 255)))
+        .with_spin(Vector3D::new(0.0, 0.0, 1.0), Angle::from_degree(90.0))
+        .with_orbit(
+            Orbit::around(ObjectId(0), 90.0, 2.0 * PI / PLANET_ORBIT)
+                .with_phase(Angle::from_degree(180.0)),
+        );
+    let moon = Object::new(Coord::default(), &MOON, Rotator::identity()).with_orbit(
+        Orbit::around(ObjectId(1), 25.0, 2.0 * PI / MOON_ORBIT)
+            .with_phase(Angle::from_degree(180.0)),
+    );
+    Demo {
+        scene: SceneBuilder::new()
+            .buffer_size(width, height)
+            .camera(camera)
+            .add_object(sun)
+            .add_object(planet)
+            .add_object(moon)
+            .build(),
+        update: no_update,
+    }
+}
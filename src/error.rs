@@ -0,0 +1,98 @@
+use core::error::Error;
+use core::fmt;
+
+use crate::out::terminal::ParseBufferError;
+#[cfg(feature = "script")]
+use crate::scene::script::ParseScriptError;
+use crate::scene::ObjectId;
+
+/// Everything that can go wrong in this crate
+#[derive(Debug, PartialEq, Eq)]
+pub enum ShapesError {
+    /// A vector with all components zero was given where a direction is needed
+    ZeroVector,
+    /// A rotation axis with a length of zero was given
+    ZeroAxis,
+    /// A quaternion with a magnitude of zero can't be normalised
+    ZeroQuaternion,
+    /// Tried to access a cell outside of a buffer
+    OutOfBounds {
+        x: usize,
+        y: usize,
+        size_x: usize,
+        size_y: usize,
+    },
+    /// Text could not be parsed into a buffer
+    ParseBuffer(ParseBufferError),
+    /// A generator was given a parameter it can't work with, describes which one and why
+    InvalidParameter(&'static str),
+    /// A point of a shape can't be rendered, gives its index and what is wrong with it
+    InvalidPoint { index: usize, reason: &'static str },
+    /// No object of the scene has the given id
+    UnknownObject(ObjectId),
+    /// A scene manager has no scene with the given index
+    UnknownScene(usize),
+    /// The GPU renderer could not be set up or run, describes which step failed
+    Gpu(&'static str),
+    /// A script could not be parsed
+    #[cfg(feature = "script")]
+    ParseScript(ParseScriptError),
+}
+
+pub type Result<T> = core::result::Result<T, ShapesError>;
+
+impl fmt::Display for ShapesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapesError::ZeroVector => write!(f, "vector can't have all components zero"),
+            ShapesError::ZeroAxis => write!(f, "rotation axis can't have a length of zero"),
+            ShapesError::ZeroQuaternion => {
+                write!(f, "can't normalise a quaternion with a magnitude of zero")
+            }
+            ShapesError::OutOfBounds {
+                x,
+                y,
+                size_x,
+                size_y,
+            } => write!(
+                f,
+                "cell ({}, {}) is outside of a {}x{} buffer",
+                x, y, size_x, size_y
+            ),
+            ShapesError::ParseBuffer(err) => write!(f, "invalid buffer text: {}", err),
+            ShapesError::InvalidParameter(reason) => write!(f, "invalid parameter: {}", reason),
+            ShapesError::InvalidPoint { index, reason } => {
+                write!(f, "point {} is invalid: {}", index, reason)
+            }
+            ShapesError::UnknownObject(id) => write!(f, "the scene has no object with id {}", id.0),
+            ShapesError::UnknownScene(index) => write!(f, "there is no scene {}", index),
+            ShapesError::Gpu(reason) => write!(f, "gpu renderer failed: {}", reason),
+            #[cfg(feature = "script")]
+            ShapesError::ParseScript(err) => write!(f, "invalid script: {}", err),
+        }
+    }
+}
+
+impl Error for ShapesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ShapesError::ParseBuffer(err) => Some(err),
+            #[cfg(feature = "script")]
+            ShapesError::ParseScript(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseBufferError> for ShapesError {
+    fn from(err: ParseBufferError) -> Self {
+        ShapesError::ParseBuffer(err)
+    }
+}
+
+#[cfg(feature = "script")]
+impl From<ParseScriptError> for ShapesError {
+    fn from(err: ParseScriptError) -> Self {
+        ShapesError::ParseScript(err)
+    }
+}
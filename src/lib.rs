@@ -1,33 +1,145 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(dead_code)]
 #![allow(clippy::should_implement_trait)]
 #![allow(clippy::needless_return)]
 
 fn main() {}
 
+extern crate alloc;
+
 // Internal Types
+#[macro_use]
+mod trace;
+#[cfg(feature = "std")]
+mod animate;
 mod basetype;
+#[cfg(feature = "std")]
+mod binary;
 mod component;
+mod error;
 mod math;
 mod out;
 mod scene;
 mod shape;
 
 // Exports
+pub mod demos;
+#[cfg(feature = "std")]
+pub mod testing;
+
 pub mod generators {
+    pub use crate::shape::shape_gen::CubeFace;
     pub use crate::shape::shape_gen::CubeGenerator;
+    pub use crate::shape::shape_gen::CubeMode;
+    pub use crate::math::hull::convex_hull;
+    pub use crate::math::hull::ConvexHull;
+    pub use crate::shape::polyhedron::Platonic;
+    pub use crate::shape::polyhedron::PlatonicGenerator;
+    pub use crate::shape::polyhedron::PrismGenerator;
+    pub use crate::shape::polyhedron::PyramidGenerator;
+    pub use crate::shape::shape_gen::ReliefGenerator;
+    pub use crate::shape::shape_gen::ReliefMode;
+    pub use crate::shape::shape_gen::RingGenerator;
+    pub use crate::shape::shape_gen::StarfieldGenerator;
     pub use crate::shape::shape_gen::TorusGenerator;
+    pub use crate::shape::voxel::VoxelGrid;
+    pub use crate::shape::voxel::VoxelMode;
     pub mod selfmade {
         pub use crate::shape::shape_gen::ShapeGen;
     }
 }
 
+pub mod modifiers {
+    pub use crate::shape::modifier::AmbientOcclusion;
+    pub use crate::shape::modifier::Axis;
+    pub use crate::shape::modifier::ColorBy;
+    pub use crate::shape::modifier::Jitter;
+    pub use crate::shape::modifier::LuminanceBy;
+    pub use crate::shape::modifier::Mirror;
+    pub use crate::shape::modifier::PointAttribute;
+    pub use crate::shape::modifier::Scale;
+    pub use crate::shape::modifier::ShapeModifier;
+}
+
 pub mod renderer {
+    pub use crate::math::dither::bayer_threshold;
+    pub use crate::math::dither::dither_levels;
+    pub use crate::math::dither::Dither;
+    #[cfg(feature = "gpu")]
+    pub use crate::math::gpu::GpuRenderer;
+    pub use crate::scene::options::DepthTest;
+    pub use crate::math::projection::char_luminance;
+    pub use crate::math::projection::fragments_to_buffer;
+    pub use crate::math::projection::luminance_char;
     pub use crate::math::projection::pers_proj;
+    pub use crate::math::projection::pers_proj_color;
+    pub use crate::math::projection::pers_proj_half_block;
+    pub use crate::math::projection::pers_proj_hdr;
+    pub use crate::math::projection::pers_proj_painter;
+    pub use crate::math::projection::pers_proj_tiled;
+    pub use crate::math::projection::pers_proj_tonemapped;
+    pub use crate::math::projection::project_coord;
+    pub use crate::math::projection::project_fragments;
+    pub use crate::math::projection::project_fragments_sized;
+    #[cfg(feature = "bench")]
+    pub use crate::math::projection::project_points;
+    pub use crate::math::projection::shade_lit;
+    pub use crate::math::projection::shade_material;
+    pub use crate::math::projection::tone_map;
+    pub use crate::math::projection::Fragment;
+    pub use crate::math::raymarch::raymarch_fragments;
+    pub use crate::math::raymarch::raymarch_proj;
+    pub use crate::math::temporal::TemporalFilter;
+    pub use crate::scene::options::RenderOptions;
+    pub use crate::scene::options::ToneMap;
+}
+
+#[cfg(feature = "script")]
+pub mod scripting {
+    pub use crate::scene::script::ParseScriptError;
+    pub use crate::scene::script::Script;
+}
+
+pub mod sampling {
+    pub use crate::math::random::Rng;
+    pub use crate::math::sampling::fibonacci_sphere;
+    pub use crate::math::sampling::hemisphere;
+    pub use crate::math::sampling::stratified_sphere;
+}
+
+pub mod sdf {
+    pub use crate::shape::sdf::Sdf;
+}
+
+pub mod texture {
+    pub use crate::shape::texture::Checkerboard;
+    pub use crate::shape::texture::ImageTexture;
+    pub use crate::shape::texture::Texture;
 }
 
 pub mod components {
     pub use crate::component::Camera;
+    pub use crate::component::ClipPlane;
+    pub use crate::math::easing::Easing;
+    pub use crate::component::Environment;
+    pub use crate::component::Falloff;
+    pub use crate::component::FovAxis;
+    pub use crate::component::Joint;
+    pub use crate::component::Label;
+    pub use crate::component::Lerp;
     pub use crate::component::Light3D;
+    pub use crate::component::LightUpdate;
+    pub use crate::component::Material;
+    pub use crate::component::ObjectUpdate;
+    pub use crate::component::Orbit;
+    pub use crate::component::Repeat;
+    pub use crate::component::RunningTween;
+    pub use crate::component::Shading;
+    pub use crate::component::Shake;
+    pub use crate::component::Skeleton;
+    pub use crate::component::Skin;
+    pub use crate::component::SpotLight;
+    pub use crate::component::Tween;
 }
 
 pub mod base {
@@ -35,13 +147,130 @@ pub mod base {
     pub use crate::basetype::Angle3D;
     pub use crate::basetype::Coord;
     pub use crate::basetype::Vector3D;
+    pub use crate::shape::rotator::EulerOrder;
     pub use crate::shape::rotator::Rotator;
+    pub use crate::shape::shape_base::Bounds;
+    pub use crate::shape::shape_base::Point;
+    pub use crate::shape::shape_base::Shape;
+    pub use crate::shape::shape_base::ShapeStats;
 }
 
 pub mod buffer {
+    pub use crate::out::color_terminal::ColorCell;
+    pub use crate::out::color_terminal::ColorTerminalBuffer;
+    pub use crate::out::graphics::encode_kitty;
+    pub use crate::out::graphics::encode_sixel;
+    pub use crate::out::graphics::GraphicsProtocol;
+    #[cfg(feature = "std")]
+    pub use crate::out::graphics::GraphicsSink;
+    pub use crate::out::half_block::HalfBlockBuffer;
+    pub use crate::out::luminance::LuminanceBuffer;
+    #[cfg(feature = "net")]
+    pub use crate::out::net::NetSink;
+    pub use crate::out::png::encode_png;
+    #[cfg(feature = "std")]
+    pub use crate::out::record::RecordedFrame;
+    #[cfg(feature = "std")]
+    pub use crate::out::record::Recorder;
+    #[cfg(feature = "std")]
+    pub use crate::out::sink::ChannelSink;
+    #[cfg(feature = "std")]
+    pub use crate::out::sink::FileSink;
+    #[cfg(feature = "std")]
+    pub use crate::out::sink::FrameSink;
+    #[cfg(feature = "std")]
+    pub use crate::out::sink::StdoutSink;
+    pub use crate::out::terminal::ParseBufferError;
+    pub use crate::out::terminal::ScaleFilter;
     pub use crate::out::terminal::SimpleTerminalBuffer;
+    pub use crate::out::rgba::char_gray;
+    pub use crate::out::rgba::color_to_rgba;
+    pub use crate::out::rgba::terminal_to_rgba;
+    #[cfg(feature = "window")]
+    pub use crate::out::window::WindowBuffer;
+    pub use crate::out::zbuffer::ZBuffer;
     pub use crate::out::Buffer;
+    pub use crate::out::SizedBuffer;
+}
+
+pub mod color {
+    pub use crate::out::color::ColorMode;
+    pub use crate::out::color::ColorSource;
+    pub use crate::out::color::Colormap;
+    pub use crate::out::color::Rgb;
+}
+
+#[cfg(feature = "wasm")]
+pub mod wasm {
+    pub use crate::out::wasm::WasmDonut;
+}
+
+#[cfg(feature = "std")]
+pub mod animation {
+    pub use crate::animate::animator::Animator;
+    pub use crate::animate::input::Key;
+    pub use crate::animate::input::SampleSignal;
+    pub use crate::animate::input::SignalSource;
+    pub use crate::animate::input::StreamSignal;
+    pub use crate::animate::replay::ObjectTransform;
+    pub use crate::animate::replay::TransformFrame;
+    pub use crate::animate::replay::TransformPlayer;
+    pub use crate::animate::replay::TransformRecorder;
+    pub use crate::animate::stats::FrameStats;
+    pub use crate::math::easing;
+    #[cfg(feature = "terminal")]
+    pub use crate::animate::input::pending_keys;
+    #[cfg(feature = "terminal")]
+    pub use crate::animate::input::restore_terminal;
+    #[cfg(feature = "terminal")]
+    pub use crate::animate::input::RawModeGuard;
+    #[cfg(feature = "terminal")]
+    pub use crate::animate::input::TerminalGuard;
+}
+
+/// Everything needed for the usual scene in a single import
+/// ### Example:
+/// ```
+/// use shapes_rs::prelude::*;
+///
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let scene = SceneBuilder::new()
+///     .renderer(pers_proj)
+///     .add_object(Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity()))
+///     .build();
+/// let frame = scene.render();
+/// assert_eq!(frame.size(), (80, 40));
+/// ```
+pub mod prelude {
+    #[cfg(feature = "std")]
+    pub use crate::animation::*;
+    pub use crate::base::*;
+    pub use crate::buffer::*;
+    pub use crate::color::*;
+    pub use crate::components::*;
+    pub use crate::modifiers::*;
+    pub use crate::generators::selfmade::ShapeGen;
+    pub use crate::generators::{
+        convex_hull, ConvexHull, CubeFace, CubeGenerator, CubeMode, Platonic, PlatonicGenerator,
+        PrismGenerator, PyramidGenerator, ReliefGenerator, ReliefMode, RingGenerator,
+        StarfieldGenerator, TorusGenerator, VoxelGrid, VoxelMode,
+    };
+    pub use crate::renderer::*;
+    pub use crate::sdf::*;
+    pub use crate::texture::*;
+    pub use crate::{
+        DebugFlags, Object, ObjectId, RenderSnapshot, Scene, SceneBuilder, SceneManager,
+        Transition, Viewport,
+    };
 }
 
 pub use crate::component::Object;
+pub use crate::error::{Result, ShapesError};
+pub use crate::scene::debug::{DebugFlags, NonFinitePoint};
+pub use crate::scene::manager::{SceneManager, Transition};
+pub use crate::scene::ObjectId;
+pub use crate::scene::snapshot::{ObjectSnapshot, RenderSnapshot};
+pub use crate::scene::state::{ObjectState, SceneState};
 pub use crate::scene::Scene;
+pub use crate::scene::Viewport;
+pub use crate::scene::SceneBuilder;
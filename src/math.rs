@@ -1,2 +1,12 @@
+pub mod dither;
+pub mod easing;
+pub mod float;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod hull;
 pub mod projection;
+pub mod random;
+pub mod sampling;
+pub mod temporal;
+pub mod raymarch;
 pub mod trig;
@@ -0,0 +1,99 @@
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// 4x4 ordered dithering matrix
+const BAYER_4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Returns the ordered dithering threshold of a cell, strictly between 0 and 1.
+/// Neighbouring cells get thresholds that are spread out as evenly as possible.
+/// ### Example:
+/// ```
+/// use shapes_rs::renderer::bayer_threshold;
+///
+/// assert_eq!(bayer_threshold(0, 0), 0.5 / 16.0);
+/// assert_eq!(bayer_threshold(4, 4), bayer_threshold(0, 0));
+/// ```
+pub fn bayer_threshold(x: usize, y: usize) -> f64 {
+    (BAYER_4[y % 4][x % 4] as f64 + 0.5) / 16.0
+}
+
+/// How luminance is spread over the characters of the ramp when it falls between two of them
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Dither {
+    /// Every cell takes the character its luminance falls on, smooth gradients turn into bands
+    #[default]
+    None,
+    /// Cells are pushed up or down by the Bayer matrix, giving a regular pattern
+    Ordered,
+    /// Floyd-Steinberg, the rounding error of every cell is passed on to its unvisited
+    /// neighbours. Less regular than Ordered, but a cell depends on the ones before it
+    ErrorDiffusion,
+}
+
+/// Snaps values between 0 and 1 onto the middle of `levels` equally wide bands, row by row.
+/// Cells without a value are skipped and do not take part in error diffusion.
+/// On average a dithered area ends up on the same band as it would without dithering.
+/// ### Example:
+/// ```
+/// use shapes_rs::renderer::{dither_levels, Dither};
+///
+/// // Halfway between the second and third of four bands
+/// let mut plain = vec![Some(0.5); 16];
+/// let mut ordered = plain.clone();
+/// dither_levels(&mut plain, 4, 4, Dither::None);
+/// dither_levels(&mut ordered, 4, 4, Dither::Ordered);
+///
+/// assert!(plain.iter().all(|v| *v == Some(0.625)));
+/// assert_eq!(ordered.iter().filter(|v| **v == Some(0.375)).count(), 8);
+/// assert_eq!(ordered.iter().filter(|v| **v == Some(0.625)).count(), 8);
+/// ```
+pub fn dither_levels(values: &mut [Option<f64>], size_x: usize, levels: usize, mode: Dither) {
+    if levels == 0 || size_x == 0 {
+        return;
+    }
+    let top = (levels - 1) as f64;
+    let center = |band: f64| (band.clamp(0.0, top) + 0.5) / levels as f64;
+    match mode {
+        Dither::None => {
+            for value in values.iter_mut().flatten() {
+                *value = center(f64::floor(*value * levels as f64));
+            }
+        }
+        Dither::Ordered => {
+            for (index, value) in values.iter_mut().enumerate() {
+                if let Some(value) = value {
+                    let threshold = bayer_threshold(index % size_x, index / size_x);
+                    *value = center(f64::floor(*value * levels as f64 + threshold - 0.5));
+                }
+            }
+        }
+        Dither::ErrorDiffusion => {
+            let mut error = alloc::vec![0.0; values.len()];
+            for index in 0..values.len() {
+                let Some(value) = values[index] else {
+                    continue;
+                };
+                // Position on the ramp, with the middle of every band on a whole number
+                let target = value * levels as f64 - 0.5 + error[index];
+                let band = f64::round(target).clamp(0.0, top);
+                values[index] = Some(center(band));
+                let rest = target - band;
+                let (x, y) = (index % size_x, index / size_x);
+                let mut pass = |dx: isize, dy: usize, weight: f64| {
+                    let nx = x as isize + dx;
+                    if nx < 0 || nx as usize >= size_x {
+                        return;
+                    }
+                    let neighbour = (y + dy) * size_x + nx as usize;
+                    if values.get(neighbour).is_some_and(|v| v.is_some()) {
+                        error[neighbour] += rest * weight;
+                    }
+                };
+                pass(1, 0, 7.0 / 16.0);
+                pass(-1, 1, 3.0 / 16.0);
+                pass(0, 1, 5.0 / 16.0);
+                pass(1, 1, 1.0 / 16.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Easing functions, which turn the linear progress of an animation into how far along its
+//! motion it is. All of them take and give values from 0 at the start to 1 at the end, though
+//! elastic and bounce overshoot or fall back in between. Progress outside of 0 to 1 is clamped.
+//!
+//! `in` functions start slowly, `out` functions end slowly and `in_out` ones do both.
+
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+use core::f64::consts::PI;
+
+/// Every easing function of this module by name, for picking one at runtime or storing it
+/// ### Example:
+/// ```
+/// use std::time::Duration;
+/// use shapes_rs::animation::easing::{self, Easing};
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::Object;
+///
+/// assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+/// assert_eq!(Easing::CubicOut.apply(0.5), easing::cubic_out(0.5));
+/// assert!(Easing::ALL.iter().all(|e| e.apply(0.0).abs() < 1e-9 && (e.apply(1.0) - 1.0).abs() < 1e-9));
+///
+/// // Slides the cube in from the side over two seconds, slowing down as it arrives
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut cube = Object::new(Coord::new(60.0, -40.0, 0.0), &cubegen, Rotator::identity())
+///     .with_update(|obj, t, _| {
+///         obj.location.y = -40.0 + 40.0 * easing::quad_out(t / 2.0);
+///     });
+/// let update = cube.update.unwrap();
+/// update(&mut cube, 1.0, 1.0);
+/// assert_eq!(cube.location.y, -10.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Easing {
+    pub const ALL: [Easing; 13] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    /// How far along its motion an animation is at the given progress
+    pub fn apply(&self, t: f64) -> f64 {
+        let function = match self {
+            Easing::Linear => linear,
+            Easing::QuadIn => quad_in,
+            Easing::QuadOut => quad_out,
+            Easing::QuadInOut => quad_in_out,
+            Easing::CubicIn => cubic_in,
+            Easing::CubicOut => cubic_out,
+            Easing::CubicInOut => cubic_in_out,
+            Easing::ElasticIn => elastic_in,
+            Easing::ElasticOut => elastic_out,
+            Easing::ElasticInOut => elastic_in_out,
+            Easing::BounceIn => bounce_in,
+            Easing::BounceOut => bounce_out,
+            Easing::BounceInOut => bounce_in_out,
+        };
+        function(t)
+    }
+}
+
+pub fn linear(t: f64) -> f64 {
+    t.clamp(0.0, 1.0)
+}
+
+pub fn quad_in(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    t * t
+}
+
+pub fn quad_out(t: f64) -> f64 {
+    1.0 - quad_in(1.0 - t)
+}
+
+pub fn quad_in_out(t: f64) -> f64 {
+    in_out(t, quad_in)
+}
+
+pub fn cubic_in(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * t
+}
+
+pub fn cubic_out(t: f64) -> f64 {
+    1.0 - cubic_in(1.0 - t)
+}
+
+pub fn cubic_in_out(t: f64) -> f64 {
+    in_out(t, cubic_in)
+}
+
+/// Winds up with growing swings below the start before shooting off
+pub fn elastic_in(t: f64) -> f64 {
+    1.0 - elastic_out(1.0 - t)
+}
+
+/// Shoots past the end and swings around it, like a released spring
+pub fn elastic_out(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if t == 0.0 || t == 1.0 {
+        return t;
+    }
+    // Three swings, each a third of the last
+    f64::powf(2.0, -10.0 * t) * f64::sin((10.0 * t - 0.75) * 2.0 * PI / 3.0) + 1.0
+}
+
+pub fn elastic_in_out(t: f64) -> f64 {
+    in_out(t, elastic_in)
+}
+
+/// Hops off the start with hops that grow higher
+pub fn bounce_in(t: f64) -> f64 {
+    1.0 - bounce_out(1.0 - t)
+}
+
+/// Falls onto the end and bounces on it a few times, like a dropped ball
+pub fn bounce_out(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    // Parabolas meeting at 1, each bounce a quarter as high as the one before
+    const STRENGTH: f64 = 7.5625;
+    const SPAN: f64 = 2.75;
+    if t < 1.0 / SPAN {
+        STRENGTH * t * t
+    } else if t < 2.0 / SPAN {
+        let t = t - 1.5 / SPAN;
+        STRENGTH * t * t + 0.75
+    } else if t < 2.5 / SPAN {
+        let t = t - 2.25 / SPAN;
+        STRENGTH * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / SPAN;
+        STRENGTH * t * t + 0.984375
+    }
+}
+
+pub fn bounce_in_out(t: f64) -> f64 {
+    in_out(t, bounce_in)
+}
+
+/// First half of the motion eased in, the second half the same mirrored
+fn in_out(t: f64, ease_in: fn(f64) -> f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    match t < 0.5 {
+        true => ease_in(2.0 * t) / 2.0,
+        false => 1.0 - ease_in(2.0 - 2.0 * t) / 2.0,
+    }
+}
@@ -0,0 +1,74 @@
+//! Float functions for `no_std` builds, where f64 lacks its math methods.
+//! Modules doing float math import `FloatExt` when the `std` feature is disabled, so the same
+//! method calls work with and without std.
+
+#[cfg(all(not(feature = "std"), not(feature = "libm")))]
+compile_error!("Either the `std` or the `libm` feature has to be enabled");
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub trait FloatExt {
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn acos(self) -> Self;
+    fn asin(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn round(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn abs(self) -> Self;
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+impl FloatExt for f64 {
+    fn sin(self) -> Self {
+        libm::sin(self)
+    }
+    fn cos(self) -> Self {
+        libm::cos(self)
+    }
+    fn tan(self) -> Self {
+        libm::tan(self)
+    }
+    fn acos(self) -> Self {
+        libm::acos(self)
+    }
+    fn asin(self) -> Self {
+        libm::asin(self)
+    }
+    fn atan(self) -> Self {
+        libm::atan(self)
+    }
+    fn atan2(self, other: Self) -> Self {
+        libm::atan2(self, other)
+    }
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+    fn exp(self) -> Self {
+        libm::exp(self)
+    }
+    fn powf(self, n: Self) -> Self {
+        libm::pow(self, n)
+    }
+    fn powi(self, n: i32) -> Self {
+        libm::pow(self, n as f64)
+    }
+    fn round(self) -> Self {
+        libm::round(self)
+    }
+    fn floor(self) -> Self {
+        libm::floor(self)
+    }
+    fn ceil(self) -> Self {
+        libm::ceil(self)
+    }
+    fn abs(self) -> Self {
+        libm::fabs(self)
+    }
+}
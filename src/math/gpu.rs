@@ -0,0 +1,355 @@
+//! Experimental perspective renderer running in a wgpu compute shader.
+//! The points of every visible object are uploaded each frame, moved into world space,
+//! projected and shaded on the GPU, and the character index of every cell is read back.
+//! Only a subset of the CPU renderer is supported: clip planes, textures, reflective
+//! materials, opacity, spot light cones, depth test modes and dithering are ignored, and as the
+//! shader works with f32 a few cells may come out differently than with pers_proj.
+
+use alloc::{vec, vec::Vec};
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::task::Wake;
+use std::thread::{self, Thread};
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::basetype::Vector3D;
+use crate::component::Shading;
+use crate::error::{Result, ShapesError};
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::scene::snapshot::RenderSnapshot;
+use crate::shape::rotator::Rotator;
+
+use super::projection::LUMINANCE_RAMP;
+
+const SHADER: &str = include_str!("gpu.wgsl");
+/// Has to match the workgroup size of both entry points of the shader
+const WORKGROUP_SIZE: u32 = 64;
+/// Cells nothing was drawn in keep this value in the depth, winner and cell buffers
+const EMPTY: u32 = u32::MAX;
+/// The cell buffer stores the object index above these bits and the ramp index below them,
+/// the luminance ramp has to fit into them
+const RAMP_BITS: u32 = 4;
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct GpuCamera {
+    /// Columns of the view rotation
+    axes: [[f32; 4]; 3],
+    position: [f32; 4],
+    scale: [f32; 2],
+    size: [u32; 2],
+    /// Gamma and contrast
+    curve: [f32; 2],
+    point_count: u32,
+    light_count: u32,
+    ramp_len: u32,
+    _padding: [u32; 3],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct GpuObject {
+    /// Columns of the object rotation
+    axes: [[f32; 4]; 3],
+    location: [f32; 4],
+    /// Luminance of materials that don't depend on the lights, negative for lit ones
+    fixed: f32,
+    depth_test: u32,
+    _padding: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct GpuPoint {
+    position: [f32; 3],
+    object: u32,
+    normal: [f32; 3],
+    luminance_bias: f32,
+    occlusion: f32,
+    _padding: [f32; 3],
+}
+
+/// Renderer which projects the scene on the GPU, holds the device and the compiled shader so
+/// they are only set up once. As it keeps state it is not a plain renderer function, draw a
+/// scene with it through Scene::render_with.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::buffer::Buffer;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::renderer::{pers_proj, GpuRenderer};
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut scene = fixture_scene(40, 20);
+/// scene.add_object(Object::new(Coord::new(30.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+///
+/// // Machines without a usable adapter can't create the renderer
+/// if let Ok(gpu) = GpuRenderer::new() {
+///     let frame = scene.render_with(|view| gpu.render(view));
+///     let cpu = scene.render_with(pers_proj);
+///     // The face towards the camera is fully lit on both
+///     assert_eq!(frame.get(10, 20), cpu.get(10, 20));
+/// }
+/// ```
+pub struct GpuRenderer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    /// Depth, closest and shade pass, run in this order
+    pipelines: [wgpu::ComputePipeline; 3],
+}
+
+impl GpuRenderer {
+    /// Picks an adapter, opens a device on it and compiles the shader.
+    /// Fails if the machine has no adapter wgpu can use.
+    pub fn new() -> Result<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .ok_or(ShapesError::Gpu("no adapter found"))?;
+        let (device, queue) = block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+            .map_err(|_| ShapesError::Gpu("the adapter refused to open a device"))?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("shapes projection"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let entries: Vec<wgpu::BindGroupLayoutEntry> = (0..7)
+            .map(|binding| wgpu::BindGroupLayoutEntry {
+                binding,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: match binding {
+                        0 => wgpu::BufferBindingType::Uniform,
+                        1..=3 => wgpu::BufferBindingType::Storage { read_only: true },
+                        _ => wgpu::BufferBindingType::Storage { read_only: false },
+                    },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            })
+            .collect();
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("shapes projection"),
+            entries: &entries,
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("shapes projection"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let pipelines = [pipeline("depth"), pipeline("closest"), pipeline("shade")];
+
+        Ok(Self {
+            device,
+            queue,
+            layout,
+            pipelines,
+        })
+    }
+
+    /// Renders the snapshot, panics if the result can't be read back from the GPU.
+    /// See try_render for a non panicking version
+    pub fn render(&self, view: &RenderSnapshot) -> SimpleTerminalBuffer {
+        self.try_render(view).expect("gpu frame could not be read back")
+    }
+
+    /// Renders the snapshot, fails if the result can't be read back from the GPU
+    pub fn try_render(&self, view: &RenderSnapshot) -> Result<SimpleTerminalBuffer> {
+        let (size_x, size_y) = view.get_buffer_size();
+        let mut buffer = SimpleTerminalBuffer::filled(size_x, size_y, view.clear.ch);
+        let cells = self.cell_indices(view)?;
+
+        for (index, cell) in cells.into_iter().enumerate() {
+            if cell == EMPTY {
+                continue;
+            }
+            let object = &view.objects[(cell >> RAMP_BITS) as usize];
+            buffer[index] = match object.material.shading {
+                Shading::Glyph(glyph) => glyph,
+                _ => LUMINANCE_RAMP[(cell & ((1 << RAMP_BITS) - 1)) as usize] as char,
+            };
+        }
+        Ok(buffer)
+    }
+
+    /// Runs the shader and reads back the object and ramp index of every cell, row by row
+    fn cell_indices(&self, view: &RenderSnapshot) -> Result<Vec<u32>> {
+        let (size_x, size_y) = view.get_buffer_size();
+        let cell_count = size_x * size_y;
+
+        let mut objects = Vec::new();
+        let mut points = Vec::new();
+        for (index, obj) in view.visible_objects() {
+            let (x, y, z) = obj.location.get();
+            // Objects are uploaded at their index in the snapshot, hidden ones left empty
+            objects.resize(index, GpuObject::zeroed());
+            objects.push(GpuObject {
+                axes: axes(&obj.rotation),
+                location: [x as f32, y as f32, z as f32, 0.0],
+                fixed: obj.material.fixed_luminance().map_or(-1.0, |l| l as f32),
+                depth_test: obj.depth_test as u32,
+                _padding: [0; 2],
+            });
+            points.extend(obj.points.iter().map(|point| {
+                let (x, y, z) = point.rel_coord.get();
+                let (nx, ny, nz) = point.normal.get();
+                GpuPoint {
+                    position: [x as f32, y as f32, z as f32],
+                    object: index as u32,
+                    normal: [nx as f32, ny as f32, nz as f32],
+                    luminance_bias: point.luminance_bias as f32,
+                    occlusion: point.occlusion as f32,
+                    _padding: [0.0; 3],
+                }
+            }));
+        }
+        if points.is_empty() || cell_count == 0 {
+            return Ok(vec![EMPTY; cell_count]);
+        }
+
+        // Storage buffers can't be empty, the shader only reads as many lights as there are
+        let mut lights: Vec<[f32; 4]> = view
+            .lights
+            .iter()
+            .map(|light| {
+                let (x, y, z) = light.coord.get();
+                [x as f32, y as f32, z as f32, 0.0]
+            })
+            .collect();
+        let light_count = lights.len() as u32;
+        lights.resize(lights.len().max(1), [0.0; 4]);
+
+        let (scale_x, scale_y) = view.camera.projection_scale(size_x, size_y);
+        let (cx, cy, cz) = view.camera.coord.get();
+        let camera = GpuCamera {
+            axes: axes(&view.camera.view_rotator()),
+            position: [cx as f32, cy as f32, cz as f32, 0.0],
+            scale: [scale_x as f32, scale_y as f32],
+            size: [size_x as u32, size_y as u32],
+            curve: [view.options.gamma as f32, view.options.contrast as f32],
+            point_count: points.len() as u32,
+            light_count,
+            ramp_len: LUMINANCE_RAMP.len() as u32,
+            _padding: [0; 3],
+        };
+
+        let init = |label, contents: &[u8], usage| {
+            self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents,
+                usage,
+            })
+        };
+        let cleared = vec![EMPTY; cell_count];
+        let storage = wgpu::BufferUsages::STORAGE;
+        let buffers = [
+            init("camera", bytemuck::bytes_of(&camera), wgpu::BufferUsages::UNIFORM),
+            init("points", bytemuck::cast_slice(&points), storage),
+            init("objects", bytemuck::cast_slice(&objects), storage),
+            init("lights", bytemuck::cast_slice(&lights), storage),
+            init("depths", bytemuck::cast_slice(&cleared), storage),
+            init("winners", bytemuck::cast_slice(&cleared), storage),
+            init("cells", bytemuck::cast_slice(&cleared), storage | wgpu::BufferUsages::COPY_SRC),
+        ];
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size: (cell_count * 4) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .zip(0..)
+            .map(|(buffer, binding)| wgpu::BindGroupEntry {
+                binding,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("shapes projection"),
+            layout: &self.layout,
+            entries: &entries,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_bind_group(0, &bind_group, &[]);
+            let workgroups = (points.len() as u32).div_ceil(WORKGROUP_SIZE);
+            // Every pass has to see the whole result of the one before
+            for pipeline in &self.pipelines {
+                pass.set_pipeline(pipeline);
+                pass.dispatch_workgroups(workgroups, 1, 1);
+            }
+        }
+        encoder.copy_buffer_to_buffer(&buffers[6], 0, &readback, 0, readback.size());
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        match receiver.recv() {
+            Ok(Ok(())) => {}
+            _ => return Err(ShapesError::Gpu("the frame could not be read back")),
+        }
+        let cells = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        readback.unmap();
+        Ok(cells)
+    }
+}
+
+/// Columns of the matrix a rotator stands for, padded to the alignment of vec3 in WGSL
+fn axes(rotator: &Rotator) -> [[f32; 4]; 3] {
+    [
+        Vector3D::new(1.0, 0.0, 0.0),
+        Vector3D::new(0.0, 1.0, 0.0),
+        Vector3D::new(0.0, 0.0, 1.0),
+    ]
+    .map(|axis| {
+        let (x, y, z) = axis.rotate(rotator).get();
+        [x as f32, y as f32, z as f32, 0.0]
+    })
+}
+
+/// Drives a future to completion on the current thread, wgpu hands out futures for setting
+/// up the adapter and device
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
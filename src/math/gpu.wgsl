@@ -0,0 +1,149 @@
+// Projection of the GPU renderer, see gpu.rs for the layout of the buffers.
+// The depth pass leaves the distance of the closest point in every cell and the closest pass
+// picks the first of the points at that distance, like the CPU renderer keeps the first one.
+// The shade pass then writes the object and ramp index of that point.
+
+const PI: f32 = 3.14159265358979;
+const RAMP_BITS: u32 = 4u;
+
+struct Camera {
+    x_axis: vec3<f32>,
+    y_axis: vec3<f32>,
+    z_axis: vec3<f32>,
+    position: vec3<f32>,
+    scale: vec2<f32>,
+    size: vec2<u32>,
+    // Gamma and contrast
+    curve: vec2<f32>,
+    point_count: u32,
+    light_count: u32,
+    ramp_len: u32,
+}
+
+struct Object {
+    x_axis: vec3<f32>,
+    y_axis: vec3<f32>,
+    z_axis: vec3<f32>,
+    // Padded, so fixed starts at the same offset as on the CPU side
+    location: vec4<f32>,
+    fixed: f32,
+    depth_test: u32,
+}
+
+struct Point {
+    position: vec3<f32>,
+    object: u32,
+    normal: vec3<f32>,
+    luminance_bias: f32,
+    occlusion: f32,
+}
+
+struct Projected {
+    valid: bool,
+    cell: u32,
+    depth: u32,
+    position: vec3<f32>,
+}
+
+@group(0) @binding(0) var<uniform> camera: Camera;
+@group(0) @binding(1) var<storage, read> points: array<Point>;
+@group(0) @binding(2) var<storage, read> objects: array<Object>;
+@group(0) @binding(3) var<storage, read> lights: array<vec4<f32>>;
+@group(0) @binding(4) var<storage, read_write> depths: array<atomic<u32>>;
+@group(0) @binding(5) var<storage, read_write> winners: array<atomic<u32>>;
+@group(0) @binding(6) var<storage, read_write> cells: array<u32>;
+
+fn rotate(x_axis: vec3<f32>, y_axis: vec3<f32>, z_axis: vec3<f32>, v: vec3<f32>) -> vec3<f32> {
+    return x_axis * v.x + y_axis * v.y + z_axis * v.z;
+}
+
+fn project(point: Point) -> Projected {
+    var projected: Projected;
+    let object = objects[point.object];
+    projected.position = rotate(object.x_axis, object.y_axis, object.z_axis, point.position)
+        + object.location.xyz;
+
+    let pv = projected.position - camera.position;
+    let cpv = rotate(camera.x_axis, camera.y_axis, camera.z_axis, pv);
+    let half_size = vec2<f32>(camera.size) / 2.0;
+    // Cells left of or above the buffer end up in its first column or row, like the
+    // saturating casts of the CPU renderer
+    let x = u32(max((cpv.y / cpv.x) * camera.scale.x + half_size.x, 0.0));
+    let y = u32(max(-(cpv.z / cpv.x) * camera.scale.y + half_size.y, 0.0));
+    projected.valid = x < camera.size.x && y < camera.size.y;
+    projected.cell = y * camera.size.x + x;
+
+    // Distances are never negative, so their bits sort the same way as the floats.
+    // Objects without a depth test are always in front
+    var distance = 0.0;
+    if object.depth_test != 0u {
+        distance = length(pv);
+    }
+    projected.depth = bitcast<u32>(distance);
+    return projected;
+}
+
+fn curve(luminance: f32) -> f32 {
+    if camera.curve.x == 1.0 && camera.curve.y == 1.0 {
+        return luminance;
+    }
+    let contrasted = clamp((luminance - 0.5) * camera.curve.y + 0.5, 0.0, 1.0);
+    if camera.curve.x > 0.0 {
+        return pow(contrasted, 1.0 / camera.curve.x);
+    }
+    return contrasted;
+}
+
+fn point_luminance(point: Point, position: vec3<f32>) -> f32 {
+    let object = objects[point.object];
+    var luminance = object.fixed;
+    if luminance < 0.0 {
+        luminance = 0.0;
+        let normal = normalize(rotate(object.x_axis, object.y_axis, object.z_axis, point.normal));
+        // The brightest light decides the character
+        for (var i = 0u; i < camera.light_count; i++) {
+            let lp = normalize(lights[i].xyz - position);
+            let angle = acos(clamp(dot(normal, lp), -1.0, 1.0));
+            luminance = max(luminance, 1.0 - angle / PI);
+        }
+    }
+    luminance *= 1.0 - point.occlusion;
+    return curve(clamp(luminance + point.luminance_bias, 0.0, 1.0));
+}
+
+@compute @workgroup_size(64)
+fn depth(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= camera.point_count {
+        return;
+    }
+    let projected = project(points[id.x]);
+    if projected.valid {
+        atomicMin(&depths[projected.cell], projected.depth);
+    }
+}
+
+@compute @workgroup_size(64)
+fn closest(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= camera.point_count {
+        return;
+    }
+    let projected = project(points[id.x]);
+    if projected.valid && atomicLoad(&depths[projected.cell]) == projected.depth {
+        atomicMin(&winners[projected.cell], id.x);
+    }
+}
+
+@compute @workgroup_size(64)
+fn shade(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= camera.point_count {
+        return;
+    }
+    let point = points[id.x];
+    let projected = project(point);
+    if !projected.valid || atomicLoad(&winners[projected.cell]) != id.x {
+        return;
+    }
+    let luminance = point_luminance(point, projected.position);
+    let ramp = min(u32(luminance * f32(camera.ramp_len)), camera.ramp_len - 1u);
+    cells[projected.cell] = (point.object << RAMP_BITS) | ramp;
+}
@@ -0,0 +1,195 @@
+//! Smallest convex surface around a cloud of points, for wrapping arbitrary point data such as
+//! measurements or debris into a closed shape.
+
+use alloc::vec::Vec;
+
+use crate::basetype::{Coord, Vector3D};
+use crate::error::{Result, ShapesError};
+use crate::shape::polyhedron::Face;
+use crate::shape::sdf::Sdf;
+use crate::shape::shape_base::{Bounds, Shape};
+use crate::shape::shape_gen::ShapeGen;
+
+/// Closed convex surface made of triangles, see convex_hull. Generates its triangles filled
+/// with points at most `node_dis` apart, every one of them facing outwards.
+pub struct ConvexHull {
+    /// Points on the hull, the ones inside of it are left out
+    pub vertices: Vec<Coord>,
+    /// Corners of every triangle as indices into the vertices, counterclockwise when looking
+    /// at the triangle from outside
+    pub triangles: Vec<[usize; 3]>,
+    pub node_dis: f64,
+}
+
+impl ConvexHull {
+    /// Sets the largest distance between two generated points, fails if it isn't positive and
+    /// finite
+    pub fn try_with_node_dis(mut self, node_dis: f64) -> Result<Self> {
+        if !node_dis.is_finite() || node_dis <= 0.0 {
+            return Err(ShapesError::InvalidParameter(
+                "distance between hull points has to be finite and positive",
+            ));
+        }
+        self.node_dis = node_dis;
+        Ok(self)
+    }
+
+    /// Normal of the triangle with the given index, pointing out of the hull
+    pub fn normal(&self, triangle: usize) -> Vector3D {
+        let [a, b, c] = self.triangles[triangle].map(|index| self.vertices[index]);
+        triangle_normal(a, b, c)
+    }
+
+    /// Whether the point is inside of the hull or on it
+    pub fn contains(&self, point: Coord) -> bool {
+        self.distance(point) <= 0.0
+    }
+}
+
+impl ShapeGen for ConvexHull {
+    fn bounds(&self) -> Option<Bounds> {
+        let first = Bounds::new(self.vertices[0], self.vertices[0]);
+        Some(self.vertices.iter().fold(first, |bounds, vertex| {
+            bounds.union(&Bounds::new(*vertex, *vertex))
+        }))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        for (index, triangle) in self.triangles.iter().enumerate() {
+            let face = Face {
+                corners: triangle.map(|index| self.vertices[index]).to_vec(),
+                normal: self.normal(index),
+            };
+            face.fill(self.node_dis, &mut shape.points);
+        }
+    }
+}
+
+/// Distance to the surface of the hull, exact inside of it and never more than the real
+/// distance outside
+impl Sdf for ConvexHull {
+    fn distance(&self, point: Coord) -> f64 {
+        (0..self.triangles.len())
+            .map(|index| {
+                let corner = self.vertices[self.triangles[index][0]];
+                (point - corner).to_vector().dot(self.normal(index))
+            })
+            .fold(f64::MIN, f64::max)
+    }
+}
+
+fn triangle_normal(a: Coord, b: Coord, c: Coord) -> Vector3D {
+    (b - a).to_vector().cross((c - a).to_vector()).normalise()
+}
+
+/// How far the point is above the plane of the triangle, times twice its area
+fn height_above(points: &[Coord], [a, b, c]: [usize; 3], point: Coord) -> f64 {
+    let (a, b, c) = (points[a], points[b], points[c]);
+    let normal = (b - a).to_vector().cross((c - a).to_vector());
+    (point - a).to_vector().dot(normal)
+}
+
+/// Wraps the points into the smallest convex surface around all of them. Fails if there are
+/// fewer than four points or all of them lie in one plane, which leaves nothing to wrap.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::convex_hull;
+/// use shapes_rs::sdf::Sdf;
+/// use shapes_rs::Object;
+///
+/// // Corners of a box, and a point inside of it which is left out
+/// let mut points = Vec::new();
+/// for x in [-10.0, 10.0] {
+///     for y in [-10.0, 10.0] {
+///         for z in [-5.0, 5.0] {
+///             points.push(Coord::new(x, y, z));
+///         }
+///     }
+/// }
+/// points.push(Coord::new(1.0, 2.0, 3.0));
+///
+/// let hull = convex_hull(&points).unwrap();
+/// assert_eq!(hull.vertices.len(), 8);
+/// // Every side of the box is made of two triangles
+/// assert_eq!(hull.triangles.len(), 12);
+/// assert!(hull.contains(Coord::default()) && !hull.contains(Coord::new(0.0, 0.0, 6.0)));
+///
+/// let object = Object::new(Coord::default(), &hull, Rotator::identity());
+/// assert!(object.shape.points.iter().all(|p| p.normal.dot(p.rel_coord.to_vector()) > 0.0));
+///
+/// assert!(convex_hull(&points[..3]).is_err());
+/// ```
+pub fn convex_hull(points: &[Coord]) -> Result<ConvexHull> {
+    let degenerate = ShapesError::InvalidParameter("a hull needs points which don't share a plane");
+    if points.len() < 4 {
+        return Err(degenerate);
+    }
+    let distance = |a: Coord, b: Coord| (b - a).to_vector().magnitude();
+    let farthest = |measure: &dyn Fn(Coord) -> f64| {
+        (0..points.len())
+            .map(|index| (index, measure(points[index])))
+            .fold((0, 0.0), |best, candidate| match candidate.1 > best.1 {
+                true => candidate,
+                false => best,
+            })
+    };
+
+    // Starting tetrahedron out of points as far apart as possible, so it isn't flat
+    let (b, extent) = farthest(&|point| distance(points[0], point));
+    // Anything closer to a plane than this is counted as lying in it. Heights are scaled by the
+    // area of the triangles, hence the cube
+    let epsilon = 1e-9 * extent * extent * extent;
+    let line = (points[b] - points[0]).to_vector();
+    let (c, _) = farthest(&|point| (point - points[0]).to_vector().cross(line).magnitude());
+    let (d, height) = farthest(&|point| height_above(points, [0, b, c], point).abs());
+    if extent == 0.0 || height <= epsilon {
+        return Err(degenerate);
+    }
+    let mut triangles: Vec<[usize; 3]> = match height_above(points, [0, b, c], points[d]) > 0.0 {
+        true => Vec::from([[0, c, b], [0, b, d], [b, c, d], [c, 0, d]]),
+        false => Vec::from([[0, b, c], [0, d, b], [b, d, c], [c, d, 0]]),
+    };
+
+    for (index, point) in points.iter().enumerate() {
+        if [0, b, c, d].contains(&index) {
+            continue;
+        }
+        let (visible, hidden): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles
+            .into_iter()
+            .partition(|triangle| height_above(points, *triangle, *point) > epsilon);
+        triangles = hidden;
+        // Edges of the visible part which no other visible triangle shares are the horizon,
+        // the new triangles join it to the point
+        for triangle in visible.iter() {
+            for corner in 0..3 {
+                let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+                let shared = visible.iter().any(|other| {
+                    (0..3).any(|i| other[i] == to && other[(i + 1) % 3] == from)
+                });
+                if !shared {
+                    triangles.push([from, to, index]);
+                }
+            }
+        }
+    }
+
+    // Only keeps the points which ended up as corners
+    let mut remap = Vec::new();
+    remap.resize(points.len(), usize::MAX);
+    let mut vertices = Vec::new();
+    for triangle in triangles.iter_mut() {
+        for corner in triangle.iter_mut() {
+            if remap[*corner] == usize::MAX {
+                remap[*corner] = vertices.len();
+                vertices.push(points[*corner]);
+            }
+            *corner = remap[*corner];
+        }
+    }
+    Ok(ConvexHull {
+        vertices,
+        triangles,
+        node_dis: 1.0,
+    })
+}
@@ -1,76 +1,753 @@
+use alloc::borrow::Cow;
+use alloc::{vec, vec::Vec};
+use crate::basetype::{Coord, Vector3D};
+#[cfg(feature = "bench")]
+use crate::component::Camera;
+use crate::component::{Material, Shading};
+use crate::math::dither::{bayer_threshold, dither_levels, Dither};
+use crate::out::color::{ColorSource, Rgb};
+use crate::out::color_terminal::{ColorCell, ColorTerminalBuffer};
+use crate::out::half_block::HalfBlockBuffer;
+use crate::out::luminance::LuminanceBuffer;
+use crate::out::Buffer;
 use crate::out::terminal::SimpleTerminalBuffer;
-use crate::scene::Scene;
+#[cfg(feature = "bench")]
+use crate::out::zbuffer::ZBuffer;
+use crate::scene::snapshot::{ObjectSnapshot, RenderSnapshot};
 use crate::shape::rotator::Rotator;
+use crate::shape::shape_base::Point;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
 
 use core::f64;
-use std::f64::consts::PI;
+use core::f64::consts::PI;
 
-/// Perspective renderer implementation
-pub fn pers_proj(view: &Scene) -> SimpleTerminalBuffer {
-    let luminance_str = ".,-~:;=!*#$@@@";
-    let luminance: &[u8] = luminance_str.as_bytes();
-    let lumi_length = luminance_str.len();
+pub(crate) const LUMINANCE_RAMP: &[u8] = ".,-~:;=!*#$@@@".as_bytes();
+
+/// What ended up in a single cell of the output after projecting every point
+#[derive(Copy, Clone)]
+pub struct Fragment {
+    /// Index of the object in the scene this fragment was taken from
+    pub object: usize,
+    /// Position of the point in world coordinates
+    pub coord: Coord,
+    /// Distance between the point and the camera
+    pub depth: f64,
+    /// Brightness of the point between 0 and 1
+    pub luminance: f64,
+    /// Sum of the light colors reaching the point, each channel weighted by how much that light
+    /// illuminates it. One channel is 1.0 for a single fully lit white light
+    pub light: [f64; 3],
+    /// Color of the point itself, if it has one
+    pub color: Option<Rgb>,
+}
+
+/// Converts a luminance between 0 and 1 into a character of the luminance ramp
+pub fn luminance_char(luminance: f64) -> char {
+    let index = (luminance * LUMINANCE_RAMP.len() as f64) as usize;
+    LUMINANCE_RAMP[index.min(LUMINANCE_RAMP.len() - 1)] as char
+}
+
+/// Inverse of luminance_char, returns the luminance a character of the ramp stands for.
+/// Empty cells have no luminance, characters which are not part of the ramp count as fully lit.
+pub fn char_luminance(ch: char) -> Option<f64> {
+    if ch == ' ' {
+        return None;
+    }
+    // The ramp repeats its last character, the first occurence is the one that counts
+    let last = LUMINANCE_RAMP[LUMINANCE_RAMP.len() - 1];
+    let brightest = LUMINANCE_RAMP.iter().position(|c| *c == last).unwrap_or(1);
+    match LUMINANCE_RAMP.iter().position(|c| *c as char == ch) {
+        Some(index) => Some(index as f64 / brightest as f64),
+        None => Some(1.0),
+    }
+}
+
+/// Fragments with their luminance snapped onto the characters of the ramp, dithered the way
+/// the render options ask for. Left as they are without dithering
+fn quantized<'f>(view: &RenderSnapshot, fragments: &'f [Option<Fragment>]) -> Cow<'f, [Option<Fragment>]> {
+    if view.options.dither == Dither::None {
+        return Cow::Borrowed(fragments);
+    }
+    let mut luminance: Vec<Option<f64>> =
+        fragments.iter().map(|f| f.map(|f| f.luminance)).collect();
+    dither_levels(&mut luminance, view.size_x, LUMINANCE_RAMP.len(), view.options.dither);
+    let mut fragments = fragments.to_vec();
+    for (fragment, luminance) in fragments.iter_mut().zip(luminance) {
+        if let (Some(fragment), Some(luminance)) = (fragment, luminance) {
+            fragment.luminance = luminance;
+        }
+    }
+    Cow::Owned(fragments)
+}
+
+/// Character a fragment is drawn with, either the glyph of its material or taken from the
+/// luminance ramp
+fn fragment_char(view: &RenderSnapshot, fragment: &Fragment) -> char {
+    match view.objects[fragment.object].material.shading {
+        Shading::Glyph(glyph) => glyph,
+        _ => luminance_char(fragment.luminance),
+    }
+}
+
+/// Shades a point with the given normal using every light of the scene.
+/// Returns the luminance of the brightest light and the sum of the light colors, weighted by
+/// how much each light illuminates the point.
+pub fn shade_lit(view: &RenderSnapshot, point_coord: Coord, normal: Vector3D) -> (f64, [f64; 3]) {
+    let (brightest, _, light_sum) = shade_lights(view, point_coord, normal, false);
+    (brightest, light_sum)
+}
+
+/// Same as shade_lit, with the luminance of every light added together as well. Two sided
+/// surfaces are lit from behind as if they faced the light
+fn shade_lights(
+    view: &RenderSnapshot,
+    point_coord: Coord,
+    normal: Vector3D,
+    two_sided: bool,
+) -> (f64, f64, [f64; 3]) {
+    let mut luminance = 0.0;
+    let mut total = 0.0;
+    let mut light_sum = [0.0; 3];
+    for light in view.lights {
+        let light_coord = light.coord;
+
+        let to_light = (light_coord - point_coord).to_vector();
+        let lp = to_light.normalise();
+
+        let mut cos_angle = normal.dot(lp) / (normal.magnitude() * lp.magnitude());
+        if two_sided {
+            cos_angle = cos_angle.abs();
+        }
+        let angle = f64::acos(cos_angle);
+
+        let light_luminance = (1.0 - (angle / PI))
+            * light.cone_factor(lp.mul(-1.0))
+            * light.falloff.attenuation(to_light.magnitude());
+        // The brightest light decides the character
+        luminance = f64::max(luminance, light_luminance);
+        total += light_luminance;
+
+        let Rgb { r, g, b } = light.color;
+        light_sum[0] += light_luminance * r as f64 / 255.0;
+        light_sum[1] += light_luminance * g as f64 / 255.0;
+        light_sum[2] += light_luminance * b as f64 / 255.0;
+    }
+    (luminance, total, light_sum)
+}
+
+/// Shades a point of an object with the given material, seen from the camera.
+/// Returns the luminance, the light reaching the point and the color reflective materials give
+/// it. The normal is only computed for materials that need one.
+pub fn shade_material(
+    view: &RenderSnapshot,
+    material: &Material,
+    point_coord: Coord,
+    normal: impl FnOnce() -> Vector3D,
+) -> (f64, [f64; 3], Option<Rgb>) {
+    shade(view, material, point_coord, normal, false)
+}
+
+/// Same as shade_material, in linear space all lights add up instead of the brightest one
+/// deciding the luminance
+fn shade(
+    view: &RenderSnapshot,
+    material: &Material,
+    point_coord: Coord,
+    normal: impl FnOnce() -> Vector3D,
+    linear: bool,
+) -> (f64, [f64; 3], Option<Rgb>) {
+    if let Shading::Reflective(environment) = material.shading {
+        let incoming = (point_coord - view.camera.coord).to_vector().normalise();
+        let reflection = environment.sample(incoming.reflect(normal()));
+        return (reflection.brightness(), [1.0; 3], Some(reflection));
+    }
+    match material.fixed_luminance() {
+        Some(luminance) => (luminance, [luminance; 3], None),
+        None => {
+            let (brightest, total, light) = shade_lights(view, point_coord, normal(), material.two_sided);
+            (if linear { total } else { brightest }, light, None)
+        }
+    }
+}
+
+/// Cell of the buffer a point lands in, given its offset from the camera. None if it falls
+/// outside of the buffer
+fn screen_cell(
+    pv: Vector3D,
+    view_rotator: &Rotator,
+    (scale_x, scale_y): (f64, f64),
+    size_x: usize,
+    size_y: usize,
+) -> Option<(usize, usize)> {
+    // Camera transform by rotating pv with negative angle of camera
+    let cpv = pv.rotate(view_rotator);
+    let (cpv_x, cpv_y, cpv_z) = cpv.get();
 
+    let screen_x = (cpv_y / cpv_x) * scale_x + (size_x as f64 / 2.0);
+    let screen_y = -(cpv_z / cpv_x) * scale_y + (size_y as f64 / 2.0);
+    // NaN would be cast to the first cell, see DebugFlags::non_finite for finding its source
+    if !screen_x.is_finite() || !screen_y.is_finite() {
+        return None;
+    }
+    let buffer_x = screen_x as usize;
+    let buffer_y = screen_y as usize;
+
+    // Prevent going out of bounds
+    if buffer_x >= size_x || buffer_y >= size_y {
+        return None;
+    }
+    Some((buffer_x, buffer_y))
+}
+
+/// Cell of the bound buffer a point in world coordinates is drawn in, along with its distance
+/// from the camera. None if it is behind the camera or outside of the buffer
+pub fn project_coord(view: &RenderSnapshot, coord: Coord) -> Option<((usize, usize), f64)> {
+    let view_rotator = view.camera.view_rotator();
+    let pv = (coord - view.camera.coord).to_vector();
+    if pv.rotate(&view_rotator).x <= 0.0 {
+        return None;
+    }
     let (size_x, size_y) = view.get_buffer_size();
+    let scale = view.camera.projection_scale(size_x, size_y);
+    let cell = screen_cell(pv, &view_rotator, scale, size_x, size_y)?;
+    Some((cell, pv.magnitude()))
+}
 
-    let view_coord = view.camera.coord;
-    let v_a = view.camera.angle();
+/// Inner loop of the perspective renderer without any scene around it. Draws points given in
+/// world coordinates into the buffer, keeping the closest one of every cell in the z-buffer.
+/// Points are lit by a light at the camera, so surfaces facing it are the brightest.
+/// Neither buffer is cleared, which lets several calls draw into the same frame.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::buffer::*;
+/// use shapes_rs::components::Camera;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::renderer::project_points;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut cube = Shape::new(&cubegen);
+/// cube.translate_self(Coord::new(30.0, 0.0, 0.0));
+/// let camera = Camera::new(Coord::default(), Vector3D::new(1.0, 0.0, 0.0), Angle::from_degree(60.0));
+///
+/// let mut zbuffer = ZBuffer::new(20, 10);
+/// let mut buffer = SimpleTerminalBuffer::new(20, 10);
+/// project_points(&cube.points, &camera, &mut zbuffer, &mut buffer);
+///
+/// // The face pointing at the camera is drawn fully lit
+/// assert_eq!(buffer.get(5, 10), '@');
+/// assert_eq!(zbuffer.depth[5 * 20 + 10], 25.0);
+/// ```
+#[cfg(feature = "bench")]
+pub fn project_points(
+    points: &[Point],
+    camera: &Camera,
+    zbuffer: &mut ZBuffer,
+    buffer: &mut SimpleTerminalBuffer,
+) {
+    let (size_x, size_y) = (buffer.size_x, buffer.size_y);
+    let view_rotator = camera.view_rotator();
+    let scale = camera.projection_scale(size_x, size_y);
+
+    for point in points {
+        let pv = (point.rel_coord - camera.coord).to_vector();
+        let Some((buffer_x, buffer_y)) = screen_cell(pv, &view_rotator, scale, size_x, size_y)
+        else {
+            continue;
+        };
+        let depth = pv.magnitude();
+        if !zbuffer.test_and_set(buffer_x, buffer_y, depth) {
+            continue;
+        }
+        let facing = -point.normal.dot(pv) / (point.normal.magnitude() * depth);
+        buffer[buffer_y * size_x + buffer_x] = luminance_char(facing.max(0.0));
+    }
+}
 
-    let mut z_buffer = vec![f64::MAX; size_y * size_x];
+/// Projects every point of the scene onto the bound buffer and returns the closest fragment
+/// of each cell, row by row
+pub fn project_fragments(view: &RenderSnapshot) -> Vec<Option<Fragment>> {
+    let (size_x, size_y) = view.get_buffer_size();
+    project_fragments_sized(view, size_x, size_y)
+}
 
-    let mut projected_buffer = SimpleTerminalBuffer::new(size_x, size_y);
+/// Same as project_fragments, but onto a grid of the given size instead of the bound buffer
+pub fn project_fragments_sized(
+    view: &RenderSnapshot,
+    size_x: usize,
+    size_y: usize,
+) -> Vec<Option<Fragment>> {
+    project_fragments_with(view, size_x, size_y, false)
+}
 
-    let pb_dis = 1.0 / f64::tan(view.camera.fov.get() / 2.0) * ((size_y as f64) / 2.0);
+/// Projects the scene like project_fragments_sized. In linear space the luminance of all
+/// lights is added up and left unclamped, with the render options' curve not applied yet
+fn project_fragments_with(
+    view: &RenderSnapshot,
+    size_x: usize,
+    size_y: usize,
+    linear: bool,
+) -> Vec<Option<Fragment>> {
+    project_region(view, (size_x, size_y), Region::new(0, 0, size_x, size_y), linear)
+}
+
+/// Part of the buffer, in cells from the top left corner
+#[derive(Copy, Clone)]
+struct Region {
+    x: usize,
+    y: usize,
+    size_x: usize,
+    size_y: usize,
+}
+
+impl Region {
+    fn new(x: usize, y: usize, size_x: usize, size_y: usize) -> Self {
+        Self { x, y, size_x, size_y }
+    }
+}
+
+/// Projects the scene onto a buffer of the given size and returns the closest fragment of
+/// every cell inside of the region, row by row. Points outside of it are skipped before they
+/// are shaded
+fn project_region(
+    view: &RenderSnapshot,
+    (size_x, size_y): (usize, usize),
+    region: Region,
+    linear: bool,
+) -> Vec<Option<Fragment>> {
+    phase_span!("project", size_x = region.size_x, size_y = region.size_y);
+    let mut fragments: Vec<Option<Fragment>> = vec![None; region.size_y * region.size_x];
+
+    visit_points(view, size_x, size_y, |projected| {
+        let (buffer_x, buffer_y) = projected.cell;
+        let (Some(x), Some(y)) = (buffer_x.checked_sub(region.x), buffer_y.checked_sub(region.y))
+        else {
+            return;
+        };
+        if x >= region.size_x || y >= region.size_y {
+            return;
+        }
+        let obj = projected.obj;
+        // Screen door transparency, skipped cells neither draw nor occlude
+        if obj.material.opacity < bayer_threshold(buffer_x, buffer_y) {
+            return;
+        }
+
+        let cell = &mut fragments[y * region.size_x + x];
+        if let Some(stored) = cell {
+            // Objects without a depth test always win against those with one
+            let passes = match (obj.depth_test, view.objects[stored.object].depth_test) {
+                (false, true) => true,
+                (true, false) => false,
+                _ => view.options.depth_test.passes(projected.depth, stored.depth),
+            };
+            if !passes {
+                return;
+            }
+        }
+        *cell = Some(shade_point(view, &projected, linear));
+    });
+    fragments
+}
+
+/// A point of an object, moved into world coordinates and projected onto the buffer
+struct ProjectedPoint<'v> {
+    object: usize,
+    obj: &'v ObjectSnapshot<'v>,
+    point: &'v Point,
+    coord: Coord,
+    /// Distance from the camera
+    depth: f64,
+    cell: (usize, usize),
+}
 
-    for obj in &view.objects {
+/// Calls the function with every point of the visible objects which lands on the buffer and
+/// is not cut away by a clip plane
+fn visit_points<'v>(
+    view: &'v RenderSnapshot,
+    size_x: usize,
+    size_y: usize,
+    mut visit: impl FnMut(ProjectedPoint<'v>),
+) {
+    let view_coord = view.camera.coord;
+    let view_rotator = view.camera.view_rotator();
+    let scale = view.camera.projection_scale(size_x, size_y);
+
+    for (object_index, obj) in view.visible_objects() {
         let object_rotation = &obj.rotation;
         let object_coord = obj.location;
-        for point in &obj.shape.points {
+        for point in obj.points {
             let point_coord = point
                 .rel_coord
                 .to_vector()
-                .rotate(&object_rotation)
+                .rotate(object_rotation)
                 .as_coord()
                 + object_coord;
 
+            if view.clip_planes.iter().any(|plane| plane.clips(point_coord)) {
+                continue;
+            }
+
             // Distance between point and camera in vector
             let pv = (point_coord - view_coord).to_vector();
 
-            // Distance between point and camera in scalar
-            let pv_dis = pv.magnitude();
+            let Some(cell) = screen_cell(pv, &view_rotator, scale, size_x, size_y) else {
+                continue;
+            };
+            // A depth of NaN fails every depth test against it and would keep its cell forever
+            let depth = pv.magnitude();
+            if !depth.is_finite() {
+                continue;
+            }
 
-            // Camera transform by rotating pv with negative angle of camera
-            let cpv = pv.rotate(&Rotator::from_global(v_a.mul(-1.0)));
-            let (cpv_x, cpv_y, cpv_z) = cpv.get();
+            visit(ProjectedPoint {
+                object: object_index,
+                obj,
+                point,
+                coord: point_coord,
+                depth,
+                cell,
+            });
+        }
+    }
+}
 
-            let buffer_x = ((cpv_y / cpv_x) * pb_dis + (size_x as f64 / 2.0)) as usize;
-            let buffer_y = (-(cpv_z / cpv_x) * pb_dis + (size_y as f64 / 2.0)) as usize;
+/// Shades a projected point into the fragment it leaves in its cell
+fn shade_point(view: &RenderSnapshot, projected: &ProjectedPoint, linear: bool) -> Fragment {
+    let (obj, point) = (projected.obj, projected.point);
+    let (mut luminance, light, reflection) = shade(view, obj.material, projected.coord, || {
+        point.normal.rotate(&obj.rotation).normalise()
+    }, linear);
+    luminance *= 1.0 - point.occlusion;
 
-            // Prevent going out of bounds
-            if buffer_x >= size_x || buffer_y >= size_y {
-                continue;
+    let mut color = point.color.or(reflection);
+    if let (Some(texture), Some((u, v))) = (obj.texture, point.uv) {
+        let texel = texture.sample(u, v);
+        luminance *= texel.brightness();
+        color = color.or(Some(texel));
+    }
+
+    Fragment {
+        object: projected.object,
+        coord: projected.coord,
+        depth: projected.depth,
+        luminance: match linear {
+            true => (luminance + point.luminance_bias).max(0.0),
+            false => view.options.curve((luminance + point.luminance_bias).clamp(0.0, 1.0)),
+        },
+        light,
+        color,
+    }
+}
+
+/// Turns fragments into characters, cells without a fragment are cleared
+pub fn fragments_to_buffer(view: &RenderSnapshot, fragments: &[Option<Fragment>]) -> SimpleTerminalBuffer {
+    let (size_x, size_y) = view.get_buffer_size();
+    let mut projected_buffer = SimpleTerminalBuffer::filled(size_x, size_y, view.clear.ch);
+
+    for (index, fragment) in quantized(view, fragments).iter().enumerate() {
+        if let Some(fragment) = fragment {
+            projected_buffer[index] = fragment_char(view, fragment);
+        }
+    }
+    projected_buffer
+}
+
+/// Perspective renderer implementation
+pub fn pers_proj(view: &RenderSnapshot) -> SimpleTerminalBuffer {
+    fragments_to_buffer(view, &project_fragments(view))
+}
+
+/// Perspective renderer which keeps the luminance of every cell as a float in linear space,
+/// with every light adding to it and nothing clamped above 1. Turn it into characters with
+/// tone_map. Glyphs are ignored.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::components::Light3D;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::renderer::{pers_proj_hdr, pers_proj_tonemapped, tone_map, ToneMap};
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut scene = fixture_scene(40, 20);
+/// scene.add_object(Object::new(Coord::new(30.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+/// // A second light right next to the first one
+/// scene.add_light(Light3D::new(Coord::new(0.0, 1.0, 0.0)));
+///
+/// let hdr = pers_proj_hdr(&scene.snapshot());
+/// // The face towards both lights gets the light of both
+/// assert!(hdr.buffer.iter().flatten().any(|luminance| *luminance > 1.5));
+///
+/// scene.render_options_mut().tone_map = ToneMap::Reinhard;
+/// let frame = tone_map(&scene.snapshot(), &hdr);
+/// assert_eq!(frame.hash(), pers_proj_tonemapped(&scene.snapshot()).hash());
+/// ```
+pub fn pers_proj_hdr(view: &RenderSnapshot) -> LuminanceBuffer {
+    let (size_x, size_y) = view.get_buffer_size();
+    let fragments = project_fragments_with(view, size_x, size_y, true);
+    let luminance = fragments.iter().map(|f| f.map(|f| f.luminance)).collect();
+    LuminanceBuffer::new_with_buffer(size_x, size_y, luminance)
+}
+
+/// Final pass of the linear renderers, brings luminance back between 0 and 1 with the render
+/// options' tone map and then applies their curve and dithering before picking characters
+pub fn tone_map(view: &RenderSnapshot, hdr: &LuminanceBuffer) -> SimpleTerminalBuffer {
+    let mut luminance: Vec<Option<f64>> = hdr
+        .buffer
+        .iter()
+        .map(|cell| cell.map(|l| view.options.curve(view.options.tone_map.apply(l))))
+        .collect();
+    let mut frame = SimpleTerminalBuffer::filled(hdr.size_x, hdr.size_y, view.clear.ch);
+    if view.options.dither != Dither::None {
+        dither_levels(&mut luminance, hdr.size_x, LUMINANCE_RAMP.len(), view.options.dither);
+    }
+    for (index, cell) in luminance.iter().enumerate() {
+        if let Some(luminance) = cell {
+            frame[index] = luminance_char(*luminance);
+        }
+    }
+    frame
+}
+
+/// Renders in linear space and tone maps the result, see pers_proj_hdr
+pub fn pers_proj_tonemapped(view: &RenderSnapshot) -> SimpleTerminalBuffer {
+    tone_map(view, &pers_proj_hdr(view))
+}
+
+/// Perspective renderer which paints points from back to front instead of keeping the closest
+/// one of every cell, so objects which are not fully opaque are blended over whatever is behind
+/// them rather than drawn with a screen door pattern. Every projected point is kept and sorted,
+/// which takes more memory and time than the depth buffer of pers_proj. Objects without a depth
+/// test are painted last. Opaque scenes come out the same as with pers_proj using
+/// DepthTest::LessEqual.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::components::Material;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::renderer::{pers_proj, pers_proj_painter, DepthTest};
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut scene = fixture_scene(40, 20);
+/// let cube = scene.add_object(Object::new(Coord::new(30.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+/// scene.render_options_mut().depth_test = DepthTest::LessEqual;
+/// assert_eq!(pers_proj_painter(&scene.snapshot()).hash(), pers_proj(&scene.snapshot()).hash());
+///
+/// // Half see-through, every cell is drawn and dimmer than before
+/// let opaque = pers_proj_painter(&scene.snapshot());
+/// scene.object_mut(cube).unwrap().material = Material::new().with_opacity(0.5);
+/// let glass = pers_proj_painter(&scene.snapshot());
+/// assert!(opaque.buffer.iter().zip(glass.buffer.iter()).all(|(a, b)| (*a == ' ') == (*b == ' ')));
+/// assert_ne!(opaque.hash(), glass.hash());
+/// ```
+pub fn pers_proj_painter(view: &RenderSnapshot) -> SimpleTerminalBuffer {
+    let (size_x, size_y) = view.get_buffer_size();
+    let mut painted: Vec<(usize, Fragment)> = Vec::new();
+    {
+        phase_span!("project", size_x = size_x, size_y = size_y);
+        visit_points(view, size_x, size_y, |projected| {
+            if projected.obj.material.opacity <= 0.0 {
+                return;
             }
+            let (x, y) = projected.cell;
+            painted.push((y * size_x + x, shade_point(view, &projected, false)));
+        });
+    }
+    // Sorting is stable, points at the same depth keep their order
+    painted.sort_by(|(_, a), (_, b)| {
+        let overlay = |f: &Fragment| !view.objects[f.object].depth_test;
+        overlay(a).cmp(&overlay(b)).then(b.depth.total_cmp(&a.depth))
+    });
+
+    let mut cells: Vec<Option<Fragment>> = vec![None; size_x * size_y];
+    for (index, mut fragment) in painted {
+        let opacity = view.objects[fragment.object].material.opacity.min(1.0);
+        let below = cells[index].map_or(0.0, |f| f.luminance);
+        fragment.luminance = below * (1.0 - opacity) + fragment.luminance * opacity;
+        cells[index] = Some(fragment);
+    }
+    fragments_to_buffer(view, &cells)
+}
 
-            if z_buffer[buffer_y * size_x + buffer_x] > pv_dis {
-                z_buffer[buffer_y * size_x + buffer_x] = pv_dis;
+/// Perspective renderer which works through the buffer one tile at a time, each with a depth
+/// buffer of its own, so memory stays bounded by the tile size however large the buffer is.
+/// Every tile looks at all points again, which trades time for memory. Tiles are set by the
+/// render options, rounded up to a multiple of 4 cells so ordered dithering lines up, and are
+/// rendered on all cores with `parallel_tiles`. Error diffusion does not cross tile borders.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::renderer::{pers_proj, pers_proj_tiled};
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let mut scene = fixture_scene(60, 30);
+/// scene.add_object(Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+/// scene.render_options_mut().tile_size = (16, 8);
+/// scene.render_options_mut().parallel_tiles = true;
+///
+/// assert_eq!(pers_proj_tiled(&scene.snapshot()).hash(), pers_proj(&scene.snapshot()).hash());
+/// ```
+pub fn pers_proj_tiled(view: &RenderSnapshot) -> SimpleTerminalBuffer {
+    let (size_x, size_y) = view.get_buffer_size();
+    let round_up = |size: usize| size.max(1).div_ceil(4) * 4;
+    let (tile_x, tile_y) = (round_up(view.options.tile_size.0), round_up(view.options.tile_size.1));
+    let tiles: Vec<Region> = (0..size_y)
+        .step_by(tile_y)
+        .flat_map(|y| {
+            (0..size_x).step_by(tile_x).map(move |x| {
+                Region::new(x, y, tile_x.min(size_x - x), tile_y.min(size_y - y))
+            })
+        })
+        .collect();
 
-                let p_normal = point.normal.rotate(&object_rotation).normalise();
+    let render_tile = |region: &Region| -> Vec<char> {
+        let mut fragments = project_region(view, (size_x, size_y), *region, false);
+        if view.options.dither != Dither::None {
+            let mut luminance: Vec<Option<f64>> =
+                fragments.iter().map(|f| f.map(|f| f.luminance)).collect();
+            dither_levels(&mut luminance, region.size_x, LUMINANCE_RAMP.len(), view.options.dither);
+            for (fragment, luminance) in fragments.iter_mut().zip(luminance) {
+                if let (Some(fragment), Some(luminance)) = (fragment, luminance) {
+                    fragment.luminance = luminance;
+                }
+            }
+        }
+        fragments
+            .iter()
+            .map(|f| f.map_or(view.clear.ch, |f| fragment_char(view, &f)))
+            .collect()
+    };
 
-                let mut lumi_index: i32 = 0;
-                for light in &view.lights {
-                    let light_coord = light.coord;
+    #[cfg(feature = "std")]
+    let rendered: Vec<Vec<char>> = if view.options.parallel_tiles {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let per_thread = tiles.len().div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = tiles
+                .chunks(per_thread)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(render_tile).collect::<Vec<_>>()))
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("Tile thread panicked"))
+                .collect()
+        })
+    } else {
+        tiles.iter().map(render_tile).collect()
+    };
+    #[cfg(not(feature = "std"))]
+    let rendered: Vec<Vec<char>> = tiles.iter().map(render_tile).collect();
 
-                    let lp = (light_coord - point_coord).to_vector().normalise();
+    let mut buffer = SimpleTerminalBuffer::filled(size_x, size_y, view.clear.ch);
+    for (region, chars) in tiles.iter().zip(rendered) {
+        for (index, ch) in chars.into_iter().enumerate() {
+            buffer.set(region.y + index / region.size_x, region.x + index % region.size_x, ch);
+        }
+    }
+    buffer
+}
 
-                    let angle =
-                        f64::acos(p_normal.dot(lp) / (p_normal.magnitude() * lp.magnitude()));
+/// Depth range of the visible fragments as (closest depth, range), color renderers normalise
+/// depth over whatever is visible in the frame
+fn depth_range(fragments: &[Option<Fragment>]) -> (f64, f64) {
+    let (min_depth, max_depth) = fragments
+        .iter()
+        .flatten()
+        .fold((f64::MAX, f64::MIN), |(min, max), f| {
+            (min.min(f.depth), max.max(f.depth))
+        });
+    (min_depth, max_depth - min_depth)
+}
 
-                    lumi_index = ((1.0 - (angle / PI)) * (lumi_length as f64)) as i32;
+/// Color of a fragment, see pers_proj_color
+fn fragment_color(
+    view: &RenderSnapshot,
+    fragment: &Fragment,
+    (min_depth, depth_range): (f64, f64),
+) -> Rgb {
+    let material = &view.objects[fragment.object].material;
+    match fragment.color.or(material.base_color) {
+        Some(base_color) => base_color.lit(fragment.light),
+        None => {
+            let colormap = material.colormap.as_ref().unwrap_or(view.colormap);
+            let t = match view.color_source {
+                ColorSource::Luminance => fragment.luminance,
+                ColorSource::Depth if depth_range > 0.0 => {
+                    1.0 - (fragment.depth - min_depth) / depth_range
                 }
+                ColorSource::Depth => 1.0,
+            };
+            colormap.sample(t)
+        }
+    }
+}
 
-                projected_buffer[buffer_y * size_x + buffer_x] =
-                    luminance[lumi_index as usize] as char;
-            }
+/// Perspective renderer which also colors every cell.
+/// Points with a color of their own, textured points, reflective objects and objects whose material has a base color are lit by the colors of all lights added together.
+/// Otherwise the color is taken from the colormap of the object's material, or the scene's
+/// colormap if the object does not have one. Depending on the scene's color source the colormap
+/// is sampled with either the luminance or the depth of the cell.
+pub fn pers_proj_color(view: &RenderSnapshot) -> ColorTerminalBuffer {
+    let (size_x, size_y) = view.get_buffer_size();
+    let mut projected_buffer = ColorTerminalBuffer::filled(size_x, size_y, view.clear);
+    projected_buffer.background = view.background;
+
+    let fragments = project_fragments(view);
+    let range = depth_range(&fragments);
+
+    // Only the characters are dithered, colors keep the exact luminance
+    let characters = quantized(view, &fragments);
+    for (index, (fragment, character)) in fragments.iter().zip(characters.iter()).enumerate() {
+        if let (Some(fragment), Some(character)) = (fragment, character) {
+            let color = fragment_color(view, fragment, range);
+            projected_buffer[index] = ColorCell::new(fragment_char(view, character), color);
+        }
+    }
+    projected_buffer
+}
+
+/// Perspective renderer drawing colored pixels at twice the vertical resolution of the bound
+/// buffer, meant to be printed with half block characters. Colors are chosen the same way as
+/// pers_proj_color, glyphs are ignored.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::buffer::*;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::renderer::pers_proj_half_block;
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut scene = fixture_scene(30, 15);
+/// scene.add_object(Object::new(Coord::new(30.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+///
+/// let pixels = pers_proj_half_block(&scene.snapshot());
+/// assert_eq!(pixels.size(), (30, 30));
+/// assert!(pixels.get(15, 15).is_some());
+/// assert!(pixels.get(0, 0).is_none());
+/// ```
+pub fn pers_proj_half_block(view: &RenderSnapshot) -> HalfBlockBuffer {
+    let (size_x, size_y) = view.get_buffer_size();
+    let mut projected_buffer = HalfBlockBuffer::filled(size_x, size_y * 2, view.background);
+
+    // Every cell holds two pixels on top of each other
+    let mut camera = view.camera.clone();
+    camera.cell_aspect /= 2.0;
+    let fragments = project_fragments_sized(&view.clone().with_camera(camera), size_x, size_y * 2);
+    let range = depth_range(&fragments);
+
+    for (index, fragment) in fragments.iter().enumerate() {
+        if let Some(fragment) = fragment {
+            projected_buffer[index] = Some(fragment_color(view, fragment, range));
         }
     }
     projected_buffer
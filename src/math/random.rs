@@ -0,0 +1,63 @@
+//! Small seeded random number generator, so anything random renders the same way on every run
+//! and every platform.
+
+use crate::basetype::Vector3D;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+use core::f64::consts::PI;
+
+/// SplitMix64, fast and good enough for scattering points around. Not meant for anything
+/// security related.
+/// ### Example:
+/// ```
+/// use shapes_rs::sampling::Rng;
+///
+/// let mut a = Rng::new(42);
+/// let mut b = Rng::new(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+///
+/// let value = a.range(-1.0, 1.0);
+/// assert!((-1.0..1.0).contains(&value));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Value between 0 (inclusive) and 1 (exclusive)
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill the mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Value between min (inclusive) and max (exclusive)
+    pub fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    /// Direction picked evenly from all directions
+    pub fn unit_vector(&mut self) -> Vector3D {
+        let z = self.range(-1.0, 1.0);
+        let angle = self.range(0.0, 2.0 * PI);
+        let r = f64::sqrt(1.0 - z * z);
+        Vector3D {
+            x: r * f64::cos(angle),
+            y: r * f64::sin(angle),
+            z,
+        }
+    }
+}
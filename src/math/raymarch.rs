@@ -0,0 +1,89 @@
+use alloc::{vec, vec::Vec};
+use crate::basetype::{Coord, Vector3D};
+use crate::math::projection::{fragments_to_buffer, shade_material, Fragment};
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::scene::snapshot::RenderSnapshot;
+
+const MAX_STEPS: usize = 128;
+/// Rays closer than this to a surface count as a hit
+const HIT_DISTANCE: f64 = 0.01;
+/// Rays travelling further than this count as a miss
+const MAX_DISTANCE: f64 = 10000.0;
+/// Offset used to sample the distance field around a hit when estimating its normal
+const NORMAL_STEP: f64 = 0.001;
+
+/// Distance from a point to the closest surface of the scene, with clip planes cutting away
+/// their removed half. Returns the index of the closest object as well, or None if no object
+/// has a distance function.
+fn scene_distance(view: &RenderSnapshot, point: Coord) -> Option<(f64, usize)> {
+    let clip = view
+        .clip_planes
+        .iter()
+        .fold(f64::MIN, |acc, plane| acc.max(-plane.distance(point)));
+    view.visible_objects()
+        .filter_map(|(index, obj)| obj.distance(point).map(|d| (d.max(clip), index)))
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+}
+
+/// Estimates the surface normal at a point from the gradient of the distance field
+fn scene_normal(view: &RenderSnapshot, point: Coord, fallback: Vector3D) -> Vector3D {
+    let sample = |dx: f64, dy: f64, dz: f64| {
+        scene_distance(view, point + Coord::new(dx, dy, dz)).map_or(0.0, |(d, _)| d)
+    };
+    let x = sample(NORMAL_STEP, 0.0, 0.0) - sample(-NORMAL_STEP, 0.0, 0.0);
+    let y = sample(0.0, NORMAL_STEP, 0.0) - sample(0.0, -NORMAL_STEP, 0.0);
+    let z = sample(0.0, 0.0, NORMAL_STEP) - sample(0.0, 0.0, -NORMAL_STEP);
+    if x == 0.0 && y == 0.0 && z == 0.0 {
+        return fallback;
+    }
+    Vector3D::new(x, y, z).normalise()
+}
+
+/// Marches a ray through every cell of the bound buffer against the distance functions of the
+/// scene's objects, returning the fragment hit in each cell
+pub fn raymarch_fragments(view: &RenderSnapshot) -> Vec<Option<Fragment>> {
+    let (size_x, size_y) = view.get_buffer_size();
+    phase_span!("raymarch", size_x = size_x, size_y = size_y);
+    let mut fragments: Vec<Option<Fragment>> = vec![None; size_y * size_x];
+
+    for buffer_y in 0..size_y {
+        for buffer_x in 0..size_x {
+            let (origin, direction) = view.camera.ray_through(buffer_x, buffer_y, (size_x, size_y));
+
+            let mut travelled = 0.0;
+            for _ in 0..MAX_STEPS {
+                let point = origin + direction.mul(travelled).as_coord();
+                let (distance, object_index) = match scene_distance(view, point) {
+                    Some(hit) => hit,
+                    None => break,
+                };
+                if distance < HIT_DISTANCE {
+                    let obj = &view.objects[object_index];
+                    let (luminance, light, color) = shade_material(view, obj.material, point, || {
+                        scene_normal(view, point, direction.mul(-1.0))
+                    });
+                    fragments[buffer_y * size_x + buffer_x] = Some(Fragment {
+                        object: object_index,
+                        coord: point,
+                        depth: travelled,
+                        luminance: view.options.curve(luminance),
+                        light,
+                        color,
+                    });
+                    break;
+                }
+                travelled += distance;
+                if travelled > MAX_DISTANCE {
+                    break;
+                }
+            }
+        }
+    }
+    fragments
+}
+
+/// Raymarching renderer. Only objects with a distance function are drawn, in exchange their
+/// silhouettes are smooth and every covered cell is filled.
+pub fn raymarch_proj(view: &RenderSnapshot) -> SimpleTerminalBuffer {
+    fragments_to_buffer(view, &raymarch_fragments(view))
+}
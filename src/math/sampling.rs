@@ -0,0 +1,93 @@
+//! Evenly spread directions, for anything that has to look around a point in every direction
+//! such as ambient occlusion, or place points over a sphere.
+
+use alloc::vec::Vec;
+
+use crate::basetype::Vector3D;
+use crate::math::random::Rng;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+use core::f64::consts::PI;
+
+/// Direction on the unit sphere with the given height and angle around the Z axis
+fn sphere_direction(z: f64, angle: f64) -> Vector3D {
+    let r = f64::sqrt((1.0 - z * z).max(0.0));
+    Vector3D {
+        x: r * f64::cos(angle),
+        y: r * f64::sin(angle),
+        z,
+    }
+}
+
+/// Directions spread over the whole sphere along a Fibonacci spiral, which covers it almost
+/// evenly for any count without any randomness
+/// ### Example:
+/// ```
+/// use shapes_rs::sampling::fibonacci_sphere;
+///
+/// let directions: Vec<_> = fibonacci_sphere(100).collect();
+/// assert_eq!(directions.len(), 100);
+/// assert!(directions.iter().all(|d| (d.magnitude() - 1.0).abs() < 1e-9));
+///
+/// // Just as many point up as down
+/// let up = directions.iter().filter(|d| d.z > 0.0).count();
+/// assert_eq!(up, 50);
+/// ```
+pub fn fibonacci_sphere(count: usize) -> impl Iterator<Item = Vector3D> {
+    // Turning by the golden angle keeps neighbouring points from lining up
+    let golden_angle = PI * (3.0 - f64::sqrt(5.0));
+    (0..count).map(move |i| {
+        let z = 1.0 - (2.0 * i as f64 + 1.0) / count as f64;
+        sphere_direction(z, golden_angle * i as f64)
+    })
+}
+
+/// Random directions over the whole sphere, one picked from each of `strata_z` times
+/// `strata_angle` equally large patches. Spread more evenly than picking all of them at random
+/// while still avoiding the patterns fixed directions leave behind
+/// ### Example:
+/// ```
+/// use shapes_rs::sampling::Rng;
+/// use shapes_rs::sampling::stratified_sphere;
+///
+/// let mut rng = Rng::new(3);
+/// let directions = stratified_sphere(4, 8, &mut rng);
+/// assert_eq!(directions.len(), 32);
+///
+/// // Every band of height gets its share
+/// let top_band = directions.iter().filter(|d| d.z > 0.5).count();
+/// assert_eq!(top_band, 8);
+/// ```
+pub fn stratified_sphere(strata_z: usize, strata_angle: usize, rng: &mut Rng) -> Vec<Vector3D> {
+    let mut directions = Vec::with_capacity(strata_z * strata_angle);
+    for band in 0..strata_z {
+        for sector in 0..strata_angle {
+            // Equal steps of height cover equal areas of the sphere
+            let z = 1.0 - 2.0 * (band as f64 + rng.next_f64()) / strata_z as f64;
+            let angle = 2.0 * PI * (sector as f64 + rng.next_f64()) / strata_angle as f64;
+            directions.push(sphere_direction(z, angle));
+        }
+    }
+    directions
+}
+
+/// Turns directions over the whole sphere into directions over the half facing along the
+/// normal, by flipping those pointing away from it
+/// ### Example:
+/// ```
+/// use shapes_rs::base::Vector3D;
+/// use shapes_rs::sampling::{fibonacci_sphere, hemisphere};
+///
+/// let normal = Vector3D::new(1.0, 0.0, 0.0);
+/// assert!(hemisphere(fibonacci_sphere(64), normal).all(|d| d.dot(normal) >= 0.0));
+/// ```
+pub fn hemisphere(
+    directions: impl IntoIterator<Item = Vector3D>,
+    normal: Vector3D,
+) -> impl Iterator<Item = Vector3D> {
+    directions.into_iter().map(move |direction| match direction.dot(normal) < 0.0 {
+        true => direction.mul(-1.0),
+        false => direction,
+    })
+}
@@ -0,0 +1,119 @@
+//! Filters which look at the frames before the current one, to keep still scenes from
+//! flickering.
+
+use alloc::vec::Vec;
+
+use crate::math::projection::Fragment;
+
+/// Keeps cells from shimmering when neighbouring points of a point cloud win the same cell on
+/// alternate frames. A cell keeps its previous luminance while the new one stays within
+/// `luminance_threshold` of it and the surface stays within `depth_tolerance`, and cells which
+/// suddenly turn empty keep their previous fragment for up to `hold_frames` frames.
+/// Moving objects get past both thresholds quickly, so this is meant for still scenes seen from
+/// fixed cameras. See Animator::set_temporal_smoothing
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::renderer::project_fragments;
+/// use shapes_rs::renderer::TemporalFilter;
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut scene = fixture_scene(40, 20);
+/// scene.add_object(Object::new(Coord::new(30.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+/// let first = project_fragments(&scene.snapshot());
+/// let cell = first.iter().position(|f| f.is_some()).unwrap();
+///
+/// let mut filter = TemporalFilter::new();
+/// filter.apply(&mut first.clone());
+///
+/// // A slightly brighter point wins the cell, the cell stays as it was
+/// let mut second = first.clone();
+/// second[cell].as_mut().unwrap().luminance += 0.05;
+/// filter.apply(&mut second);
+/// assert_eq!(second[cell].unwrap().luminance, first[cell].unwrap().luminance);
+///
+/// // Nothing lands in the cell for a frame, the last fragment is held
+/// let mut third = first.clone();
+/// third[cell] = None;
+/// filter.apply(&mut third);
+/// assert!(third[cell].is_some());
+/// ```
+#[derive(Clone)]
+pub struct TemporalFilter {
+    /// Largest change of luminance which is ignored
+    pub luminance_threshold: f64,
+    /// Largest change of depth which still counts as the same surface
+    pub depth_tolerance: f64,
+    /// How many frames in a row an empty cell keeps its last fragment
+    pub hold_frames: usize,
+    /// Fragment every cell was drawn with in the last frame, along with how many frames it
+    /// has been held for
+    previous: Vec<Option<(Fragment, usize)>>,
+}
+
+impl Default for TemporalFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemporalFilter {
+    pub fn new() -> Self {
+        Self {
+            luminance_threshold: 0.1,
+            depth_tolerance: 1.0,
+            hold_frames: 1,
+            previous: Vec::new(),
+        }
+    }
+
+    pub fn with_luminance_threshold(mut self, luminance_threshold: f64) -> Self {
+        self.luminance_threshold = luminance_threshold;
+        self
+    }
+
+    pub fn with_depth_tolerance(mut self, depth_tolerance: f64) -> Self {
+        self.depth_tolerance = depth_tolerance;
+        self
+    }
+
+    pub fn with_hold_frames(mut self, hold_frames: usize) -> Self {
+        self.hold_frames = hold_frames;
+        self
+    }
+
+    /// Forgets every frame seen so far, the next one passes through unchanged
+    pub fn reset(&mut self) {
+        self.previous.clear();
+    }
+
+    /// Smooths the fragments of a frame against the frames before it and remembers the result.
+    /// Frames of a different size than the last one pass through unchanged
+    pub fn apply(&mut self, fragments: &mut [Option<Fragment>]) {
+        if self.previous.len() != fragments.len() {
+            self.previous = fragments.iter().map(|f| f.map(|f| (f, 0))).collect();
+            return;
+        }
+        for (fragment, previous) in fragments.iter_mut().zip(self.previous.iter_mut()) {
+            *previous = match (fragment.as_mut(), *previous) {
+                (Some(current), Some((last, _))) => {
+                    let same_surface = (current.depth - last.depth).abs() <= self.depth_tolerance;
+                    let small_change =
+                        (current.luminance - last.luminance).abs() <= self.luminance_threshold;
+                    if same_surface && small_change {
+                        current.luminance = last.luminance;
+                    }
+                    Some((*current, 0))
+                }
+                (None, Some((last, held))) if held < self.hold_frames => {
+                    *fragment = Some(last);
+                    Some((last, held + 1))
+                }
+                (current, _) => current.map(|f| (*f, 0)),
+            };
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use crate::basetype::Coord;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
 
 pub fn get_distance(point1: &Coord, point2: &Coord) -> f64 {
     let diff_x = point1.x - point2.x;
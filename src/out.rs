@@ -1,4 +1,24 @@
+pub mod color;
+pub mod color_terminal;
+pub mod graphics;
+pub mod half_block;
+pub mod luminance;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod png;
+#[cfg(feature = "std")]
+pub mod record;
+pub mod rgba;
+#[cfg(feature = "std")]
+pub mod sink;
 pub mod terminal;
+pub mod zbuffer;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "window")]
+pub mod window;
+
+use crate::error::{Result, ShapesError};
 
 /// Trait which provides an interface for algoritms and outputs that make use of it.
 /// Traits must be constructed from their own ```::new(...)``` function, 
@@ -7,10 +27,104 @@ pub trait Buffer {
     type Data;
     type Container;
 
+    /// Panics if the cell is out of bounds, see try_get for a non panicking version
     fn get(&self, y: usize, x: usize) -> Self::Data;
+    /// Panics if the cell is out of bounds, see try_set for a non panicking version
     fn set(&mut self, y: usize, x: usize, val: Self::Data);
+    #[cfg(feature = "std")]
     fn print(&self);
     fn replace_buffer(&mut self, new_buffer: Self::Container);
     fn replace_buffer_self(&mut self, new_buffer: Self);
     fn new_with_buffer(size_x: usize, size_y: usize, buffer: Self::Container) -> Self;
 }
+
+/// Buffer which knows its size, for checking cells against it and working on the whole
+/// buffer. Apart from Buffer, so buffers which only implement that one keep working
+pub trait SizedBuffer: Buffer {
+    /// Size of the buffer as (x, y)
+    fn size(&self) -> (usize, usize);
+
+    /// Returns the value of a cell, fails if it is out of bounds
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    ///
+    /// let buffer = SimpleTerminalBuffer::new(3, 2);
+    ///
+    /// assert_eq!(buffer.try_get(1, 2), Ok(' '));
+    /// assert!(buffer.try_get(2, 0).is_err());
+    /// ```
+    fn try_get(&self, y: usize, x: usize) -> Result<Self::Data> {
+        self.check_bounds(y, x)?;
+        Ok(self.get(y, x))
+    }
+
+    /// Sets the value of a cell, fails if it is out of bounds
+    fn try_set(&mut self, y: usize, x: usize, val: Self::Data) -> Result<()> {
+        self.check_bounds(y, x)?;
+        self.set(y, x, val);
+        Ok(())
+    }
+
+    /// Copies another buffer into this one with its top left corner at the given cell,
+    /// whatever does not fit is cut off
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    ///
+    /// let mut buffer = SimpleTerminalBuffer::filled(4, 2, '.');
+    /// buffer.blit(&SimpleTerminalBuffer::filled(2, 2, '#'), 3, 1);
+    ///
+    /// assert_eq!(buffer.to_string(), "....\n...#");
+    /// ```
+    fn blit(&mut self, other: &Self, x: usize, y: usize)
+    where
+        Self: Sized,
+    {
+        let (size_x, size_y) = self.size();
+        let (other_x, other_y) = other.size();
+        for oy in 0..other_y.min(size_y.saturating_sub(y)) {
+            for ox in 0..other_x.min(size_x.saturating_sub(x)) {
+                self.set(y + oy, x + ox, other.get(oy, ox));
+            }
+        }
+    }
+
+    /// Copy of the buffer resized to the given size, every cell taking the value of the cell
+    /// closest to it. Panics if the buffer is empty but the new size is not
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    ///
+    /// let small: SimpleTerminalBuffer = "ab\ncd".parse().unwrap();
+    ///
+    /// assert_eq!(small.scaled(4, 2).to_string(), "aabb\nccdd");
+    /// assert_eq!(small.scaled(1, 1).to_string(), "a");
+    /// ```
+    fn scaled(&self, new_x: usize, new_y: usize) -> Self
+    where
+        Self: Sized,
+        Self::Container: FromIterator<Self::Data>,
+    {
+        let (size_x, size_y) = self.size();
+        let cells = (0..new_y)
+            .flat_map(|y| (0..new_x).map(move |x| (x, y)))
+            .map(|(x, y)| self.get(y * size_y / new_y, x * size_x / new_x))
+            .collect();
+        Self::new_with_buffer(new_x, new_y, cells)
+    }
+
+    /// Fails if the cell is out of bounds
+    fn check_bounds(&self, y: usize, x: usize) -> Result<()> {
+        let (size_x, size_y) = self.size();
+        if x >= size_x || y >= size_y {
+            return Err(ShapesError::OutOfBounds {
+                x,
+                y,
+                size_x,
+                size_y,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Plain 24 bit color
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Linearly interpolates between two colors, t being between 0 and 1
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::color::Rgb;
+    ///
+    /// let black = Rgb::new(0, 0, 0);
+    /// let white = Rgb::new(255, 255, 255);
+    ///
+    /// assert_eq!(black.lerp(white, 0.5), Rgb::new(128, 128, 128));
+    /// ```
+    pub fn lerp(&self, other: Rgb, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Self {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+        }
+    }
+
+    /// Scales every channel with the matching channel of the given light intensity and clamps
+    /// the result. Intensities are usually between 0 and 1 but may go above when several lights
+    /// add up.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::color::Rgb;
+    ///
+    /// let white = Rgb::new(255, 255, 255);
+    ///
+    /// assert_eq!(white.lit([1.0, 0.0, 0.5]), Rgb::new(255, 0, 128));
+    /// assert_eq!(Rgb::new(200, 0, 0).lit([2.0, 2.0, 2.0]), Rgb::new(255, 0, 0));
+    /// ```
+    pub fn lit(&self, intensity: [f64; 3]) -> Self {
+        let scale = |c: u8, i: f64| (c as f64 * i).round().clamp(0.0, 255.0) as u8;
+        Self {
+            r: scale(self.r, intensity[0]),
+            g: scale(self.g, intensity[1]),
+            b: scale(self.b, intensity[2]),
+        }
+    }
+
+    /// Perceived brightness between 0 and 1
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::color::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(255, 255, 255).brightness(), 1.0);
+    /// assert!(Rgb::new(0, 255, 0).brightness() > Rgb::new(0, 0, 255).brightness());
+    /// ```
+    pub fn brightness(&self) -> f64 {
+        (2126 * self.r as u32 + 7152 * self.g as u32 + 722 * self.b as u32) as f64 / 2_550_000.0
+    }
+
+    /// Returns the closest color of the 6x6x6 color cube of the 256 color palette
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::color::Rgb;
+    ///
+    /// assert_eq!(Rgb::new(0, 0, 0).to_ansi256(), 16);
+    /// assert_eq!(Rgb::new(255, 255, 255).to_ansi256(), 231);
+    /// ```
+    pub fn to_ansi256(&self) -> u8 {
+        let to_cube = |c: u8| ((c as u16 * 5 + 127) / 255) as u8;
+        16 + 36 * to_cube(self.r) + 6 * to_cube(self.g) + to_cube(self.b)
+    }
+}
+
+/// How colors are written to the terminal
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorMode {
+    /// 24 bit colors, supported by most modern terminals
+    TrueColor,
+    /// The 256 color palette, for terminals that can't do true colors
+    Ansi256,
+}
+
+/// Which value of a rendered cell is used to sample the colormap
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorSource {
+    /// Brighter cells sample further along the colormap
+    Luminance,
+    /// Closer cells sample further along the colormap
+    Depth,
+}
+
+const VIRIDIS: [(f64, Rgb); 9] = [
+    (0.0, Rgb::new(68, 1, 84)),
+    (0.125, Rgb::new(71, 44, 122)),
+    (0.25, Rgb::new(59, 81, 139)),
+    (0.375, Rgb::new(44, 113, 142)),
+    (0.5, Rgb::new(33, 144, 141)),
+    (0.625, Rgb::new(39, 173, 129)),
+    (0.75, Rgb::new(92, 200, 99)),
+    (0.875, Rgb::new(170, 220, 50)),
+    (1.0, Rgb::new(253, 231, 37)),
+];
+
+const INFERNO: [(f64, Rgb); 9] = [
+    (0.0, Rgb::new(0, 0, 4)),
+    (0.125, Rgb::new(31, 12, 72)),
+    (0.25, Rgb::new(85, 15, 109)),
+    (0.375, Rgb::new(136, 34, 106)),
+    (0.5, Rgb::new(186, 54, 85)),
+    (0.625, Rgb::new(227, 89, 51)),
+    (0.75, Rgb::new(249, 140, 10)),
+    (0.875, Rgb::new(249, 201, 50)),
+    (1.0, Rgb::new(252, 255, 164)),
+];
+
+const GRAYSCALE: [(f64, Rgb); 2] = [(0.0, Rgb::new(0, 0, 0)), (1.0, Rgb::new(255, 255, 255))];
+
+/// Maps a value between 0 and 1 to a color
+/// ### Example:
+/// ```
+/// use shapes_rs::color::{Colormap, Rgb};
+///
+/// let custom = Colormap::Custom(vec![(0.0, Rgb::new(255, 0, 0)), (1.0, Rgb::new(0, 0, 255))]);
+///
+/// assert_eq!(custom.sample(0.0), Rgb::new(255, 0, 0));
+/// assert_eq!(custom.sample(1.0), Rgb::new(0, 0, 255));
+/// assert_eq!(Colormap::Grayscale.sample(2.0), Rgb::new(255, 255, 255));
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum Colormap {
+    #[default]
+    Grayscale,
+    Viridis,
+    Inferno,
+    /// Color stops given as (position, color), sorted by position
+    Custom(Vec<(f64, Rgb)>),
+}
+
+impl Colormap {
+    /// Returns the color at the given position, values outside of 0 to 1 are clamped
+    pub fn sample(&self, t: f64) -> Rgb {
+        let stops: &[(f64, Rgb)] = match self {
+            Colormap::Grayscale => &GRAYSCALE,
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Inferno => &INFERNO,
+            Colormap::Custom(stops) => stops,
+        };
+        sample_stops(stops, t)
+    }
+}
+
+fn sample_stops(stops: &[(f64, Rgb)], t: f64) -> Rgb {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Rgb::new(255, 255, 255),
+    };
+    let t = if t.is_nan() { 0.0 } else { t };
+    if t <= first.0 {
+        return first.1;
+    }
+    if t >= last.0 {
+        return last.1;
+    }
+    for pair in stops.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        if t <= end.0 {
+            let span = end.0 - start.0;
+            if span <= 0.0 {
+                return end.1;
+            }
+            return start.1.lerp(end.1, (t - start.0) / span);
+        }
+    }
+    last.1
+}
@@ -0,0 +1,163 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use core::ops::{Index, IndexMut};
+
+use super::color::{ColorMode, Rgb};
+use super::{Buffer, SizedBuffer};
+
+/// A single character together with the color it is drawn in
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ColorCell {
+    pub ch: char,
+    pub color: Rgb,
+}
+
+impl ColorCell {
+    pub fn new(ch: char, color: Rgb) -> Self {
+        Self { ch, color }
+    }
+
+    /// An empty cell, drawn as a white space
+    pub fn empty() -> Self {
+        Self {
+            ch: ' ',
+            color: Rgb::new(255, 255, 255),
+        }
+    }
+}
+
+/// Terminal buffer where every character carries its own color, printed with ANSI escapes
+#[derive(Clone)]
+pub struct ColorTerminalBuffer {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub mode: ColorMode,
+    pub buffer: Vec<ColorCell>,
+    /// Color behind every cell, the background of the terminal shows if not set
+    pub background: Option<Rgb>,
+}
+
+impl ColorTerminalBuffer {
+    /// Creates an empty buffer which prints in true color
+    pub fn new(size_x: usize, size_y: usize) -> Self {
+        Self::filled(size_x, size_y, ColorCell::empty())
+    }
+
+    /// Creates a buffer which prints in true color with every cell set to the given one
+    pub fn filled(size_x: usize, size_y: usize, cell: ColorCell) -> Self {
+        Self {
+            size_x,
+            size_y,
+            mode: ColorMode::TrueColor,
+            buffer: vec![cell; size_y * size_x],
+            background: None,
+        }
+    }
+
+    /// Sets the escape codes used when printing
+    pub fn with_mode(mut self, mode: ColorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the color drawn behind every cell
+    pub fn with_background(mut self, background: Rgb) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    fn color_escape(&self, color: Rgb, layer: u8) -> String {
+        let Rgb { r, g, b } = color;
+        match self.mode {
+            ColorMode::TrueColor => format!("\x1b[{}8;2;{};{};{}m", layer, r, g, b),
+            ColorMode::Ansi256 => format!("\x1b[{}8;5;{}m", layer, color.to_ansi256()),
+        }
+    }
+
+    /// Returns the buffer as a string with ANSI color escapes, rows separated by newlines
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::color::*;
+    ///
+    /// let mut buffer = ColorTerminalBuffer::new(1, 1).with_mode(ColorMode::Ansi256);
+    /// buffer.set(0, 0, ColorCell::new('@', Rgb::new(255, 0, 0)));
+    ///
+    /// assert_eq!(buffer.to_ansi(), "\x1b[38;5;196m@\x1b[0m\n");
+    ///
+    /// // Empty cells show the background as well
+    /// let navy = ColorTerminalBuffer::new(2, 1).with_background(Rgb::new(0, 0, 80));
+    /// assert_eq!(navy.to_ansi(), "\x1b[48;2;0;0;80m \x1b[48;2;0;0;80m \x1b[0m\n");
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        let background = self.background.map(|color| self.color_escape(color, 4));
+        let mut out = String::new();
+        for y in 0..self.size_y {
+            for cell in &self.buffer[y * self.size_x..(y + 1) * self.size_x] {
+                if let Some(background) = &background {
+                    out.push_str(background);
+                }
+                if cell.ch != ' ' {
+                    out.push_str(&self.color_escape(cell.color, 3));
+                }
+                out.push(cell.ch);
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+}
+
+impl Buffer for ColorTerminalBuffer {
+    type Data = ColorCell;
+    type Container = Vec<ColorCell>;
+
+    fn new_with_buffer(size_x: usize, size_y: usize, buffer: Vec<ColorCell>) -> Self {
+        let mut ret = Self::new(size_x, size_y);
+        ret.replace_buffer(buffer);
+        ret
+    }
+
+    fn replace_buffer(&mut self, new_buffer: Vec<ColorCell>) {
+        self.buffer = new_buffer;
+    }
+
+    fn replace_buffer_self(&mut self, new_buffer: Self) {
+        self.replace_buffer(new_buffer.buffer);
+    }
+
+    fn get(&self, y: usize, x: usize) -> ColorCell {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x]
+    }
+
+    fn set(&mut self, y: usize, x: usize, val: ColorCell) {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x] = val;
+    }
+
+    #[cfg(feature = "std")]
+    fn print(&self) {
+        print!("{}", self.to_ansi());
+    }
+}
+
+impl SizedBuffer for ColorTerminalBuffer {
+    fn size(&self) -> (usize, usize) {
+        (self.size_x, self.size_y)
+    }
+}
+
+impl Index<usize> for ColorTerminalBuffer {
+    type Output = ColorCell;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.buffer[index]
+    }
+}
+
+impl IndexMut<usize> for ColorTerminalBuffer {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.buffer[index]
+    }
+}
@@ -0,0 +1,227 @@
+//! Encodes frames as inline images for terminals that support a graphics protocol, giving a
+//! much higher fidelity than characters while staying inside of the terminal.
+//! Both encoders take RGBA pixels as produced by the `rgba` module.
+
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::Write;
+
+#[cfg(feature = "std")]
+use super::rgba::terminal_to_rgba;
+#[cfg(feature = "std")]
+use super::sink::FrameSink;
+#[cfg(feature = "std")]
+use super::terminal::SimpleTerminalBuffer;
+
+/// Largest payload of a single kitty graphics escape
+const KITTY_CHUNK: usize = 4096;
+
+/// Which graphics protocol to encode frames with
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GraphicsProtocol {
+    /// Supported by xterm, mlterm, foot, WezTerm and others
+    Sixel,
+    /// Supported by kitty, WezTerm, Konsole and others
+    Kitty,
+}
+
+impl GraphicsProtocol {
+    /// Encodes RGBA pixels with this protocol
+    pub fn encode(&self, rgba: &[u8], width: usize, height: usize) -> String {
+        match self {
+            GraphicsProtocol::Sixel => encode_sixel(rgba, width, height),
+            GraphicsProtocol::Kitty => encode_kitty(rgba, width, height),
+        }
+    }
+}
+
+/// Index of a color in the 6x6x6 palette used for sixel images
+fn palette_index(r: u8, g: u8, b: u8) -> usize {
+    let to_cube = |c: u8| (c as usize * 5 + 127) / 255;
+    36 * to_cube(r) + 6 * to_cube(g) + to_cube(b)
+}
+
+/// Encodes RGBA pixels as a sixel image, colors are reduced to a palette of 216 colors and
+/// transparency is ignored
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::encode_sixel;
+///
+/// // A single red pixel
+/// let sixel = encode_sixel(&[255, 0, 0, 255], 1, 1);
+///
+/// assert!(sixel.starts_with("\x1bPq\"1;1;1;1"));
+/// assert!(sixel.contains("#180;2;100;0;0"));
+/// assert!(sixel.ends_with("#180@-\x1b\\"));
+/// ```
+pub fn encode_sixel(rgba: &[u8], width: usize, height: usize) -> String {
+    let mut out = String::new();
+    let _ = write!(out, "\x1bPq\"1;1;{};{}", width, height);
+
+    let indices: Vec<usize> = rgba
+        .chunks_exact(4)
+        .map(|p| palette_index(p[0], p[1], p[2]))
+        .collect();
+
+    let mut used = [false; 216];
+    for index in &indices {
+        used[*index] = true;
+    }
+    for (index, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let to_percent = |c: usize| c * 100 / 5;
+        let _ = write!(
+            out,
+            "#{};2;{};{};{}",
+            index,
+            to_percent(index / 36),
+            to_percent(index / 6 % 6),
+            to_percent(index % 6)
+        );
+    }
+
+    // Every band covers six rows of pixels, each color of a band is drawn in its own pass
+    for band in 0..height.div_ceil(6) {
+        let mut band_colors = [false; 216];
+        let mut bits = vec![0u8; width * 216];
+        for row in 0..6 {
+            let y = band * 6 + row;
+            if y >= height {
+                break;
+            }
+            for x in 0..width {
+                let index = indices[y * width + x];
+                band_colors[index] = true;
+                bits[index * width + x] |= 1 << row;
+            }
+        }
+        let mut first = true;
+        for (index, _) in band_colors.iter().enumerate().filter(|(_, used)| **used) {
+            if !first {
+                out.push('$');
+            }
+            first = false;
+            let _ = write!(out, "#{}", index);
+            write_sixel_row(&mut out, &bits[index * width..(index + 1) * width]);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Writes one row of sixels, compressing repeated ones
+fn write_sixel_row(out: &mut String, row: &[u8]) {
+    let mut x = 0;
+    while x < row.len() {
+        let bits = row[x];
+        let mut run = 1;
+        while x + run < row.len() && row[x + run] == bits {
+            run += 1;
+        }
+        let ch = (63 + bits) as char;
+        if run > 3 {
+            let _ = write!(out, "!{}{}", run, ch);
+        } else {
+            for _ in 0..run {
+                out.push(ch);
+            }
+        }
+        x += run;
+    }
+}
+
+/// Encodes RGBA pixels as a kitty graphics protocol image, transmitted and displayed at the
+/// cursor position
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::encode_kitty;
+///
+/// let kitty = encode_kitty(&[255, 0, 0, 255], 1, 1);
+///
+/// assert_eq!(kitty, "\x1b_Gf=32,a=T,s=1,v=1,m=0;/wAA/w==\x1b\\");
+/// ```
+pub fn encode_kitty(rgba: &[u8], width: usize, height: usize) -> String {
+    let payload = base64(rgba);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+    let mut out = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = if index + 1 < chunks.len() { 1 } else { 0 };
+        let chunk = core::str::from_utf8(chunk).unwrap_or_default();
+        if index == 0 {
+            let _ = write!(
+                out,
+                "\x1b_Gf=32,a=T,s={},v={},m={};{}\x1b\\",
+                width, height, more, chunk
+            );
+        } else {
+            let _ = write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk);
+        }
+    }
+    if chunks.is_empty() {
+        out = format!("\x1b_Gf=32,a=T,s={},v={},m=0;\x1b\\", width, height);
+    }
+    out
+}
+
+/// Standard base64 with padding
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+/// Prints every frame to the terminal as an inline image, each cell becoming `cell_size`
+/// pixels wide and high
+#[cfg(feature = "std")]
+pub struct GraphicsSink {
+    pub protocol: GraphicsProtocol,
+    pub cell_size: usize,
+}
+
+#[cfg(feature = "std")]
+impl GraphicsSink {
+    pub fn new(protocol: GraphicsProtocol, cell_size: usize) -> Self {
+        Self {
+            protocol,
+            cell_size,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FrameSink for GraphicsSink {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        use std::io::Write;
+
+        let rgba = terminal_to_rgba(frame, self.cell_size);
+        let image = self.protocol.encode(
+            &rgba,
+            frame.size_x * self.cell_size,
+            frame.size_y * self.cell_size,
+        );
+        let stdout = std::io::stdout();
+        let mut lock = stdout.lock();
+        // Draw over the previous frame instead of scrolling
+        write!(lock, "\x1b[H{}", image).expect("Failed to write frame to stdout");
+        lock.flush().expect("Failed to flush stdout");
+    }
+}
@@ -0,0 +1,155 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use core::ops::{Index, IndexMut};
+
+use super::color::{ColorMode, Rgb};
+use super::{Buffer, SizedBuffer};
+
+/// Buffer of colored pixels which is printed with '▀' and '▄', every character showing two
+/// pixels on top of each other by using both its foreground and background color.
+/// Sizes are given in pixels, so a buffer printed in 40 rows is 80 pixels high. Empty pixels
+/// show the background of the terminal.
+#[derive(Clone)]
+pub struct HalfBlockBuffer {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub mode: ColorMode,
+    pub buffer: Vec<Option<Rgb>>,
+}
+
+impl HalfBlockBuffer {
+    /// Creates an empty buffer which prints in true color
+    pub fn new(size_x: usize, size_y: usize) -> Self {
+        Self::filled(size_x, size_y, None)
+    }
+
+    /// Creates a buffer which prints in true color with every pixel set to the given color
+    pub fn filled(size_x: usize, size_y: usize, color: Option<Rgb>) -> Self {
+        Self {
+            size_x,
+            size_y,
+            mode: ColorMode::TrueColor,
+            buffer: vec![color; size_y * size_x],
+        }
+    }
+
+    /// Sets the escape codes used when printing
+    pub fn with_mode(mut self, mode: ColorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn color_escape(&self, color: Rgb, layer: u8) -> String {
+        let Rgb { r, g, b } = color;
+        match self.mode {
+            ColorMode::TrueColor => format!("\x1b[{}8;2;{};{};{}m", layer, r, g, b),
+            ColorMode::Ansi256 => format!("\x1b[{}8;5;{}m", layer, color.to_ansi256()),
+        }
+    }
+
+    /// Returns the buffer as a string with ANSI color escapes, two rows of pixels per line.
+    /// An odd last row of pixels is printed in the upper half of the last line.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::color::*;
+    ///
+    /// let mut buffer = HalfBlockBuffer::new(2, 2).with_mode(ColorMode::Ansi256);
+    /// buffer.set(0, 0, Some(Rgb::new(255, 0, 0)));
+    /// buffer.set(1, 0, Some(Rgb::new(0, 0, 255)));
+    /// buffer.set(1, 1, Some(Rgb::new(0, 0, 255)));
+    ///
+    /// assert_eq!(
+    ///     buffer.to_ansi(),
+    ///     "\x1b[38;5;196m\x1b[48;5;21m▀\x1b[49m\x1b[38;5;21m▄\x1b[0m\n"
+    /// );
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for y in (0..self.size_y).step_by(2) {
+            for x in 0..self.size_x {
+                let top = self.buffer[y * self.size_x + x];
+                let bottom = match y + 1 < self.size_y {
+                    true => self.buffer[(y + 1) * self.size_x + x],
+                    false => None,
+                };
+                match (top, bottom) {
+                    (Some(top), Some(bottom)) => {
+                        out.push_str(&self.color_escape(top, 3));
+                        out.push_str(&self.color_escape(bottom, 4));
+                        out.push('▀');
+                    }
+                    (Some(top), None) => {
+                        out.push_str("\x1b[49m");
+                        out.push_str(&self.color_escape(top, 3));
+                        out.push('▀');
+                    }
+                    (None, Some(bottom)) => {
+                        out.push_str("\x1b[49m");
+                        out.push_str(&self.color_escape(bottom, 3));
+                        out.push('▄');
+                    }
+                    (None, None) => {
+                        out.push_str("\x1b[49m ");
+                    }
+                }
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+}
+
+impl Buffer for HalfBlockBuffer {
+    type Data = Option<Rgb>;
+    type Container = Vec<Option<Rgb>>;
+
+    fn new_with_buffer(size_x: usize, size_y: usize, buffer: Vec<Option<Rgb>>) -> Self {
+        let mut ret = Self::new(size_x, size_y);
+        ret.replace_buffer(buffer);
+        ret
+    }
+
+    fn replace_buffer(&mut self, new_buffer: Vec<Option<Rgb>>) {
+        self.buffer = new_buffer;
+    }
+
+    fn replace_buffer_self(&mut self, new_buffer: Self) {
+        self.replace_buffer(new_buffer.buffer);
+    }
+
+    fn get(&self, y: usize, x: usize) -> Option<Rgb> {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x]
+    }
+
+    fn set(&mut self, y: usize, x: usize, val: Option<Rgb>) {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x] = val;
+    }
+
+    #[cfg(feature = "std")]
+    fn print(&self) {
+        print!("{}", self.to_ansi());
+    }
+}
+
+impl SizedBuffer for HalfBlockBuffer {
+    fn size(&self) -> (usize, usize) {
+        (self.size_x, self.size_y)
+    }
+}
+
+impl Index<usize> for HalfBlockBuffer {
+    type Output = Option<Rgb>;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.buffer[index]
+    }
+}
+
+impl IndexMut<usize> for HalfBlockBuffer {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.buffer[index]
+    }
+}
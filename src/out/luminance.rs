@@ -0,0 +1,92 @@
+use alloc::{vec, vec::Vec};
+use core::ops::{Index, IndexMut};
+
+use super::{Buffer, SizedBuffer};
+#[cfg(feature = "std")]
+use crate::math::projection::luminance_char;
+
+/// Buffer of luminance values in linear space, which can go above 1 where several lights add
+/// up. Empty cells hold None. See pers_proj_hdr
+#[derive(Clone)]
+pub struct LuminanceBuffer {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub buffer: Vec<Option<f64>>,
+}
+
+impl LuminanceBuffer {
+    pub fn new(size_x: usize, size_y: usize) -> Self {
+        Self {
+            size_x,
+            size_y,
+            buffer: vec![None; size_x * size_y],
+        }
+    }
+
+    /// Highest luminance in the buffer, 0 if it is empty
+    pub fn peak(&self) -> f64 {
+        self.buffer.iter().flatten().fold(0.0, |peak, l| f64::max(peak, *l))
+    }
+}
+
+impl Buffer for LuminanceBuffer {
+    type Data = Option<f64>;
+    type Container = Vec<Option<f64>>;
+
+    fn new_with_buffer(size_x: usize, size_y: usize, buffer: Vec<Option<f64>>) -> Self {
+        let mut ret = Self::new(size_x, size_y);
+        ret.replace_buffer(buffer);
+        ret
+    }
+
+    fn replace_buffer(&mut self, new_buffer: Vec<Option<f64>>) {
+        self.buffer = new_buffer;
+    }
+
+    fn replace_buffer_self(&mut self, new_buffer: Self) {
+        self.replace_buffer(new_buffer.buffer);
+    }
+
+    fn get(&self, y: usize, x: usize) -> Option<f64> {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x]
+    }
+
+    fn set(&mut self, y: usize, x: usize, val: Option<f64>) {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x] = val;
+    }
+
+    /// Prints the buffer with every luminance clamped, tone map it first for anything better
+    #[cfg(feature = "std")]
+    fn print(&self) {
+        for row in self.buffer.chunks(self.size_x) {
+            let line: String = row
+                .iter()
+                .map(|cell| cell.map_or(' ', |l| luminance_char(l.clamp(0.0, 1.0))))
+                .collect();
+            println!("{}", line);
+        }
+    }
+}
+
+impl SizedBuffer for LuminanceBuffer {
+    fn size(&self) -> (usize, usize) {
+        (self.size_x, self.size_y)
+    }
+}
+
+impl Index<usize> for LuminanceBuffer {
+    type Output = Option<f64>;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.buffer[index]
+    }
+}
+
+impl IndexMut<usize> for LuminanceBuffer {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.buffer[index]
+    }
+}
@@ -0,0 +1,237 @@
+//! Serves frames over TCP to anyone who connects, like the famous telnet Star Wars. Frames are
+//! sent as plain ANSI text, so `telnet` or `nc` is all a viewer needs. Every client is written
+//! to on a thread of its own and paced on its own: slow clients skip frames instead of holding
+//! up the animation or the other clients.
+
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::sink::FrameSink;
+use super::terminal::SimpleTerminalBuffer;
+
+/// How often the listener looks for new clients, and client threads for a closed sink
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// Clients served at once unless set with max_clients, every one of them takes a thread
+const DEFAULT_MAX_CLIENTS: usize = 64;
+
+/// Latest frame for one client. Frames arriving before the client took the last one replace it
+struct Slot {
+    frame: Mutex<Option<Arc<str>>>,
+    ready: Condvar,
+    /// Set once the client is gone
+    closed: AtomicBool,
+}
+
+/// Frame sink which serves every frame to the clients connected over TCP.
+/// Clients connecting mid animation start with the next frame, clients connecting while the
+/// sink is full are turned away.
+/// ### Example:
+/// ```
+/// use std::io::Read;
+/// use std::net::TcpStream;
+/// use shapes_rs::buffer::*;
+///
+/// let mut sink = NetSink::bind("127.0.0.1:0").unwrap();
+/// let mut client = TcpStream::connect(sink.local_addr()).unwrap();
+/// while sink.clients() == 0 {
+///     std::thread::sleep(std::time::Duration::from_millis(5));
+/// }
+///
+/// sink.submit(&"ab\ncd".parse::<SimpleTerminalBuffer>().unwrap());
+///
+/// let mut received = Vec::new();
+/// while !received.ends_with(b"cd\r\n") {
+///     let mut chunk = [0; 64];
+///     let read = client.read(&mut chunk).unwrap();
+///     received.extend_from_slice(&chunk[..read]);
+/// }
+/// assert!(String::from_utf8(received).unwrap().ends_with("\x1b[Hab\r\ncd\r\n"));
+/// ```
+pub struct NetSink {
+    local_addr: SocketAddr,
+    clients: Arc<Mutex<Vec<Arc<Slot>>>>,
+    /// Period between two frames sent to the same client
+    min_interval: Arc<Mutex<Duration>>,
+    max_clients: Arc<AtomicUsize>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl NetSink {
+    /// Starts listening on the given address, port 0 picks any free port, see local_addr.
+    /// Clients get every frame, as fast as they can take it, unless limited with max_fps. Up
+    /// to 64 clients are served at once, see max_clients
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        // Polling, so the thread notices when the sink is dropped
+        listener.set_nonblocking(true)?;
+        let sink = Self {
+            local_addr: listener.local_addr()?,
+            clients: Arc::new(Mutex::new(Vec::new())),
+            min_interval: Arc::new(Mutex::new(Duration::ZERO)),
+            max_clients: Arc::new(AtomicUsize::new(DEFAULT_MAX_CLIENTS)),
+            stopped: Arc::new(AtomicBool::new(false)),
+        };
+
+        let clients = sink.clients.clone();
+        let min_interval = sink.min_interval.clone();
+        let max_clients = sink.max_clients.clone();
+        let stopped = sink.stopped.clone();
+        thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let mut connected = clients.lock().unwrap();
+                        connected.retain(|slot| !slot.closed.load(Ordering::SeqCst));
+                        if connected.len() >= max_clients.load(Ordering::SeqCst) {
+                            // Dropping the stream hangs up on the client
+                            continue;
+                        }
+                        drop(connected);
+                        let slot = Arc::new(Slot {
+                            frame: Mutex::new(None),
+                            ready: Condvar::new(),
+                            closed: AtomicBool::new(false),
+                        });
+                        clients.lock().unwrap().push(slot.clone());
+                        let (min_interval, stopped) = (min_interval.clone(), stopped.clone());
+                        thread::spawn(move || serve(stream, &slot, &min_interval, &stopped));
+                    }
+                    // Either nobody is waiting or a client hung up before it was accepted,
+                    // neither is worth stopping for
+                    Err(_) => thread::sleep(POLL_INTERVAL),
+                }
+            }
+        });
+        Ok(sink)
+    }
+
+    /// Sends every client at most this many frames per second, whatever rate the frames are
+    /// submitted at. Frames in between are skipped, for that client only. A rate of zero or
+    /// below, or NaN, removes the limit like Animator::target_fps, rates too low to wait for
+    /// are ignored
+    pub fn max_fps(&mut self, fps: f64) {
+        let interval = match fps > 0.0 {
+            true => match Duration::try_from_secs_f64(1.0 / fps) {
+                Ok(interval) => interval,
+                Err(_) => return,
+            },
+            false => Duration::ZERO,
+        };
+        *self.min_interval.lock().unwrap() = interval;
+    }
+
+    /// Same as max_fps, for building sinks
+    pub fn with_max_fps(mut self, fps: f64) -> Self {
+        self.max_fps(fps);
+        self
+    }
+
+    /// Serves at most this many clients at once, clients connecting while that many are
+    /// connected are hung up on. Clients connected already stay
+    /// ### Example:
+    /// ```
+    /// use std::io::Read;
+    /// use std::net::TcpStream;
+    /// use shapes_rs::buffer::*;
+    ///
+    /// let sink = NetSink::bind("127.0.0.1:0").unwrap().with_max_clients(1);
+    /// let _first = TcpStream::connect(sink.local_addr()).unwrap();
+    /// while sink.clients() == 0 {
+    ///     std::thread::sleep(std::time::Duration::from_millis(5));
+    /// }
+    ///
+    /// let mut second = TcpStream::connect(sink.local_addr()).unwrap();
+    /// assert_eq!(second.read(&mut [0; 8]).unwrap(), 0);
+    /// assert_eq!(sink.clients(), 1);
+    /// ```
+    pub fn max_clients(&mut self, max: usize) {
+        self.max_clients.store(max, Ordering::SeqCst);
+    }
+
+    /// Same as max_clients, for building sinks
+    pub fn with_max_clients(mut self, max: usize) -> Self {
+        self.max_clients(max);
+        self
+    }
+
+    /// Address the sink listens on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of clients connected right now
+    pub fn clients(&self) -> usize {
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain(|slot| !slot.closed.load(Ordering::SeqCst));
+        clients.len()
+    }
+}
+
+impl FrameSink for NetSink {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        // Cursor home instead of clearing, which flickers over the network. Telnet wants CRLF
+        let mut text = String::from("\x1b[H");
+        for line in frame.to_string().lines() {
+            text.push_str(line);
+            text.push_str("\r\n");
+        }
+        let text: Arc<str> = Arc::from(text);
+
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain(|slot| !slot.closed.load(Ordering::SeqCst));
+        for slot in clients.iter() {
+            *slot.frame.lock().unwrap() = Some(text.clone());
+            slot.ready.notify_one();
+        }
+    }
+}
+
+impl Drop for NetSink {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Writes the frames of one slot to its client until either of them goes away
+fn serve(
+    mut stream: TcpStream,
+    slot: &Slot,
+    min_interval: &Mutex<Duration>,
+    stopped: &AtomicBool,
+) {
+    // Some platforms hand out streams which inherit the non blocking listener
+    let _ = stream.set_nonblocking(false);
+    // A client which stops reading gets dropped instead of blocking its thread forever
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(5)));
+    let _ = stream.set_nodelay(true);
+    let mut next_frame = Instant::now();
+    let mut sent = stream.write_all(b"\x1b[2J");
+    while sent.is_ok() && !stopped.load(Ordering::SeqCst) {
+        let mut frame = slot.frame.lock().unwrap();
+        while frame.is_none() && !stopped.load(Ordering::SeqCst) {
+            frame = slot.ready.wait_timeout(frame, POLL_INTERVAL).unwrap().0;
+        }
+        let Some(text) = frame.take() else {
+            break;
+        };
+        drop(frame);
+
+        sent = stream.write_all(text.as_bytes());
+        // A rate low enough to overflow the clock would never send again
+        let Some(next) = next_frame.checked_add(*min_interval.lock().unwrap()) else {
+            break;
+        };
+        next_frame = next;
+        let now = Instant::now();
+        match next_frame > now {
+            true => thread::sleep(next_frame - now),
+            // Behind, the client only gets whatever is newest from here on
+            false => next_frame = now,
+        }
+    }
+    slot.closed.store(true, Ordering::SeqCst);
+}
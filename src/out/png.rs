@@ -0,0 +1,105 @@
+//! Minimal PNG writer for RGBA pixels. Image data is stored without compression, which keeps
+//! the encoder small and is plenty for screenshots of terminal sized frames.
+
+use alloc::vec::Vec;
+
+use crate::error::{Result, ShapesError};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// Largest amount of data a single stored deflate block can hold
+const MAX_STORED_BLOCK: usize = 0xffff;
+
+/// Encodes RGBA pixels, row by row starting at the top left, as a PNG file. Fails if either
+/// side is zero, which PNG does not allow, or the amount of pixel data does not match the size
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::*;
+///
+/// let mut buffer = SimpleTerminalBuffer::new(3, 2);
+/// buffer.set(1, 1, '@');
+/// let png = encode_png(3 * 4, 2 * 4, &terminal_to_rgba(&buffer, 4)).unwrap();
+///
+/// assert_eq!(&png[1..4], b"PNG");
+/// // Width and height are stored right after the IHDR chunk type
+/// assert_eq!(&png[16..24], &[0, 0, 0, 12, 0, 0, 0, 8]);
+/// assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+/// assert!(encode_png(0, 0, &[]).is_err());
+/// ```
+pub fn encode_png(width: usize, height: usize, rgba: &[u8]) -> Result<Vec<u8>> {
+    if width == 0 || height == 0 {
+        return Err(ShapesError::InvalidParameter("a png needs at least one pixel per side"));
+    }
+    if width.checked_mul(height).and_then(|pixels| pixels.checked_mul(4)) != Some(rgba.len()) {
+        return Err(ShapesError::InvalidParameter("pixel data does not match the image size"));
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits per channel, RGBA, default compression, filtering and no interlacing
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    // Every row starts with the filter type, none
+    let mut raw = Vec::with_capacity(height * (width * 4 + 1));
+    for row in rgba.chunks(width * 4).take(height) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut png = Vec::new();
+    png.extend_from_slice(&SIGNATURE);
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps the data into a zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_STORED_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
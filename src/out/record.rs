@@ -0,0 +1,143 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::sink::FrameSink;
+use super::terminal::SimpleTerminalBuffer;
+
+/// Moves the cursor to the top left corner and clears the screen
+const ANSI_CLEAR: &str = "\x1b[H\x1b[2J";
+
+/// A single captured frame and the time at which it was captured, relative to the first frame
+#[derive(Clone)]
+pub struct RecordedFrame {
+    pub time: Duration,
+    pub frame: SimpleTerminalBuffer,
+}
+
+/// Captures frames together with their timing so they can be replayed later.
+/// Can be subscribed to an Animator like any other sink, the time of each frame is then
+/// taken from the moment it was submitted.
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::*;
+/// use std::time::Duration;
+///
+/// let mut recorder = Recorder::new();
+/// recorder.push(SimpleTerminalBuffer::new(4, 2), Duration::from_millis(0));
+/// recorder.push(SimpleTerminalBuffer::new(4, 2), Duration::from_millis(50));
+///
+/// let mut cast = Vec::new();
+/// recorder.write_cast(&mut cast).unwrap();
+/// let cast = String::from_utf8(cast).unwrap();
+///
+/// assert!(cast.starts_with("{\"version\": 2, \"width\": 4, \"height\": 2}"));
+/// assert_eq!(cast.lines().count(), 3);
+/// ```
+#[derive(Default)]
+pub struct Recorder {
+    pub frames: Vec<RecordedFrame>,
+    start: Option<Instant>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            start: None,
+        }
+    }
+
+    /// Adds a frame with an explicit timestamp, useful when the timing should not depend on
+    /// how fast the frames were rendered
+    pub fn push(&mut self, frame: SimpleTerminalBuffer, time: Duration) {
+        self.frames.push(RecordedFrame { time, frame });
+    }
+
+    /// Total length of the recording
+    pub fn duration(&self) -> Duration {
+        self.frames.last().map_or(Duration::ZERO, |f| f.time)
+    }
+
+    /// Writes the recording as an asciinema v2 cast
+    pub fn write_cast(&self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = self
+            .frames
+            .first()
+            .map_or((0, 0), |f| (f.frame.size_x, f.frame.size_y));
+        writeln!(
+            out,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+            width, height
+        )?;
+        for recorded in &self.frames {
+            let data = format!("{}{}", ANSI_CLEAR, frame_to_ansi(&recorded.frame));
+            writeln!(
+                out,
+                "[{:.6}, \"o\", \"{}\"]",
+                recorded.time.as_secs_f64(),
+                json_escape(&data)
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the recording as an asciinema v2 cast file at the given path
+    pub fn save_cast<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_cast(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Writes the frames as a raw ANSI script which can simply be `cat`ed into a terminal.
+    /// There is no timing information in this format.
+    pub fn write_ansi(&self, out: &mut impl Write) -> io::Result<()> {
+        for recorded in &self.frames {
+            write!(out, "{}{}", ANSI_CLEAR, frame_to_ansi(&recorded.frame))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the frames as a raw ANSI script file at the given path
+    pub fn save_ansi<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_ansi(&mut writer)?;
+        writer.flush()
+    }
+}
+
+impl FrameSink for Recorder {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        let now = Instant::now();
+        let start = *self.start.get_or_insert(now);
+        self.push(frame.clone(), now - start);
+    }
+}
+
+/// Joins the rows of a frame with terminal line endings
+fn frame_to_ansi(frame: &SimpleTerminalBuffer) -> String {
+    let mut out = String::with_capacity((frame.size_x + 2) * frame.size_y);
+    for y in 0..frame.size_y {
+        out.extend(&frame.buffer[y * frame.size_x..(y + 1) * frame.size_x]);
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Escapes a string so it can be put between quotes in JSON
+fn json_escape(data: &str) -> String {
+    let mut out = String::with_capacity(data.len());
+    for c in data.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
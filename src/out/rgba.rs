@@ -0,0 +1,82 @@
+//! Converts character buffers into RGBA pixel data, where every cell becomes a square block of
+//! pixels. Useful for drawing frames onto anything that takes raw pixels, like a canvas.
+
+use alloc::{vec, vec::Vec};
+
+use super::color::Rgb;
+use super::color_terminal::ColorTerminalBuffer;
+use super::terminal::SimpleTerminalBuffer;
+use crate::math::projection::char_luminance;
+
+/// Color of cells without anything in them
+const BACKGROUND: [u8; 4] = [0, 0, 0, 255];
+
+/// Gray of a character of the luminance ramp, brighter characters give brighter grays.
+/// Empty cells have no color.
+pub fn char_gray(ch: char) -> Option<Rgb> {
+    char_luminance(ch).map(|luminance| {
+        let gray = (luminance * 255.0) as u8;
+        Rgb::new(gray, gray, gray)
+    })
+}
+
+fn fill_cell(pixels: &mut [u8], size_x: usize, cell_size: usize, x: usize, y: usize, rgba: [u8; 4]) {
+    let row_len = size_x * cell_size * 4;
+    for py in 0..cell_size {
+        let row = (y * cell_size + py) * row_len;
+        for px in 0..cell_size {
+            let start = row + (x * cell_size + px) * 4;
+            pixels[start..start + 4].copy_from_slice(&rgba);
+        }
+    }
+}
+
+fn to_rgba(
+    size_x: usize,
+    size_y: usize,
+    cell_size: usize,
+    mut cell_color: impl FnMut(usize) -> Option<Rgb>,
+) -> Vec<u8> {
+    let mut pixels = vec![0; size_x * size_y * cell_size * cell_size * 4];
+    for y in 0..size_y {
+        for x in 0..size_x {
+            let rgba = match cell_color(y * size_x + x) {
+                Some(Rgb { r, g, b }) => [r, g, b, 255],
+                None => BACKGROUND,
+            };
+            fill_cell(&mut pixels, size_x, cell_size, x, y, rgba);
+        }
+    }
+    pixels
+}
+
+/// Converts a character buffer into grayscale pixels, brighter characters of the luminance ramp
+/// become brighter pixels. Each cell becomes a block of `cell_size` by `cell_size` pixels.
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::*;
+///
+/// let mut buffer = SimpleTerminalBuffer::new(2, 1);
+/// buffer.set(0, 1, '@');
+///
+/// let pixels = terminal_to_rgba(&buffer, 2);
+/// assert_eq!(pixels.len(), 4 * 2 * 4);
+/// // Empty cell
+/// assert_eq!(&pixels[0..4], &[0, 0, 0, 255]);
+/// // Brightest character
+/// assert_eq!(&pixels[8..12], &[255, 255, 255, 255]);
+/// ```
+pub fn terminal_to_rgba(buffer: &SimpleTerminalBuffer, cell_size: usize) -> Vec<u8> {
+    to_rgba(buffer.size_x, buffer.size_y, cell_size, |index| {
+        char_gray(buffer[index])
+    })
+}
+
+/// Converts a color buffer into pixels of the cell colors.
+/// Each cell becomes a block of `cell_size` by `cell_size` pixels.
+pub fn color_to_rgba(buffer: &ColorTerminalBuffer, cell_size: usize) -> Vec<u8> {
+    to_rgba(buffer.size_x, buffer.size_y, cell_size, |index| {
+        let cell = buffer[index];
+        (cell.ch != ' ').then_some(cell.color)
+    })
+}
@@ -0,0 +1,131 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use super::terminal::SimpleTerminalBuffer;
+
+/// Anything that wants to receive rendered frames.
+/// Sinks only get to look at the frame, what they do with it (print it, save it, send it
+/// somewhere else) is up to them.
+pub trait FrameSink {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer);
+
+    /// Called once the animation feeding the sink shuts down, sinks which hold anything back
+    /// write it out here
+    fn flush(&mut self) {}
+}
+
+/// Lends a sink to an animator, it can be used again once the animator is gone
+/// ### Example:
+/// ```
+/// use shapes_rs::animation::Animator;
+/// use shapes_rs::buffer::*;
+/// use shapes_rs::testing::fixture_scene;
+///
+/// let mut recorder = Recorder::new();
+/// let mut animator = Animator::new(fixture_scene(20, 10));
+/// animator.subscribe(&mut recorder);
+/// animator.run(3);
+/// drop(animator);
+///
+/// assert_eq!(recorder.frames.len(), 3);
+/// ```
+impl<S: FrameSink + ?Sized> FrameSink for &mut S {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        (**self).submit(frame);
+    }
+
+    fn flush(&mut self) {
+        (**self).flush();
+    }
+}
+
+/// Prints every frame to the terminal, clearing the screen beforehand
+#[derive(Default)]
+pub struct StdoutSink {}
+
+impl StdoutSink {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FrameSink for StdoutSink {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        let stdout = io::stdout();
+        let mut lock = stdout.lock();
+        write!(lock, "{}[2J", 27 as char).expect("Failed to write frame to stdout");
+        writeln!(lock, "{}", frame).expect("Failed to write frame to stdout");
+        lock.flush().expect("Failed to flush stdout");
+    }
+
+    fn flush(&mut self) {
+        // Stdout going away while shutting down is not worth a panic
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Writes every frame into a file, one after another, separated by an empty line
+pub struct FileSink {
+    writer: BufWriter<File>,
+}
+
+impl FileSink {
+    /// Creates (or truncates) the file at the given path
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl FrameSink for FileSink {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        writeln!(self.writer, "{}\n", frame).expect("Failed to write frame to file");
+        self.writer.flush().expect("Failed to flush file");
+    }
+
+    fn flush(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+/// Sends a copy of every frame over a channel, handy for tests or for handing frames
+/// to another thread.
+/// Frames sent after the receiving end has been dropped are silently discarded.
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::*;
+///
+/// let (mut sink, receiver) = ChannelSink::new();
+/// let frame = SimpleTerminalBuffer::new(3, 2);
+/// sink.submit(&frame);
+///
+/// let received = receiver.recv().unwrap();
+/// assert_eq!(received.size_x, 3);
+/// assert_eq!(received.size_y, 2);
+/// ```
+pub struct ChannelSink {
+    sender: Sender<SimpleTerminalBuffer>,
+}
+
+impl ChannelSink {
+    /// Creates a new sink together with the receiving end of its channel
+    pub fn new() -> (Self, Receiver<SimpleTerminalBuffer>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self { sender }, receiver)
+    }
+
+    /// Creates a sink that sends frames over an already existing channel
+    pub fn from_sender(sender: Sender<SimpleTerminalBuffer>) -> Self {
+        Self { sender }
+    }
+}
+
+impl FrameSink for ChannelSink {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        let _ = self.sender.send(frame.clone());
+    }
+}
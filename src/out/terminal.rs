@@ -1,6 +1,22 @@
-use std::ops::{Index, IndexMut};
+use alloc::{string::String, vec, vec::Vec};
+use core::error::Error;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+use core::str::FromStr;
 
-use super::Buffer;
+use super::{Buffer, SizedBuffer};
+use crate::math::projection::{char_luminance, luminance_char};
+
+/// How cells are picked when a buffer is resized
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ScaleFilter {
+    /// Every cell takes the character of the closest cell, keeps glyphs intact
+    #[default]
+    Nearest,
+    /// Every cell takes the average luminance of the cells it covers. Cells covering mostly
+    /// empty cells stay empty
+    Box,
+}
 
 #[derive(Clone)]
 pub struct SimpleTerminalBuffer {
@@ -11,14 +27,192 @@ pub struct SimpleTerminalBuffer {
 
 impl SimpleTerminalBuffer {
     pub fn new(size_x: usize, size_y: usize) -> Self {
+        Self::filled(size_x, size_y, ' ')
+    }
+
+    /// Creates a buffer with every cell set to the given character
+    pub fn filled(size_x: usize, size_y: usize, ch: char) -> Self {
         // Precreate vectors
-        let buffer = vec![' '; size_y * size_x];
+        let buffer = vec![ch; size_y * size_x];
         Self {
             size_x,
             size_y,
             buffer,
         }
     }
+
+    /// Copy of the buffer resized with the given filter, see Buffer::scaled for the nearest
+    /// filter. Lets scenes render at a low resolution and be scaled up, or scaled down into
+    /// thumbnails.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    ///
+    /// let big: SimpleTerminalBuffer = "@@..\n@@..\n    \n  @ ".parse().unwrap();
+    ///
+    /// assert_eq!(big.scaled_with(2, 2, ScaleFilter::Box).to_string(), "@.\n  ");
+    /// assert_eq!(big.scaled_with(2, 2, ScaleFilter::Nearest).to_string(), "@.\n  ");
+    /// assert_eq!(big.scaled_with(1, 1, ScaleFilter::Box).to_string(), "!");
+    /// ```
+    pub fn scaled_with(&self, new_x: usize, new_y: usize, filter: ScaleFilter) -> Self {
+        match filter {
+            ScaleFilter::Nearest => self.scaled(new_x, new_y),
+            ScaleFilter::Box => {
+                let mut scaled = Self::new(new_x, new_y);
+                for y in 0..new_y {
+                    let (y0, y1) = box_range(y, self.size_y, new_y);
+                    for x in 0..new_x {
+                        let (x0, x1) = box_range(x, self.size_x, new_x);
+                        scaled.buffer[y * new_x + x] = self.box_average(x0..x1, y0..y1);
+                    }
+                }
+                scaled
+            }
+        }
+    }
+
+    /// Character of the average luminance of the given cells, empty if most of them are
+    fn box_average(&self, xs: core::ops::Range<usize>, ys: core::ops::Range<usize>) -> char {
+        let mut sum = 0.0;
+        let mut lit = 0;
+        let mut total = 0;
+        for y in ys {
+            for x in xs.clone() {
+                total += 1;
+                if let Some(luminance) = char_luminance(self.buffer[y * self.size_x + x]) {
+                    sum += luminance;
+                    lit += 1;
+                }
+            }
+        }
+        if lit * 2 < total || lit == 0 {
+            return ' ';
+        }
+        luminance_char(sum / lit as f64)
+    }
+
+    /// Returns a hash of the size and content of the buffer.
+    /// Unlike the hashers of the standard library the result never changes between runs,
+    /// platforms or compiler versions, so it can be stored in tests.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    ///
+    /// let mut buffer = SimpleTerminalBuffer::new(2, 2);
+    /// let empty_hash = buffer.hash();
+    /// buffer.set(1, 1, '@');
+    ///
+    /// assert_ne!(buffer.hash(), empty_hash);
+    /// assert_eq!(SimpleTerminalBuffer::new(2, 2).hash(), empty_hash);
+    /// assert_ne!(SimpleTerminalBuffer::new(1, 4).hash(), empty_hash);
+    /// ```
+    pub fn hash(&self) -> u64 {
+        // FNV-1a
+        const OFFSET: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+        let mut hash = OFFSET;
+        let mut feed = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(PRIME);
+            }
+        };
+        feed(&(self.size_x as u64).to_le_bytes());
+        feed(&(self.size_y as u64).to_le_bytes());
+        for c in &self.buffer {
+            feed(&(*c as u32).to_le_bytes());
+        }
+        hash
+    }
+}
+
+/// Cells of a side of the given length covered by one cell of the scaled side, at least one
+fn box_range(index: usize, length: usize, new_length: usize) -> (usize, usize) {
+    let start = index * length / new_length;
+    let end = ((index + 1) * length / new_length).max(start + 1).min(length);
+    (start, end)
+}
+
+/// Rows of the buffer joined by newlines, without a trailing newline
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::*;
+///
+/// let mut buffer = SimpleTerminalBuffer::new(3, 2);
+/// buffer.set(0, 0, '@');
+/// buffer.set(1, 2, '.');
+///
+/// let text = buffer.to_string();
+/// assert_eq!(text, "@  \n  .");
+///
+/// let parsed: SimpleTerminalBuffer = text.parse().unwrap();
+/// assert_eq!((parsed.size_x, parsed.size_y), (3, 2));
+/// assert_eq!(parsed.get(1, 2), '.');
+///
+/// assert!("ab\nc".parse::<SimpleTerminalBuffer>().is_err());
+/// ```
+impl fmt::Display for SimpleTerminalBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.size_y {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            let row: String = self.buffer[y * self.size_x..(y + 1) * self.size_x]
+                .iter()
+                .collect();
+            write!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returned when parsing a buffer from text whose rows are not all equally long
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseBufferError {
+    /// Index of the first row with a different length
+    pub row: usize,
+    /// Length of the first row
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for ParseBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {} is {} characters long, expected {}",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+impl Error for ParseBufferError {}
+
+/// Parses rows separated by newlines back into a buffer, the size is taken from the text.
+/// A single trailing newline is ignored.
+impl FromStr for SimpleTerminalBuffer {
+    type Err = ParseBufferError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self::new(0, 0));
+        }
+        let s = s.strip_suffix('\n').unwrap_or(s);
+        let rows: Vec<Vec<char>> = s.split('\n').map(|row| row.chars().collect()).collect();
+        let size_x = rows[0].len();
+        let mut buffer = Vec::with_capacity(size_x * rows.len());
+        for (index, row) in rows.iter().enumerate() {
+            if row.len() != size_x {
+                return Err(ParseBufferError {
+                    row: index,
+                    expected: size_x,
+                    found: row.len(),
+                });
+            }
+            buffer.extend(row);
+        }
+        Ok(Self::new_with_buffer(size_x, rows.len(), buffer))
+    }
 }
 
 impl Buffer for SimpleTerminalBuffer {
@@ -51,6 +245,7 @@ impl Buffer for SimpleTerminalBuffer {
         self.buffer[y * self.size_x + x] = val;
     }
 
+    #[cfg(feature = "std")]
     fn print(&self) {
         for y in 0..self.size_y {
             for x in 0..self.size_x {
@@ -62,6 +257,12 @@ impl Buffer for SimpleTerminalBuffer {
     }
 }
 
+impl SizedBuffer for SimpleTerminalBuffer {
+    fn size(&self) -> (usize, usize) {
+        (self.size_x, self.size_y)
+    }
+}
+
 impl Index<usize> for SimpleTerminalBuffer {
     type Output = char;
     fn index(&self, index: usize) -> &Self::Output {
@@ -0,0 +1,79 @@
+//! Bindings for running the renderer from JavaScript.
+//! Frames can be taken either as text, for a `<pre>` element or a browser terminal emulator, or
+//! as RGBA bytes which can be wrapped in an `ImageData` and drawn onto a canvas.
+
+use wasm_bindgen::prelude::*;
+
+use crate::basetype::{Angle, Angle3D, Coord, Vector3D};
+use crate::component::{Camera, Light3D, Object};
+use crate::math::projection::{pers_proj, pers_proj_color};
+use crate::out::color::{ColorSource, Colormap};
+use crate::out::rgba::{color_to_rgba, terminal_to_rgba};
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::scene::Scene;
+use crate::shape::rotator::Rotator;
+use crate::shape::shape_gen::TorusGenerator;
+
+/// The generator has to outlive the scene, which JavaScript may keep around for as long as it
+/// likes
+static TORUS: TorusGenerator = TorusGenerator::new(10.0, 50.0);
+
+/// A spinning torus, ready to be driven from a `requestAnimationFrame` loop
+#[wasm_bindgen]
+pub struct WasmDonut {
+    scene: Scene<'static>,
+}
+
+#[wasm_bindgen]
+impl WasmDonut {
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: usize, height: usize) -> WasmDonut {
+        let rotation = Rotator::from_global(Angle3D::new(
+            Angle::from_degree(5.0),
+            Angle::from_degree(5.0),
+            Angle::from_degree(8.0),
+        ));
+        let camera = Camera::new(
+            Coord::default(),
+            Vector3D::new(1.0, 0.0, 0.0),
+            Angle::from_degree(120.0),
+        );
+        let mut scene = Scene::new(camera, SimpleTerminalBuffer::new(width, height), pers_proj);
+        scene.add_light(Light3D::new(Coord::new(100.0, 0.0, 500.0)));
+        scene.add_object(Object::new(Coord::new(70.0, 0.0, 0.0), &TORUS, rotation));
+        scene.set_colormap(Colormap::Viridis, ColorSource::Luminance);
+        WasmDonut { scene }
+    }
+
+    /// Advances the animation by one frame
+    pub fn step(&mut self) {
+        for obj in self.scene.objects.iter_mut() {
+            obj.apply_rotation();
+        }
+    }
+
+    /// Current frame as text, rows separated by newlines
+    pub fn frame_text(&self) -> String {
+        self.scene.render().to_string()
+    }
+
+    /// Current frame as grayscale RGBA bytes, every cell being `cell_size` pixels wide and high
+    pub fn frame_rgba(&self, cell_size: usize) -> Vec<u8> {
+        terminal_to_rgba(&self.scene.render(), cell_size)
+    }
+
+    /// Current frame as RGBA bytes colored with the viridis colormap
+    pub fn frame_rgba_color(&self, cell_size: usize) -> Vec<u8> {
+        color_to_rgba(&pers_proj_color(&self.scene.snapshot()), cell_size)
+    }
+
+    /// Width of the RGBA frames in pixels for the given cell size
+    pub fn pixel_width(&self, cell_size: usize) -> usize {
+        self.scene.get_buffer_size().0 * cell_size
+    }
+
+    /// Height of the RGBA frames in pixels for the given cell size
+    pub fn pixel_height(&self, cell_size: usize) -> usize {
+        self.scene.get_buffer_size().1 * cell_size
+    }
+}
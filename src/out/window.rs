@@ -0,0 +1,150 @@
+//! Output into a native window, where every cell of a buffer is drawn as a square block of
+//! pixels. Frames rendered for the terminal can be shown as they are, so the same scene and
+//! renderer can drive both.
+
+use std::cell::RefCell;
+
+use minifb::{Window, WindowOptions};
+
+use super::color::Rgb;
+use super::color_terminal::ColorTerminalBuffer;
+use super::rgba::char_gray;
+use super::sink::FrameSink;
+use super::terminal::SimpleTerminalBuffer;
+use super::{Buffer, SizedBuffer};
+
+/// Cell size used when a window is created through `Buffer::new_with_buffer`
+pub const DEFAULT_CELL_SIZE: usize = 8;
+
+/// Buffer of colored cells shown in a window, each cell being `cell_size` pixels wide and high.
+/// Nothing is shown until `present` (or `print`) is called.
+pub struct WindowBuffer {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub cell_size: usize,
+    /// Color of every cell, row by row. Black cells are empty
+    pub buffer: Vec<Rgb>,
+    // Presenting only reads the cells, so printing through a shared reference works
+    pixels: RefCell<Vec<u32>>,
+    window: RefCell<Window>,
+}
+
+impl WindowBuffer {
+    /// Opens a window large enough for the given amount of cells
+    pub fn open(
+        title: &str,
+        size_x: usize,
+        size_y: usize,
+        cell_size: usize,
+    ) -> Result<Self, minifb::Error> {
+        let window = Window::new(
+            title,
+            size_x * cell_size,
+            size_y * cell_size,
+            WindowOptions::default(),
+        )?;
+        Ok(Self {
+            size_x,
+            size_y,
+            cell_size,
+            buffer: vec![Rgb::new(0, 0, 0); size_x * size_y],
+            pixels: RefCell::new(vec![0; size_x * size_y * cell_size * cell_size]),
+            window: RefCell::new(window),
+        })
+    }
+
+    /// Whether the window is still open, false once the user closed it
+    pub fn is_open(&self) -> bool {
+        self.window.borrow().is_open()
+    }
+
+    /// Copies a terminal frame into the cells, brighter characters become brighter grays.
+    /// The frame has to be the same size as the buffer.
+    pub fn draw_terminal(&mut self, frame: &SimpleTerminalBuffer) {
+        assert_eq!((frame.size_x, frame.size_y), (self.size_x, self.size_y));
+        for (cell, ch) in self.buffer.iter_mut().zip(frame.buffer.iter()) {
+            *cell = char_gray(*ch).unwrap_or(Rgb::new(0, 0, 0));
+        }
+    }
+
+    /// Copies the colors of a color frame into the cells.
+    /// The frame has to be the same size as the buffer.
+    pub fn draw_color(&mut self, frame: &ColorTerminalBuffer) {
+        assert_eq!((frame.size_x, frame.size_y), (self.size_x, self.size_y));
+        for (cell, color_cell) in self.buffer.iter_mut().zip(frame.buffer.iter()) {
+            *cell = match color_cell.ch {
+                ' ' => Rgb::new(0, 0, 0),
+                _ => color_cell.color,
+            };
+        }
+    }
+
+    /// Draws the cells into the window
+    pub fn present(&self) -> Result<(), minifb::Error> {
+        let width = self.size_x * self.cell_size;
+        let mut pixels = self.pixels.borrow_mut();
+        for (index, cell) in self.buffer.iter().enumerate() {
+            let pixel = (cell.r as u32) << 16 | (cell.g as u32) << 8 | cell.b as u32;
+            let (x, y) = (index % self.size_x, index / self.size_x);
+            for py in 0..self.cell_size {
+                let row = (y * self.cell_size + py) * width + x * self.cell_size;
+                pixels[row..row + self.cell_size].fill(pixel);
+            }
+        }
+        self.window
+            .borrow_mut()
+            .update_with_buffer(&pixels, width, self.size_y * self.cell_size)
+    }
+}
+
+impl Buffer for WindowBuffer {
+    type Data = Rgb;
+    type Container = Vec<Rgb>;
+
+    /// Opens a window titled after the crate, panics if that fails
+    fn new_with_buffer(size_x: usize, size_y: usize, buffer: Vec<Rgb>) -> Self {
+        let mut ret = Self::open("shapes_rs", size_x, size_y, DEFAULT_CELL_SIZE)
+            .expect("Failed to open window");
+        ret.replace_buffer(buffer);
+        ret
+    }
+
+    fn replace_buffer(&mut self, new_buffer: Vec<Rgb>) {
+        self.buffer = new_buffer;
+    }
+
+    fn replace_buffer_self(&mut self, new_buffer: Self) {
+        self.replace_buffer(new_buffer.buffer);
+    }
+
+    fn get(&self, y: usize, x: usize) -> Rgb {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x]
+    }
+
+    fn set(&mut self, y: usize, x: usize, val: Rgb) {
+        assert!(y < self.size_y);
+        assert!(x < self.size_x);
+        self.buffer[y * self.size_x + x] = val;
+    }
+
+    /// Shows the buffer in the window
+    fn print(&self) {
+        self.present().expect("Failed to update window");
+    }
+}
+
+impl SizedBuffer for WindowBuffer {
+    fn size(&self) -> (usize, usize) {
+        (self.size_x, self.size_y)
+    }
+}
+
+/// Shows every submitted frame in the window
+impl FrameSink for WindowBuffer {
+    fn submit(&mut self, frame: &SimpleTerminalBuffer) {
+        self.draw_terminal(frame);
+        self.present().expect("Failed to update window");
+    }
+}
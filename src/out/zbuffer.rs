@@ -0,0 +1,35 @@
+use alloc::{vec, vec::Vec};
+
+/// Distance to the camera of whatever was drawn into each cell of a buffer, used to keep only
+/// the closest point of every cell. Cells start out infinitely far away.
+#[derive(Clone)]
+pub struct ZBuffer {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub depth: Vec<f64>,
+}
+
+impl ZBuffer {
+    pub fn new(size_x: usize, size_y: usize) -> Self {
+        Self {
+            size_x,
+            size_y,
+            depth: vec![f64::INFINITY; size_x * size_y],
+        }
+    }
+
+    /// Resets every cell to infinitely far away, so the buffer can be reused for the next frame
+    pub fn clear(&mut self) {
+        self.depth.fill(f64::INFINITY);
+    }
+
+    /// Stores the depth if it is closer than what the cell holds, returns whether it was
+    pub fn test_and_set(&mut self, x: usize, y: usize, depth: f64) -> bool {
+        let cell = &mut self.depth[y * self.size_x + x];
+        if *cell <= depth {
+            return false;
+        }
+        *cell = depth;
+        true
+    }
+}
@@ -1,6 +1,90 @@
+pub mod debug;
+pub mod manager;
+pub mod options;
+#[cfg(feature = "script")]
+pub mod script;
+pub mod snapshot;
+pub mod state;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::slice;
+use core::time::Duration;
+use crate::basetype::{Coord, Vector3D};
 use crate::component::*;
+use crate::basetype::Angle;
+use crate::error::{Result, ShapesError};
+use crate::math::projection::{pers_proj, project_coord, project_fragments};
+use crate::out::color::{ColorSource, Colormap, Rgb};
+use crate::out::color_terminal::ColorCell;
 use crate::out::terminal::SimpleTerminalBuffer;
-use crate::out::Buffer;
+use debug::{draw_debug, non_finite_points, DebugFlags, NonFinitePoint};
+use options::RenderOptions;
+use snapshot::{ObjectSnapshot, RenderSnapshot};
+use state::{ObjectState, SceneState};
+use crate::out::{Buffer, SizedBuffer};
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Part of the bound buffer a scene is rendered into, the rest is left clear. Given in cells,
+/// from the top left corner
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Viewport {
+    pub x: usize,
+    pub y: usize,
+    pub size_x: usize,
+    pub size_y: usize,
+}
+
+impl Viewport {
+    pub fn new(x: usize, y: usize, size_x: usize, size_y: usize) -> Self {
+        Self {
+            x,
+            y,
+            size_x,
+            size_y,
+        }
+    }
+
+    /// Largest viewport with the given width to height ratio (in cells) centered in a buffer of
+    /// the given size, leaving bars at the sides or at the top and bottom
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::Viewport;
+    ///
+    /// assert_eq!(Viewport::letterbox(100, 20, 2.0), Viewport::new(30, 0, 40, 20));
+    /// assert_eq!(Viewport::letterbox(40, 40, 2.0), Viewport::new(0, 10, 40, 20));
+    /// ```
+    pub fn letterbox(size_x: usize, size_y: usize, aspect: f64) -> Self {
+        let fit_x = (size_y as f64 * aspect).round() as usize;
+        if fit_x <= size_x {
+            return Self::new((size_x - fit_x) / 2, 0, fit_x, size_y);
+        }
+        let fit_y = ((size_x as f64 / aspect).round() as usize).min(size_y);
+        Self::new(0, (size_y - fit_y) / 2, size_x, fit_y)
+    }
+
+    /// Same viewport cut down to fit into a buffer of the given size
+    fn clamped(&self, size_x: usize, size_y: usize) -> Self {
+        let x = self.x.min(size_x);
+        let y = self.y.min(size_y);
+        Self::new(x, y, self.size_x.min(size_x - x), self.size_y.min(size_y - y))
+    }
+}
+
+/// Refers to an object of a scene, handed out when the object is added
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ObjectId(pub usize);
+
+/// Shake running on the camera, or on an object if there is a target, along with when it
+/// started and how far it has moved its target
+#[derive(Copy, Clone)]
+struct ActiveShake {
+    target: Option<ObjectId>,
+    shake: Shake,
+    start: f64,
+    offset: Vector3D,
+}
 
 /// Basic binding class that constitutes a scene
 /// Four things must be available (thus constructed if needed) beforehand in order to create a usable scene:
@@ -49,11 +133,35 @@ use crate::out::Buffer;
 /// output.print();
 /// ```
 pub struct Scene<'a> {
-    pub camera: Camera,
-    pub lights: Vec<Light3D>,
-    pub objects: Vec<Object<'a>>,
-    pub buffer: SimpleTerminalBuffer,
-    pub renderer: fn(view: &Scene) -> SimpleTerminalBuffer,
+    pub(crate) camera: Camera,
+    pub(crate) lights: Vec<Light3D>,
+    pub(crate) objects: Vec<Object<'a>>,
+    /// Points removed by any of these planes are not rendered
+    pub(crate) clip_planes: Vec<ClipPlane>,
+    pub(crate) buffer: SimpleTerminalBuffer,
+    pub(crate) renderer: fn(view: &RenderSnapshot) -> SimpleTerminalBuffer,
+    /// Colormap used by color renderers for objects whose material has none
+    pub(crate) colormap: Colormap,
+    /// Which value is used to sample colormaps
+    pub(crate) color_source: ColorSource,
+    /// What renderers fill cells without anything in them with, a white space by default
+    pub(crate) clear: ColorCell,
+    /// Drawn behind every cell by color renderers, the background of the terminal if not set
+    pub(crate) background: Option<Rgb>,
+    /// Seconds the scene has been advanced by in total
+    pub(crate) time: f64,
+    /// Part of the buffer which is rendered into, the whole buffer if None
+    pub(crate) viewport: Option<Viewport>,
+    /// Drawn over every rendered frame, see add_label
+    pub(crate) labels: Vec<Label>,
+    /// Overlays drawn over every rendered frame, all off by default
+    pub(crate) debug: DebugFlags,
+    /// Handed to the renderer with every snapshot
+    pub(crate) render_options: RenderOptions,
+    /// See shake_camera and shake_object
+    shakes: Vec<ActiveShake>,
+    /// See add_tween
+    tweens: Vec<RunningTween<'a>>,
 }
 
 impl<'a> Scene<'a> {
@@ -88,20 +196,240 @@ impl<'a> Scene<'a> {
     pub fn new(
         camera: Camera,
         buffer: SimpleTerminalBuffer,
-        renderer: fn(view: &Scene) -> SimpleTerminalBuffer,
+        renderer: fn(view: &RenderSnapshot) -> SimpleTerminalBuffer,
     ) -> Self {
         Scene {
             camera,
             lights: Vec::new(),
             objects: Vec::new(),
+            clip_planes: Vec::new(),
             buffer,
             renderer,
+            colormap: Colormap::default(),
+            color_source: ColorSource::Luminance,
+            clear: ColorCell::empty(),
+            background: None,
+            time: 0.0,
+            viewport: None,
+            labels: Vec::new(),
+            debug: DebugFlags::default(),
+            render_options: RenderOptions::default(),
+            shakes: Vec::new(),
+            tweens: Vec::new(),
         }
     }
 
-    /// Calls the renderer function given, passes itself to it as an argument
+    /// Calls the renderer function given with a snapshot of the scene. With a viewport set,
+    /// the scene is rendered into that part of a buffer of the bound size and the rest is clear
     pub fn render(&self) -> SimpleTerminalBuffer {
-        (self.renderer)(self)
+        self.render_with(self.renderer)
+    }
+
+    /// Same as render, with a different renderer for this frame only. Viewport, labels and
+    /// debug overlays are handled the same way
+    pub fn render_with(
+        &self,
+        renderer: impl FnOnce(&RenderSnapshot) -> SimpleTerminalBuffer,
+    ) -> SimpleTerminalBuffer {
+        phase_span!("render", objects = self.objects.len());
+        let frame = self.render_viewport(renderer);
+        let viewport = self.viewport();
+        if viewport == Viewport::new(0, 0, self.buffer.size_x, self.buffer.size_y) {
+            return frame;
+        }
+        let mut full = SimpleTerminalBuffer::filled(self.buffer.size_x, self.buffer.size_y, self.clear.ch);
+        full.blit(&frame, viewport.x, viewport.y);
+        full
+    }
+
+    /// Renders only the viewport into the given buffer and leaves the rest of it alone, so
+    /// several scenes can share one buffer
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::color::Rgb;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut main = fixture_scene(40, 20);
+    /// main.set_clear('.', Rgb::new(0, 0, 0));
+    /// let mut inset = fixture_scene(40, 20);
+    /// inset.add_object(Object::new(Coord::new(15.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+    /// // Picture in picture in the bottom right corner
+    /// inset.set_viewport(30, 15, 10, 5);
+    ///
+    /// let mut frame = main.render();
+    /// inset.render_into(&mut frame);
+    ///
+    /// let rows: Vec<String> = frame.to_string().lines().map(String::from).collect();
+    /// assert!(rows[0].chars().all(|ch| ch == '.'));
+    /// // The inset covers its corner completely, cleared cells included
+    /// assert!(rows[17][30..].chars().any(|ch| ch != ' ' && ch != '.'));
+    /// assert!(rows[17][30..].contains(' '));
+    /// assert!(rows[17][..30].chars().all(|ch| ch == '.'));
+    /// ```
+    pub fn render_into(&self, target: &mut SimpleTerminalBuffer) {
+        let viewport = self.viewport();
+        target.blit(&self.render_viewport(self.renderer), viewport.x, viewport.y);
+    }
+
+    /// Renders the viewport on its own and draws the labels and debug overlays over it
+    fn render_viewport(
+        &self,
+        renderer: impl FnOnce(&RenderSnapshot) -> SimpleTerminalBuffer,
+    ) -> SimpleTerminalBuffer {
+        let view = self.snapshot();
+        let mut frame = renderer(&view);
+        if self.debug.any() {
+            draw_debug(&view, &self.objects, &self.debug, &mut frame);
+        }
+        self.draw_labels(&view, &mut frame);
+        frame
+    }
+
+    /// Writes the text of every label of a visible object into the frame, centered on where
+    /// its anchor point is projected. Lines beyond the first go below it
+    fn draw_labels(&self, view: &RenderSnapshot, frame: &mut SimpleTerminalBuffer) {
+        if self.labels.is_empty() {
+            return;
+        }
+        // Only needed to hide labels behind objects
+        let fragments = match self.labels.iter().any(|label| label.depth_test) {
+            true => project_fragments(view),
+            false => Vec::new(),
+        };
+        let (size_x, size_y) = frame.size();
+        for label in self.labels.iter() {
+            let Some(object) = view.objects.get(label.anchor.0) else {
+                continue;
+            };
+            if !object.visible || object.layer_mask & view.camera.layers == 0 {
+                continue;
+            }
+            let Some(((anchor_x, anchor_y), depth)) =
+                project_coord(view, object.location + label.offset)
+            else {
+                continue;
+            };
+            for (row, line) in label.text.lines().enumerate() {
+                let y = anchor_y + row;
+                if y >= size_y {
+                    break;
+                }
+                let start = anchor_x as isize - line.chars().count() as isize / 2;
+                for (column, ch) in line.chars().enumerate() {
+                    let x = start + column as isize;
+                    if x < 0 || x as usize >= size_x {
+                        continue;
+                    }
+                    let x = x as usize;
+                    let covered = fragments
+                        .get(y * size_x + x)
+                        .is_some_and(|fragment| fragment.is_some_and(|f| f.depth < depth));
+                    if label.depth_test && covered {
+                        continue;
+                    }
+                    frame.set(y, x, ch);
+                }
+            }
+        }
+    }
+
+    /// Renders into the given part of the bound buffer only, cut down to fit the buffer
+    pub fn set_viewport(&mut self, x: usize, y: usize, size_x: usize, size_y: usize) {
+        self.viewport = Some(
+            Viewport::new(x, y, size_x, size_y).clamped(self.buffer.size_x, self.buffer.size_y),
+        );
+    }
+
+    /// Renders into the largest centered part of the bound buffer with the given width to
+    /// height ratio, in cells
+    pub fn letterbox(&mut self, aspect: f64) {
+        self.viewport = Some(Viewport::letterbox(self.buffer.size_x, self.buffer.size_y, aspect));
+    }
+
+    /// Renders into the whole bound buffer again
+    pub fn clear_viewport(&mut self) {
+        self.viewport = None;
+    }
+
+    /// Part of the bound buffer that is rendered into
+    pub fn viewport(&self) -> Viewport {
+        let (size_x, size_y) = self.get_buffer_size();
+        match self.viewport {
+            Some(viewport) => viewport.clamped(size_x, size_y),
+            None => Viewport::new(0, 0, size_x, size_y),
+        }
+    }
+
+    /// Saves the transforms of every object, the camera, where the lights are and the clock, so
+    /// the scene can be put back to this point later with restore. For checkpointing an animation, or rewinding it
+    /// while debugging.
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::demos;
+    ///
+    /// let mut scene = demos::spinning_donut(40, 20).scene;
+    /// let checkpoint = scene.checkpoint();
+    /// let before = scene.render().to_string();
+    ///
+    /// scene.advance(Duration::from_secs(3));
+    /// for light in scene.lights_mut() {
+    ///     light.coord = Coord::new(0.0, 0.0, -100.0);
+    /// }
+    /// assert_ne!(scene.render().to_string(), before);
+    ///
+    /// scene.restore(&checkpoint);
+    /// assert_eq!(scene.time(), 0.0);
+    /// assert_eq!(scene.render().to_string(), before);
+    /// ```
+    pub fn checkpoint(&self) -> SceneState {
+        SceneState {
+            camera: self.camera.clone(),
+            time: self.time,
+            objects: self.objects.iter().map(ObjectState::new).collect(),
+            lights: self.lights.iter().map(|light| light.coord).collect(),
+        }
+    }
+
+    /// Puts the objects, the camera, the lights and the clock back to how they were at the
+    /// checkpoint. Objects and lights added since are left alone. Running tweens and shakes
+    /// are stopped, without calling any completion functions, since the ones which ended
+    /// after the checkpoint can't be brought back
+    pub fn restore(&mut self, state: &SceneState) {
+        // The transforms are put back exactly, shaken or not
+        self.shakes.clear();
+        self.tweens.clear();
+        self.camera = state.camera.clone();
+        self.time = state.time;
+        for (object, saved) in self.objects.iter_mut().zip(state.objects.iter()) {
+            saved.apply(object);
+        }
+        for (light, coord) in self.lights.iter_mut().zip(state.lights.iter()) {
+            light.coord = *coord;
+        }
+    }
+
+    /// Takes a frozen view of the scene for renderers to work on, sized to the viewport
+    pub fn snapshot(&self) -> RenderSnapshot<'_> {
+        let viewport = self.viewport();
+        RenderSnapshot {
+            camera: self.camera.clone(),
+            size_x: viewport.size_x,
+            size_y: viewport.size_y,
+            lights: &self.lights,
+            objects: self.objects.iter().map(ObjectSnapshot::new).collect(),
+            clip_planes: &self.clip_planes,
+            colormap: &self.colormap,
+            color_source: self.color_source,
+            clear: self.clear,
+            background: self.background,
+            options: self.render_options,
+        }
     }
 
     /// Returns the size of the bound buffer
@@ -109,18 +437,782 @@ impl<'a> Scene<'a> {
         (self.buffer.size_x, self.buffer.size_y)
     }
 
-    /// Append an object
-    pub fn add_object(&mut self, object: Object<'a>) {
+    /// Writes the text next to an object in every rendered frame, centered on the point at the
+    /// given offset from its location. Labels are hidden by objects in front of that point
+    /// unless their depth test is turned off. Fails if there is no object with the given id
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let torusgen = TorusGenerator::new(10.0, 50.0);
+    /// let mut scene = fixture_scene(60, 30);
+    /// let torus = scene.add_object(Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+    /// // Just above the ring
+    /// scene.add_label(torus, "big", Coord::new(0.0, 0.0, 65.0)).unwrap();
+    /// // Right at the center of the ring, behind nothing
+    /// scene.add_label(torus, "hole", Coord::default()).unwrap().depth_test = false;
+    /// // Behind the top of the ring
+    /// scene.add_label(torus, "hidden", Coord::new(30.0, 0.0, 50.0)).unwrap();
+    ///
+    /// let frame = scene.render().to_string();
+    /// assert!(frame.contains("big"));
+    /// assert!(frame.contains("hole"));
+    /// assert!(!frame.contains("hidden"));
+    /// assert!(scene.add_label(shapes_rs::ObjectId(5), "none", Coord::default()).is_err());
+    /// ```
+    pub fn add_label(&mut self, anchor: ObjectId, text: &str, offset: Coord) -> Result<&mut Label> {
+        if self.object(anchor).is_none() {
+            return Err(ShapesError::UnknownObject(anchor));
+        }
+        self.labels.push(Label::new(anchor, text, offset));
+        Ok(self.labels.last_mut().unwrap())
+    }
+
+    /// Draws a line between the locations of two objects, labelled with the distance between
+    /// them. See DebugFlags for the other overlays. Fails if either object does not exist
+    pub fn measure(&mut self, a: ObjectId, b: ObjectId) -> Result<()> {
+        for id in [a, b] {
+            if self.object(id).is_none() {
+                return Err(ShapesError::UnknownObject(id));
+            }
+        }
+        self.debug.measures.push((a, b));
+        Ok(())
+    }
+
+    /// Points of the visible objects whose position in world coordinates isn't finite. They
+    /// are left out of every frame, set DebugFlags::non_finite to list them on it as well
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::selfmade::ShapeGen;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::{NonFinitePoint, Object};
+    ///
+    /// // A generator dividing by zero for its second point
+    /// struct Broken;
+    /// impl ShapeGen for Broken {
+    ///     fn generate_shape(&self, shape: &mut Shape) {
+    ///         let up = Vector3D::new(0.0, 0.0, 1.0);
+    ///         shape.points.push(Point::new(Coord::default(), up));
+    ///         shape.points.push(Point::new(Coord::new(0.0 / 0.0, 0.0, 0.0), up));
+    ///     }
+    /// }
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut scene = fixture_scene(60, 30);
+    /// scene.add_object(Object::new(Coord::new(60.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+    /// let clean = scene.render();
+    /// let broken = scene.add_object(Object::new(Coord::new(90.0, 0.0, 0.0), &Broken, Rotator::identity()));
+    ///
+    /// assert_eq!(scene.non_finite_points(), vec![NonFinitePoint { object: broken, point: 1 }]);
+    /// // The broken point is skipped instead of being drawn into the corner
+    /// assert_eq!(scene.render().buffer[0], clean.buffer[0]);
+    ///
+    /// scene.debug_mut().non_finite = true;
+    /// assert!(scene.render().to_string().starts_with("object 1: 1 non-finite points, first 1"));
+    /// ```
+    pub fn non_finite_points(&self) -> Vec<NonFinitePoint> {
+        non_finite_points(&self.snapshot())
+    }
+
+    /// Append an object, returns the id it can be referred to with
+    pub fn add_object(&mut self, object: Object<'a>) -> ObjectId {
         self.objects.push(object);
+        let id = ObjectId(self.objects.len() - 1);
+        self.place_on_orbit(id.0);
+        id
+    }
+
+    /// Returns the object with the given id
+    pub fn object(&self, id: ObjectId) -> Option<&Object<'a>> {
+        self.objects.get(id.0)
+    }
+
+    /// Returns the object with the given id
+    pub fn object_mut(&mut self, id: ObjectId) -> Option<&mut Object<'a>> {
+        self.objects.get_mut(id.0)
+    }
+
+    /// Id of the first object with the given name
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let torusgen = TorusGenerator::new(10.0, 50.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// scene.add_object(Object::new(Coord::new(70.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+    /// let donut = scene.add_object(
+    ///     Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity()).with_name("donut"),
+    /// );
+    ///
+    /// assert_eq!(scene.find_by_name("donut"), Some(donut));
+    /// assert_eq!(scene.find_by_name("bagel"), None);
+    /// ```
+    pub fn find_by_name(&self, name: &str) -> Option<ObjectId> {
+        self.objects
+            .iter()
+            .position(|obj| obj.name.as_deref() == Some(name))
+            .map(ObjectId)
+    }
+
+    /// Every object with the given tag, along with its id
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::{CubeGenerator, TorusGenerator};
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::{Object, ObjectId};
+    ///
+    /// let torusgen = TorusGenerator::new(5.0, 20.0);
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// scene.add_object(Object::new(Coord::new(70.0, 0.0, 0.0), &torusgen, Rotator::identity()).with_tag("planet"));
+    /// scene.add_object(Object::new(Coord::new(90.0, 0.0, 0.0), &cubegen, Rotator::identity()).with_tag("moon"));
+    /// scene.add_object(Object::new(Coord::new(99.0, 9.0, 0.0), &torusgen, Rotator::identity()).with_tag("planet"));
+    ///
+    /// // Rotate everything tagged planet
+    /// for planet in scene.query_mut("planet") {
+    ///     planet.spin(Vector3D::new(0.0, 0.0, 1.0), Angle::from_degree(30.0));
+    /// }
+    ///
+    /// let spinning: Vec<_> = scene.query("planet").map(|(id, _)| id.0).collect();
+    /// assert_eq!(spinning, [0, 2]);
+    /// assert!(scene.object(ObjectId(1)).unwrap().spin.is_none());
+    /// ```
+    pub fn query<'s>(
+        &'s self,
+        tag: &'s str,
+    ) -> impl Iterator<Item = (ObjectId, &'s Object<'a>)> {
+        self.objects
+            .iter()
+            .enumerate()
+            .filter(move |(_, obj)| obj.has_tag(tag))
+            .map(|(index, obj)| (ObjectId(index), obj))
+    }
+
+    /// Every object with the given tag, for changing them
+    pub fn query_mut<'s>(
+        &'s mut self,
+        tag: &'s str,
+    ) -> impl Iterator<Item = &'s mut Object<'a>> {
+        self.objects.iter_mut().filter(move |obj| obj.has_tag(tag))
+    }
+
+    /// Finds the object visible in the given cell of the buffer, along with the world
+    /// coordinates of its point closest to the camera
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::components::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::renderer;
+    /// use shapes_rs::{Object, Scene};
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let camera = Camera::new(Coord::default(), Vector3D::default(), Angle::from_degree(60.0));
+    /// let mut scene = Scene::new(camera, SimpleTerminalBuffer::new(40, 20), renderer::pers_proj);
+    /// let cube = scene.add_object(Object::new(Coord::new(30.0, 0.0, 0.0), &cubegen, Rotator::from_global(Angle3D::default())));
+    ///
+    /// let (picked, coord) = scene.pick(20, 10).unwrap();
+    /// assert_eq!(picked, cube);
+    /// assert!((coord.x - 25.0).abs() < 0.001);
+    ///
+    /// assert!(scene.pick(0, 0).is_none());
+    /// ```
+    pub fn pick(&self, buffer_x: usize, buffer_y: usize) -> Option<(ObjectId, Coord)> {
+        let viewport = self.viewport();
+        let x = buffer_x.checked_sub(viewport.x)?;
+        let y = buffer_y.checked_sub(viewport.y)?;
+        if x >= viewport.size_x || y >= viewport.size_y {
+            return None;
+        }
+        project_fragments(&self.snapshot())[y * viewport.size_x + x]
+            .map(|fragment| (ObjectId(fragment.object), fragment.coord))
+    }
+
+    /// Lets the given amount of time pass, rotating every spinning object and then calling
+    /// the update function of every object that has one
+    pub fn advance(&mut self, dt: Duration) {
+        for obj in self.objects.iter_mut() {
+            obj.advance_spin(dt);
+        }
+        self.run_updates(dt);
+    }
+
+    /// Moves the clock forward, moves every orbiting object along its orbit, calls the update
+    /// function of every object that has one and then applies the shakes, without spinning
+    /// anything
+    pub fn run_updates(&mut self, dt: Duration) {
+        self.time += dt.as_secs_f64();
+        // Updates see the transforms without the shakes
+        self.move_by_shakes(-1.0);
+        for index in 0..self.objects.len() {
+            self.place_on_orbit(index);
+        }
+        for obj in self.objects.iter_mut() {
+            if let Some(update) = obj.update {
+                update(obj, self.time, dt.as_secs_f64());
+            }
+        }
+        for light in self.lights.iter_mut() {
+            if let Some(update) = light.update {
+                update(light, self.time, dt.as_secs_f64());
+            }
+        }
+        self.run_tweens();
+        self.update_shakes();
+        self.move_by_shakes(1.0);
+        self.place_attached_lights();
+    }
+
+    /// Runs the tween on the scene's clock, starting now. Whenever the scene is advanced the
+    /// function is called with the current value of the tween, so it can put it wherever it
+    /// belongs. It is called with the start value right away
+    /// ### Example:
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::time::Duration;
+    /// use shapes_rs::animation::easing::Easing;
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::{Repeat, Tween};
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let arrived = Cell::new(false);
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// let cube = scene.add_object(Object::new(Coord::new(60.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+    ///
+    /// // Camera move towards the cube, easing into place
+    /// let dolly = Tween::new(Coord::default(), Coord::new(30.0, 0.0, 0.0), 2.0)
+    ///     .with_easing(Easing::CubicInOut);
+    /// scene
+    ///     .add_tween(dolly, |scene, coord| scene.camera_mut().coord = coord)
+    ///     .on_complete(|_| arrived.set(true));
+    /// // The cube keeps fading in and out
+    /// let pulse = Tween::new(1.0, 0.5, 0.5).with_repeat(Repeat::PingPong);
+    /// scene.add_tween(pulse, move |scene, opacity| {
+    ///     scene.object_mut(cube).unwrap().material.opacity = opacity;
+    /// });
+    ///
+    /// scene.advance(Duration::from_secs(1));
+    /// assert_eq!(scene.camera().coord.x, 15.0);
+    /// assert!(!arrived.get());
+    /// scene.advance(Duration::from_millis(1250));
+    /// assert_eq!(scene.camera().coord.x, 30.0);
+    /// assert!(arrived.get());
+    /// assert_eq!(scene.object(cube).unwrap().material.opacity, 0.75);
+    /// ```
+    pub fn add_tween<T: Lerp + 'a>(
+        &mut self,
+        tween: Tween<T>,
+        mut apply: impl FnMut(&mut Scene<'a>, T) + 'a,
+    ) -> &mut RunningTween<'a> {
+        apply(self, tween.value(0.0));
+        self.tweens.push(RunningTween {
+            start: self.time,
+            step: Box::new(move |scene, elapsed| {
+                apply(scene, tween.value(elapsed));
+                tween.is_done(elapsed)
+            }),
+            on_complete: None,
+        });
+        self.tweens.last_mut().unwrap()
+    }
+
+    /// Ends every tween where it is, without calling their completion functions
+    pub fn stop_tweens(&mut self) {
+        self.tweens.clear();
+    }
+
+    /// Steps every tween to the current time, then calls the completion functions of those
+    /// which are done. Tweens added by them start now
+    fn run_tweens(&mut self) {
+        let mut running = core::mem::take(&mut self.tweens);
+        let mut completed = Vec::new();
+        running.retain_mut(|tween| {
+            if !(tween.step)(self, self.time - tween.start) {
+                return true;
+            }
+            if let Some(done) = tween.on_complete.take() {
+                completed.push(done);
+            }
+            false
+        });
+        // Tweens added while stepping go after the ones which were already running
+        running.append(&mut self.tweens);
+        self.tweens = running;
+        for done in completed {
+            done(self);
+        }
+    }
+
+    /// Starts shaking the camera now, on top of any shake it already has. The camera is moved
+    /// away from where it would be whenever the scene is advanced, and ends up back there once
+    /// the shake is over
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::components::Shake;
+    /// use shapes_rs::testing::fixture_scene;
+    ///
+    /// let mut scene = fixture_scene(40, 20);
+    /// let start = scene.camera().coord;
+    /// scene.shake_camera(Shake::new(2.0, 8.0, 4.0));
+    ///
+    /// scene.advance(Duration::from_millis(100));
+    /// let shaken = scene.camera().coord;
+    /// assert!((shaken - start).to_vector().magnitude() > 0.0);
+    /// // The camera can still be moved while it shakes
+    /// scene.camera_mut().coord.x += 10.0;
+    ///
+    /// scene.advance(Duration::from_secs(5));
+    /// let rest = scene.camera().coord;
+    /// assert!((rest.x - start.x - 10.0).abs() < 1e-9 && (rest.y - start.y).abs() < 1e-9);
+    /// ```
+    pub fn shake_camera(&mut self, shake: Shake) {
+        self.shakes.push(ActiveShake {
+            target: None,
+            shake,
+            start: self.time,
+            offset: Vector3D { x: 0.0, y: 0.0, z: 0.0 },
+        });
+    }
+
+    /// Starts shaking the object now, see shake_camera. Fails if the object does not exist
+    pub fn shake_object(&mut self, id: ObjectId, shake: Shake) -> Result<()> {
+        if self.object(id).is_none() {
+            return Err(ShapesError::UnknownObject(id));
+        }
+        self.shakes.push(ActiveShake {
+            target: Some(id),
+            shake,
+            start: self.time,
+            offset: Vector3D { x: 0.0, y: 0.0, z: 0.0 },
+        });
+        Ok(())
+    }
+
+    /// Ends every shake right away, putting the camera and objects back where they would be
+    pub fn stop_shakes(&mut self) {
+        self.move_by_shakes(-1.0);
+        self.shakes.clear();
     }
 
-    /// Add a light source
+    /// Moves the target of every shake by its offset, times the sign
+    fn move_by_shakes(&mut self, sign: f64) {
+        for active in self.shakes.iter() {
+            let offset = active.offset.mul(sign).as_coord();
+            match active.target {
+                None => self.camera.coord = self.camera.coord + offset,
+                Some(id) => {
+                    if let Some(obj) = self.objects.get_mut(id.0) {
+                        obj.location = obj.location + offset;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Works out the offset of every shake at the current time, dropping those which are over
+    fn update_shakes(&mut self) {
+        let time = self.time;
+        self.shakes.retain(|active| !active.shake.is_over(time - active.start));
+        for active in self.shakes.iter_mut() {
+            active.offset = active.shake.offset(time - active.start);
+        }
+    }
+
+    /// Moves the object with the given index to where on its orbit it is now. Objects going
+    /// around one which does not exist go around the origin instead
+    fn place_on_orbit(&mut self, index: usize) {
+        let Some(orbit) = self.objects[index].orbit else {
+            return;
+        };
+        let mut location = orbit.position(self.time);
+        if let Some(parent) = orbit.parent.and_then(|id| self.objects.get(id.0)) {
+            location = parent.location + location;
+        }
+        self.objects[index].location = location;
+    }
+
+    /// Moves every light riding on an object to where it is on the object now. Lights whose
+    /// object does not exist stay where they are
+    fn place_attached_lights(&mut self) {
+        for light in self.lights.iter_mut() {
+            let Some((id, offset)) = light.parent else {
+                continue;
+            };
+            if let Some(obj) = self.objects.get(id.0) {
+                let offset = offset.to_vector().rotate(&obj.shape.orientation);
+                light.coord = obj.location + offset.as_coord();
+            }
+        }
+    }
+
+    /// Sets the function the object with the given id is updated with whenever the scene is
+    /// advanced, replacing any it had before
+    /// ### Example:
+    /// ```
+    /// use std::time::Duration;
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::{Object, ObjectId};
+    ///
+    /// // Bobs up and down around its starting height
+    /// fn bob(object: &mut Object, t: f64, _dt: f64) {
+    ///     object.location.z = 10.0 * t.sin();
+    /// }
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// let cube = scene.add_object(Object::new(Coord::new(60.0, 0.0, 0.0), &cubegen, Rotator::identity()));
+    /// scene.set_update(cube, bob).unwrap();
+    ///
+    /// scene.advance(Duration::from_millis(500));
+    /// scene.advance(Duration::from_millis(500));
+    /// assert_eq!(scene.time(), 1.0);
+    /// assert_eq!(scene.object(cube).unwrap().location.z, 10.0 * 1f64.sin());
+    ///
+    /// assert!(scene.set_update(ObjectId(5), bob).is_err());
+    /// ```
+    pub fn set_update(&mut self, id: ObjectId, update: ObjectUpdate) -> Result<()> {
+        let object = self.object_mut(id).ok_or(ShapesError::UnknownObject(id))?;
+        object.update = Some(update);
+        Ok(())
+    }
+
+    /// Add a light source. Lights riding on an object are moved onto it right away
     pub fn add_light(&mut self, light: Light3D) {
         self.lights.push(light);
+        self.place_attached_lights();
+    }
+
+    /// Every object, in the order they were added. Iterating over `&scene` does the same
+    pub fn objects(&self) -> slice::Iter<'_, Object<'a>> {
+        self.objects.iter()
+    }
+
+    /// Every object, for changing them. Iterating over `&mut scene` does the same
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::testing::fixture_scene;
+    /// use shapes_rs::Object;
+    ///
+    /// let torusgen = TorusGenerator::new(5.0, 20.0);
+    /// let mut scene = fixture_scene(40, 20);
+    /// scene.add_object(Object::new(Coord::new(70.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+    /// scene.add_object(Object::new(Coord::new(90.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+    ///
+    /// for object in scene.objects_mut() {
+    ///     object.location = object.location + Coord::new(0.0, 5.0, 0.0);
+    /// }
+    /// for light in scene.lights_mut() {
+    ///     light.coord = Coord::new(0.0, 0.0, 100.0);
+    /// }
+    ///
+    /// let heights: Vec<_> = (&scene).into_iter().map(|object| object.location.y).collect();
+    /// assert_eq!(heights, [5.0, 5.0]);
+    /// assert!(scene.lights().all(|light| light.coord.get() == (0.0, 0.0, 100.0)));
+    /// ```
+    pub fn objects_mut(&mut self) -> slice::IterMut<'_, Object<'a>> {
+        self.objects.iter_mut()
+    }
+
+    pub fn lights(&self) -> slice::Iter<'_, Light3D> {
+        self.lights.iter()
+    }
+
+    pub fn lights_mut(&mut self) -> slice::IterMut<'_, Light3D> {
+        self.lights.iter_mut()
+    }
+
+    /// Adds a plane that removes everything on the opposite side of its normal, useful for
+    /// looking inside of closed shapes
+    pub fn add_clip_plane(&mut self, normal: Vector3D, offset: f64) {
+        self.clip_planes.push(ClipPlane::new(normal, offset));
+    }
+
+    /// Sets the colormap used by color renderers and what it is sampled with
+    pub fn set_colormap(&mut self, colormap: Colormap, source: ColorSource) {
+        self.colormap = colormap;
+        self.color_source = source;
+    }
+
+    /// Sets what empty cells are filled with, color renderers draw the character in the color.
+    /// The color behind the cells is set with set_background
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::color::Rgb;
+    /// use shapes_rs::testing::fixture_scene;
+    ///
+    /// let mut scene = fixture_scene(4, 2);
+    /// scene.set_clear('.', Rgb::new(0, 0, 80));
+    ///
+    /// assert_eq!(scene.render().to_string(), "....\n....");
+    /// ```
+    pub fn set_clear(&mut self, ch: char, color: Rgb) {
+        self.clear = ColorCell::new(ch, color);
+    }
+
+    /// Sets the color drawn behind every cell by color renderers, the half block renderer
+    /// fills empty pixels with it. None shows the background of the terminal
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::color::Rgb;
+    /// use shapes_rs::renderer::pers_proj_color;
+    /// use shapes_rs::testing::fixture_scene;
+    ///
+    /// let mut scene = fixture_scene(4, 2);
+    /// scene.set_background(Some(Rgb::new(0, 0, 80)));
+    ///
+    /// let frame = pers_proj_color(&scene.snapshot());
+    /// assert!(frame.to_ansi().contains("\x1b[48;2;0;0;80m "));
+    /// ```
+    pub fn set_background(&mut self, background: Option<Rgb>) {
+        self.background = background;
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
+    }
+
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.camera = camera;
+    }
+
+    /// Buffer the scene renders into, which decides the size of frames
+    pub fn buffer(&self) -> &SimpleTerminalBuffer {
+        &self.buffer
+    }
+
+    pub fn set_buffer(&mut self, buffer: SimpleTerminalBuffer) {
+        self.buffer = buffer;
+    }
+
+    pub fn renderer(&self) -> fn(view: &RenderSnapshot) -> SimpleTerminalBuffer {
+        self.renderer
+    }
+
+    /// Changes the renderer used by render
+    pub fn set_renderer(&mut self, renderer: fn(view: &RenderSnapshot) -> SimpleTerminalBuffer) {
+        self.renderer = renderer;
+    }
+
+    /// Colormap used by color renderers for objects whose material has none
+    pub fn colormap(&self) -> &Colormap {
+        &self.colormap
+    }
+
+    /// Which value is used to sample colormaps
+    pub fn color_source(&self) -> ColorSource {
+        self.color_source
+    }
+
+    /// What renderers fill cells without anything in them with, see set_clear
+    pub fn clear_cell(&self) -> ColorCell {
+        self.clear
+    }
+
+    /// Color drawn behind every cell, see set_background
+    pub fn background(&self) -> Option<Rgb> {
+        self.background
+    }
+
+    /// Seconds the scene has been advanced by in total
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn set_time(&mut self, time: f64) {
+        self.time = time;
+    }
+
+    pub fn object_count(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Removes every light. Renderers draw nothing lit until a light is added again
+    pub fn clear_lights(&mut self) {
+        self.lights.clear();
+    }
+
+    /// Planes points are clipped by, see add_clip_plane
+    pub fn clip_planes(&self) -> slice::Iter<'_, ClipPlane> {
+        self.clip_planes.iter()
+    }
+
+    pub fn clear_clip_planes(&mut self) {
+        self.clip_planes.clear();
+    }
+
+    /// Labels drawn over every frame, see add_label
+    pub fn labels(&self) -> slice::Iter<'_, Label> {
+        self.labels.iter()
+    }
+
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    /// Overlays drawn over every rendered frame
+    pub fn debug(&self) -> &DebugFlags {
+        &self.debug
+    }
+
+    pub fn debug_mut(&mut self) -> &mut DebugFlags {
+        &mut self.debug
+    }
+
+    /// Handed to the renderer with every snapshot
+    pub fn render_options(&self) -> &RenderOptions {
+        &self.render_options
+    }
+
+    pub fn render_options_mut(&mut self) -> &mut RenderOptions {
+        &mut self.render_options
+    }
+
+    pub fn set_render_options(&mut self, options: RenderOptions) {
+        self.render_options = options;
     }
 
     /// Calls the bound buffer's print function. It will do what the buffer is preconfigured to do.
+    #[cfg(feature = "std")]
     pub fn print(&self) {
+        phase_span!("output");
         self.buffer.print();
     }
 }
+
+/// Builds a scene step by step, anything not given is filled in with a default:
+/// - A camera at the origin looking towards positive X with a 60 degree field of view
+/// - An 80 by 40 buffer
+/// - The perspective renderer
+/// - A key light above and behind the camera, if no other light is added
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::{Object, SceneBuilder};
+///
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let scene = SceneBuilder::new()
+///     .buffer_size(60, 30)
+///     .add_object(Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity()))
+///     .build();
+///
+/// assert_eq!(scene.get_buffer_size(), (60, 30));
+/// assert_eq!(scene.lights().len(), 1);
+/// assert!(scene.render().buffer.iter().any(|ch| *ch != ' '));
+/// ```
+pub struct SceneBuilder<'a> {
+    camera: Camera,
+    size_x: usize,
+    size_y: usize,
+    renderer: fn(view: &RenderSnapshot) -> SimpleTerminalBuffer,
+    lights: Vec<Light3D>,
+    objects: Vec<Object<'a>>,
+}
+
+impl<'a> SceneBuilder<'a> {
+    pub fn new() -> Self {
+        Self {
+            camera: Camera::new(
+                Coord::new(0.0, 0.0, 0.0),
+                Vector3D::new(1.0, 0.0, 0.0),
+                Angle::from_degree(60.0),
+            ),
+            size_x: 80,
+            size_y: 40,
+            renderer: pers_proj,
+            lights: Vec::new(),
+            objects: Vec::new(),
+        }
+    }
+
+    pub fn camera(mut self, camera: Camera) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    pub fn buffer_size(mut self, size_x: usize, size_y: usize) -> Self {
+        self.size_x = size_x;
+        self.size_y = size_y;
+        self
+    }
+
+    pub fn renderer(mut self, renderer: fn(view: &RenderSnapshot) -> SimpleTerminalBuffer) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// Adds a light, the default key light is left out once any light is added
+    pub fn add_light(mut self, light: Light3D) -> Self {
+        self.lights.push(light);
+        self
+    }
+
+    pub fn add_object(mut self, object: Object<'a>) -> Self {
+        self.objects.push(object);
+        self
+    }
+
+    pub fn build(self) -> Scene<'a> {
+        let mut scene = Scene::new(
+            self.camera,
+            SimpleTerminalBuffer::new(self.size_x, self.size_y),
+            self.renderer,
+        );
+        if self.lights.is_empty() {
+            scene.add_light(Light3D::new(Coord::new(-100.0, 0.0, 500.0)));
+        }
+        scene.lights.extend(self.lights);
+        for object in self.objects {
+            scene.add_object(object);
+        }
+        scene.place_attached_lights();
+        scene
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s Scene<'a> {
+    type Item = &'s Object<'a>;
+    type IntoIter = slice::Iter<'s, Object<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.objects()
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s mut Scene<'a> {
+    type Item = &'s mut Object<'a>;
+    type IntoIter = slice::IterMut<'s, Object<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.objects_mut()
+    }
+}
+
+impl Default for SceneBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,200 @@
+//! Overlays that help while writing generators and placing objects. They are drawn on top of
+//! every rendered frame, nothing hides them.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::basetype::Coord;
+use crate::component::Object;
+use crate::math::projection::project_coord;
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::out::{Buffer, SizedBuffer};
+use crate::scene::snapshot::RenderSnapshot;
+use crate::scene::ObjectId;
+
+/// Character the edges of bounding boxes are drawn with
+const BOUNDS_CHAR: char = '+';
+/// Character normals are drawn with
+const NORMAL_CHAR: char = '\'';
+/// Character lines between measured objects are drawn with
+const MEASURE_CHAR: char = '%';
+
+/// Debug overlays of a scene, all of them are off by default
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut scene = fixture_scene(60, 30);
+/// let left = scene.add_object(Object::new(Coord::new(60.0, -15.0, 0.0), &cubegen, Rotator::identity()));
+/// let right = scene.add_object(Object::new(Coord::new(60.0, 15.0, 0.0), &cubegen, Rotator::identity()));
+///
+/// scene.debug_mut().bounds = true;
+/// scene.debug_mut().normals = 50;
+/// scene.measure(left, right).unwrap();
+///
+/// let frame = scene.render().to_string();
+/// assert!(frame.contains('+'));
+/// assert!(frame.contains('\''));
+/// // The line between both cubes is labelled with its length
+/// assert!(frame.contains("30.0"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugFlags {
+    /// Draws the edges of the bounding box of every visible object
+    pub bounds: bool,
+    /// Draws a short line along the normal of every nth point of visible objects, 0 draws none
+    pub normals: usize,
+    /// Length of the lines drawn for normals
+    pub normal_length: f64,
+    /// Pairs of objects with a line drawn between their locations, labelled with its length.
+    /// See Scene::measure
+    pub measures: Vec<(ObjectId, ObjectId)>,
+    /// Lists every visible object with points that end up at NaN or infinity once moved into
+    /// world coordinates in the top left corner. Renderers skip such points either way, this
+    /// tells where they come from. See Scene::non_finite_points
+    pub non_finite: bool,
+}
+
+impl Default for DebugFlags {
+    fn default() -> Self {
+        Self {
+            bounds: false,
+            normals: 0,
+            normal_length: 3.0,
+            measures: Vec::new(),
+            non_finite: false,
+        }
+    }
+}
+
+impl DebugFlags {
+    /// Whether any overlay is turned on
+    pub fn any(&self) -> bool {
+        self.bounds || self.normals > 0 || !self.measures.is_empty() || self.non_finite
+    }
+}
+
+/// Point whose position isn't finite in world coordinates, found by non_finite_points
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NonFinitePoint {
+    pub object: ObjectId,
+    /// Index of the point in the shape of the object
+    pub point: usize,
+}
+
+/// Every point of the visible objects which can't be projected because its position in world
+/// coordinates isn't finite, either from its shape or from the transform of its object
+pub fn non_finite_points(view: &RenderSnapshot) -> Vec<NonFinitePoint> {
+    let mut found = Vec::new();
+    for (index, obj) in view.visible_objects() {
+        for (point, coord) in obj.points.iter().enumerate() {
+            let coord = obj.rotation.apply(coord.rel_coord.to_vector()).as_coord() + obj.location;
+            if !(coord.x.is_finite() && coord.y.is_finite() && coord.z.is_finite()) {
+                found.push(NonFinitePoint {
+                    object: ObjectId(index),
+                    point,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Draws every overlay that is turned on into the frame
+pub(crate) fn draw_debug(
+    view: &RenderSnapshot,
+    objects: &[Object],
+    flags: &DebugFlags,
+    frame: &mut SimpleTerminalBuffer,
+) {
+    for (index, obj) in view.visible_objects() {
+        if flags.bounds {
+            if let Some(bounds) = objects[index].shape.bounds {
+                let corners = bounds.corners().map(|corner| {
+                    obj.rotation.apply(corner.to_vector()).as_coord() + obj.location
+                });
+                // Corners are ordered by their bits, one per axis. Edges join corners which
+                // only differ in a single one of them
+                for (i, corner) in corners.iter().enumerate() {
+                    for bit in [1, 2, 4] {
+                        if i & bit == 0 {
+                            draw_line(view, frame, *corner, corners[i | bit], BOUNDS_CHAR);
+                        }
+                    }
+                }
+            }
+        }
+        if flags.normals > 0 {
+            for point in obj.points.iter().step_by(flags.normals) {
+                let start = obj.rotation.apply(point.rel_coord.to_vector()).as_coord() + obj.location;
+                let normal = obj.rotation.apply(point.normal.normalise());
+                let end = start + normal.mul(flags.normal_length).as_coord();
+                draw_line(view, frame, start, end, NORMAL_CHAR);
+            }
+        }
+    }
+    for (a, b) in flags.measures.iter() {
+        let (Some(a), Some(b)) = (view.objects.get(a.0), view.objects.get(b.0)) else {
+            continue;
+        };
+        draw_line(view, frame, a.location, b.location, MEASURE_CHAR);
+        let length = (b.location - a.location).to_vector().magnitude();
+        let middle = (a.location + b.location).mul(0.5);
+        if let Some(((x, y), _)) = project_coord(view, middle) {
+            let text = format!("{:.1}", length);
+            write_text(frame, x.saturating_sub(text.len() / 2), y, &text);
+        }
+    }
+    if flags.non_finite {
+        let found = non_finite_points(view);
+        // One line per object, they are listed in order
+        let mut row = 0;
+        for (at, first) in found.iter().enumerate() {
+            if at > 0 && found[at - 1].object == first.object {
+                continue;
+            }
+            let count = found[at..].iter().take_while(|p| p.object == first.object).count();
+            let text = format!(
+                "object {}: {} non-finite points, first {}",
+                first.object.0, count, first.point
+            );
+            write_text(frame, 0, row, &text);
+            row += 1;
+        }
+    }
+}
+
+/// Writes the text into a single row of the frame, cutting off what doesn't fit
+fn write_text(frame: &mut SimpleTerminalBuffer, x: usize, y: usize, text: &str) {
+    for (offset, ch) in text.chars().enumerate() {
+        let _ = frame.try_set(y, x + offset, ch);
+    }
+}
+
+/// Draws a line between two points in world coordinates, parts outside of the frame or behind
+/// the camera are left out
+fn draw_line(
+    view: &RenderSnapshot,
+    frame: &mut SimpleTerminalBuffer,
+    from: Coord,
+    to: Coord,
+    ch: char,
+) {
+    // One step per cell the line spans on screen, twice as many to make up for perspective
+    let steps = match (project_coord(view, from), project_coord(view, to)) {
+        (Some(((ax, ay), _)), Some(((bx, by), _))) => ax.abs_diff(bx).max(ay.abs_diff(by)) * 2,
+        // Part of the line is off screen, fall back to the size of the frame
+        _ => (frame.size_x + frame.size_y) * 2,
+    };
+    for step in 0..=steps.max(1) {
+        let t = step as f64 / steps.max(1) as f64;
+        let point = from + (to - from).mul(t);
+        if let Some(((x, y), _)) = project_coord(view, point) {
+            frame.set(y, x, ch);
+        }
+    }
+}
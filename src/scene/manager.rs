@@ -0,0 +1,269 @@
+//! Several scenes shown one after another, with transitions between them. Handy for demo reels
+//! cycling through shapes. Works on its own through `render`, or driven by an Animator, see
+//! `Animator::with_scenes`.
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::error::{Result, ShapesError};
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+use crate::math::projection::LUMINANCE_RAMP;
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::out::{Buffer, SizedBuffer};
+use crate::scene::{Scene, SceneBuilder};
+
+/// How one scene is replaced by the next
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Transition {
+    /// The next scene is there right away
+    Cut,
+    /// The old scene fades out along the luminance ramp, then the next one fades in
+    Fade,
+    /// The next scene pushes the old one out from the left
+    Wipe,
+}
+
+impl Transition {
+    /// Frame part way through the transition, 0 being all `from` and 1 all `to`. The result has
+    /// the size of `to`, whatever of `from` does not fit is cut off.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::buffer::*;
+    /// use shapes_rs::Transition;
+    ///
+    /// let from: SimpleTerminalBuffer = "@@@@\n@@@@".parse().unwrap();
+    /// let to: SimpleTerminalBuffer = "####\n####".parse().unwrap();
+    ///
+    /// assert_eq!(Transition::Wipe.blend(&from, &to, 0.5).to_string(), "##@@\n##@@");
+    /// assert_eq!(Transition::Fade.blend(&from, &to, 0.5).to_string(), "    \n    ");
+    /// assert_eq!(Transition::Fade.blend(&from, &to, 0.25).to_string(), ";;;;\n;;;;");
+    /// assert_eq!(Transition::Cut.blend(&from, &to, 0.1).to_string(), "####\n####");
+    /// ```
+    pub fn blend(
+        &self,
+        from: &SimpleTerminalBuffer,
+        to: &SimpleTerminalBuffer,
+        progress: f64,
+    ) -> SimpleTerminalBuffer {
+        let progress = progress.clamp(0.0, 1.0);
+        let mut frame = to.clone();
+        match self {
+            Transition::Cut => {}
+            Transition::Fade => {
+                let (source, brightness) = match progress < 0.5 {
+                    true => (from, 1.0 - 2.0 * progress),
+                    false => (to, 2.0 * progress - 1.0),
+                };
+                for y in 0..frame.size_y {
+                    for x in 0..frame.size_x {
+                        let ch = source.try_get(y, x).unwrap_or(' ');
+                        frame.set(y, x, dim(ch, brightness));
+                    }
+                }
+            }
+            Transition::Wipe => {
+                let edge = (progress * frame.size_x as f64).round() as usize;
+                for y in 0..frame.size_y {
+                    for x in edge..frame.size_x {
+                        frame.set(y, x, from.try_get(y, x).unwrap_or(' '));
+                    }
+                }
+            }
+        }
+        frame
+    }
+}
+
+/// Character of the luminance ramp for the given brightness of a character. Characters which
+/// are not on the ramp stay until the brightness drops below half
+fn dim(ch: char, brightness: f64) -> char {
+    if ch == ' ' {
+        return ch;
+    }
+    match LUMINANCE_RAMP.iter().position(|step| *step as char == ch) {
+        Some(index) => {
+            let level = ((index + 1) as f64 * brightness).round() as usize;
+            match level {
+                0 => ' ',
+                level => LUMINANCE_RAMP[level - 1] as char,
+            }
+        }
+        None if brightness >= 0.5 => ch,
+        None => ' ',
+    }
+}
+
+/// Transition in progress, from the last frame of the old scene
+struct Switch {
+    transition: Transition,
+    from: SimpleTerminalBuffer,
+    frames: usize,
+    shown: usize,
+}
+
+/// Holds several scenes, one of which is shown at a time, and switches between them with
+/// transitions lasting a given amount of frames.
+/// ### Example:
+/// ```
+/// use shapes_rs::demos;
+/// use shapes_rs::{SceneManager, Transition};
+///
+/// let mut manager = SceneManager::new(demos::spinning_donut(40, 20).scene);
+/// let cube = manager.add_scene(demos::tumbling_cube(40, 20).scene);
+///
+/// let donut = manager.render();
+/// manager.switch_to(cube, Transition::Wipe, 4).unwrap();
+/// assert_eq!(manager.current(), cube);
+///
+/// // Half way through, the left half already shows the cube
+/// manager.render();
+/// let half = manager.render();
+/// assert_eq!(half.buffer[420..440], donut.buffer[420..440]);
+///
+/// manager.render();
+/// manager.render();
+/// assert!(!manager.is_transitioning());
+/// assert!(manager.switch_to(7, Transition::Cut, 0).is_err());
+/// ```
+pub struct SceneManager<'a> {
+    /// While an Animator drives the manager, the slot of the current scene holds a placeholder
+    /// and the scene itself is the animator's
+    pub(crate) scenes: Vec<Scene<'a>>,
+    pub(crate) current: usize,
+    switch: Option<Switch>,
+}
+
+impl<'a> SceneManager<'a> {
+    /// Creates a manager showing the given scene
+    pub fn new(scene: Scene<'a>) -> Self {
+        Self {
+            scenes: Vec::from([scene]),
+            current: 0,
+            switch: None,
+        }
+    }
+
+    /// Adds a scene and returns its index
+    pub fn add_scene(&mut self, scene: Scene<'a>) -> usize {
+        self.scenes.push(scene);
+        self.scenes.len() - 1
+    }
+
+    /// Same as add_scene, for building managers
+    pub fn with_scene(mut self, scene: Scene<'a>) -> Self {
+        self.add_scene(scene);
+        self
+    }
+
+    /// Number of scenes, there is always at least one
+    pub fn scene_count(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Index of the scene shown, or being switched to
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// The scene shown, or being switched to
+    pub fn scene(&self) -> &Scene<'a> {
+        &self.scenes[self.current]
+    }
+
+    pub fn scene_mut(&mut self) -> &mut Scene<'a> {
+        &mut self.scenes[self.current]
+    }
+
+    /// Scene with the given index, None if there is none
+    pub fn get(&self, index: usize) -> Option<&Scene<'a>> {
+        self.scenes.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Scene<'a>> {
+        self.scenes.get_mut(index)
+    }
+
+    /// Whether a transition is still going on
+    pub fn is_transitioning(&self) -> bool {
+        self.switch.is_some()
+    }
+
+    /// Switches to the scene with the given index, the transition taking this many frames.
+    /// The old scene stays as it was on its last frame while it is faded or wiped out
+    pub fn switch_to(
+        &mut self,
+        index: usize,
+        transition: Transition,
+        frames: usize,
+    ) -> Result<()> {
+        let from = self.scene().render();
+        self.begin_switch(index, transition, frames, from)
+    }
+
+    /// Switches to the scene after the current one, the first one after the last
+    pub fn next(&mut self, transition: Transition, frames: usize) {
+        let index = (self.current + 1) % self.scenes.len();
+        // Can't fail, the index is always in range
+        let _ = self.switch_to(index, transition, frames);
+    }
+
+    /// Renders the current scene, blended with the old one while a transition is going on.
+    /// Every call is one frame of the transition
+    pub fn render(&mut self) -> SimpleTerminalBuffer {
+        let frame = self.scene().render();
+        self.transition_frame(frame)
+    }
+
+    /// Lets time pass in the current scene, see Scene::advance
+    pub fn advance(&mut self, dt: Duration) {
+        self.scene_mut().advance(dt);
+    }
+
+    /// Starts a transition away from the given frame of the current scene
+    pub(crate) fn begin_switch(
+        &mut self,
+        index: usize,
+        transition: Transition,
+        frames: usize,
+        from: SimpleTerminalBuffer,
+    ) -> Result<()> {
+        if index >= self.scenes.len() {
+            return Err(ShapesError::UnknownScene(index));
+        }
+        self.current = index;
+        self.switch = match (transition, frames) {
+            (Transition::Cut, _) | (_, 0) => None,
+            _ => Some(Switch {
+                transition,
+                from,
+                frames,
+                shown: 0,
+            }),
+        };
+        Ok(())
+    }
+
+    /// Blends a frame of the current scene with the old one, moving the transition one frame on
+    pub(crate) fn transition_frame(
+        &mut self,
+        frame: SimpleTerminalBuffer,
+    ) -> SimpleTerminalBuffer {
+        let Some(switch) = self.switch.as_mut() else {
+            return frame;
+        };
+        switch.shown += 1;
+        let progress = switch.shown as f64 / switch.frames as f64;
+        let blended = switch.transition.blend(&switch.from, &frame, progress);
+        if switch.shown >= switch.frames {
+            self.switch = None;
+        }
+        blended
+    }
+
+    /// Takes the current scene out, leaving a placeholder in its slot
+    pub(crate) fn take_current(&mut self) -> Scene<'a> {
+        let placeholder = SceneBuilder::new().buffer_size(0, 0).build();
+        core::mem::replace(&mut self.scenes[self.current], placeholder)
+    }
+}
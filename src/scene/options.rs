@@ -0,0 +1,182 @@
+use crate::math::dither::Dither;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// How linear renderers bring luminance above 1 back onto the ramp, see pers_proj_hdr
+/// ### Example:
+/// ```
+/// use shapes_rs::renderer::ToneMap;
+///
+/// assert_eq!(ToneMap::Clamp.apply(2.0), 1.0);
+/// assert_eq!(ToneMap::Reinhard.apply(1.0), 0.5);
+/// assert!(ToneMap::Exposure(1.0).apply(3.0) < 1.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum ToneMap {
+    /// Everything above 1 becomes the brightest character
+    #[default]
+    Clamp,
+    /// `l / (1 + l)`, compresses highlights smoothly and never reaches 1
+    Reinhard,
+    /// `1 - e^(-l * exposure)`, higher exposures brighten the whole frame
+    Exposure(f64),
+}
+
+impl ToneMap {
+    pub fn apply(&self, luminance: f64) -> f64 {
+        let luminance = luminance.max(0.0);
+        match self {
+            ToneMap::Clamp => luminance.min(1.0),
+            ToneMap::Reinhard => luminance / (1.0 + luminance),
+            ToneMap::Exposure(exposure) => 1.0 - f64::exp(-luminance * exposure),
+        }
+    }
+}
+
+/// When a point replaces whatever was drawn into its cell before it, given their distances
+/// from the camera
+/// ### Example:
+/// ```
+/// use shapes_rs::renderer::DepthTest;
+///
+/// assert!(!DepthTest::Less.passes(5.0, 5.0));
+/// assert!(DepthTest::LessEqual.passes(5.0, 5.0));
+/// // Points have to be clearly in front, so points of coplanar surfaces don't fight
+/// assert!(!DepthTest::Bias(0.5).passes(4.8, 5.0));
+/// assert!(DepthTest::Bias(0.5).passes(4.0, 5.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum DepthTest {
+    /// Only closer points, the first of two equally far points stays
+    #[default]
+    Less,
+    /// Closer or equally far points, the last of two equally far points stays
+    LessEqual,
+    /// Only points which are closer by more than the given distance
+    Bias(f64),
+}
+
+impl DepthTest {
+    /// Whether a point at the given depth replaces one at the stored depth
+    pub fn passes(&self, depth: f64, stored: f64) -> bool {
+        match self {
+            DepthTest::Less => depth < stored,
+            DepthTest::LessEqual => depth <= stored,
+            DepthTest::Bias(bias) => depth < stored - bias,
+        }
+    }
+}
+
+/// Settings renderers follow when shading points and turning them into characters, shared by
+/// all of them. Everything is off by default
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::renderer::Dither;
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let mut scene = fixture_scene(60, 30);
+/// scene.add_object(Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity()));
+/// let banded = scene.render();
+///
+/// scene.render_options_mut().dither = Dither::Ordered;
+/// let dithered = scene.render();
+///
+/// // Same cells are drawn, some of them one step brighter or darker
+/// assert_ne!(banded.hash(), dithered.hash());
+/// assert!(banded.buffer.iter().zip(dithered.buffer.iter()).all(|(a, b)| (*a == ' ') == (*b == ' ')));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RenderOptions {
+    /// How luminance between two characters of the ramp is spread over them
+    pub dither: Dither,
+    /// Values above 1 brighten dim surfaces so they don't all end up as the darkest character,
+    /// values below 1 darken them
+    pub gamma: f64,
+    /// Spreads luminance away from the middle of the ramp for values above 1, pulls it
+    /// towards it for values below 1
+    pub contrast: f64,
+    /// Used by linear renderers only
+    pub tone_map: ToneMap,
+    /// Used by renderers projecting points, objects can opt out of it
+    pub depth_test: DepthTest,
+    /// Size of the tiles pers_proj_tiled works through, in cells
+    pub tile_size: (usize, usize),
+    /// Lets pers_proj_tiled render tiles on all cores, needs std
+    pub parallel_tiles: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            dither: Dither::None,
+            gamma: 1.0,
+            contrast: 1.0,
+            tone_map: ToneMap::Clamp,
+            depth_test: DepthTest::Less,
+            tile_size: (128, 64),
+            parallel_tiles: false,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Applies contrast and then gamma to a luminance, keeping it between 0 and 1
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::renderer::RenderOptions;
+    ///
+    /// let options = RenderOptions::default().with_gamma(2.0);
+    /// assert_eq!(options.curve(0.25), 0.5);
+    ///
+    /// let options = RenderOptions::default().with_contrast(2.0);
+    /// assert_eq!(options.curve(0.75), 1.0);
+    /// assert_eq!(options.curve(0.5), 0.5);
+    /// ```
+    pub fn curve(&self, luminance: f64) -> f64 {
+        if self.gamma == 1.0 && self.contrast == 1.0 {
+            return luminance;
+        }
+        let contrasted = ((luminance - 0.5) * self.contrast + 0.5).clamp(0.0, 1.0);
+        match self.gamma > 0.0 {
+            true => f64::powf(contrasted, 1.0 / self.gamma),
+            false => contrasted,
+        }
+    }
+
+    pub fn with_gamma(mut self, gamma: f64) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    pub fn with_contrast(mut self, contrast: f64) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    pub fn with_tone_map(mut self, tone_map: ToneMap) -> Self {
+        self.tone_map = tone_map;
+        self
+    }
+
+    pub fn with_depth_test(mut self, depth_test: DepthTest) -> Self {
+        self.depth_test = depth_test;
+        self
+    }
+
+    /// Sets the size of the tiles pers_proj_tiled works through and whether they are rendered
+    /// in parallel
+    pub fn with_tiles(mut self, size_x: usize, size_y: usize, parallel: bool) -> Self {
+        self.tile_size = (size_x, size_y);
+        self.parallel_tiles = parallel;
+        self
+    }
+
+    pub fn with_dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+}
@@ -0,0 +1,349 @@
+//! A tiny line based language for putting scenes together without writing Rust.
+//! Every line holds one command, words are separated by whitespace and `#` starts a comment.
+//!
+//! | Command | Meaning |
+//! |---|---|
+//! | `size W H` | Size of the buffer in cells |
+//! | `camera X Y Z look DX DY DZ [fov DEG]` | Where the camera is and where it faces |
+//! | `light X Y Z` | Adds a light, the default light is left out once there is one |
+//! | `torus NAME THICKNESS SIZE at X Y Z` | Adds a torus |
+//! | `cube NAME X Y Z at X Y Z` | Adds a cube with the given side lengths |
+//! | `rotate NAME AX AY AZ DEG` | Rotates the object around the axis by this much every frame |
+//! | `move NAME DX DY DZ` | Moves the object by this much every frame |
+//! | `orbit NAME X Y Z AX AY AZ DEG` | Moves the object around the point, turning around the axis by this much every frame |
+//!
+//! A frame is one update of the animator playing the script, and a time scale below or above 1
+//! moves the objects only part of the way or further. shapes-play updates 30 times a second
+//! however fast it draws frames.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::str::{FromStr, SplitWhitespace};
+
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path};
+
+#[cfg(feature = "std")]
+use crate::animate::animator::Animator;
+use crate::basetype::{Angle, Coord, Vector3D};
+use crate::component::{Camera, Light3D, Object};
+use crate::scene::{ObjectId, Scene, SceneBuilder};
+use crate::shape::rotator::Rotator;
+use crate::shape::shape_gen::{CubeGenerator, ShapeGen, TorusGenerator};
+
+/// Returned when a script can't be parsed, tells which line is wrong and why
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseScriptError {
+    /// Number of the line, starting at 1
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Error for ParseScriptError {}
+
+struct ScriptObject {
+    name: String,
+    /// Index into the generators of the script
+    generator: usize,
+    location: Coord,
+    rotation: Rotator,
+}
+
+/// Something the script does to an object every frame
+enum Update {
+    Move { object: usize, by: Coord },
+    Orbit { object: usize, center: Coord, rotator: Rotator },
+}
+
+/// A parsed script, which owns the generators of its objects so the scenes built from it can
+/// borrow them. Objects get the ids of their order in the script and keep their names.
+/// ### Example:
+/// ```
+/// use shapes_rs::scripting::Script;
+///
+/// let script: Script = "
+///     size 40 20
+///     light 0 0 100
+///     torus donut 5 20 at 70 0 0
+///     rotate donut 1 0 1 5     # a little every frame
+///     move donut 0 0.5 0
+/// ".parse().unwrap();
+///
+/// let mut scene = script.scene();
+/// assert_eq!(scene.get_buffer_size(), (40, 20));
+/// assert!(scene.render().buffer.iter().any(|ch| *ch != ' '));
+///
+/// script.update(&mut scene);
+/// let donut = scene.find_by_name("donut").unwrap();
+/// assert_eq!(scene.object(donut).unwrap().location.get(), (70.0, 0.5, 0.0));
+///
+/// let err = "torus donut 5 20 at 70 0".parse::<Script>().err().unwrap();
+/// assert_eq!(err.to_string(), "line 1: expected a number");
+/// ```
+pub struct Script {
+    size: Option<(usize, usize)>,
+    camera: Option<Camera>,
+    lights: Vec<Light3D>,
+    generators: Vec<Box<dyn ShapeGen>>,
+    objects: Vec<ScriptObject>,
+    updates: Vec<Update>,
+}
+
+impl Script {
+    /// Reads and parses the script in the given file, parse errors come back as invalid data
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Builds the scene the script describes, rendered with pers_proj
+    pub fn scene(&self) -> Scene<'_> {
+        let mut builder = SceneBuilder::new();
+        if let Some((size_x, size_y)) = self.size {
+            builder = builder.buffer_size(size_x, size_y);
+        }
+        if let Some(camera) = &self.camera {
+            builder = builder.camera(camera.clone());
+        }
+        for light in self.lights.iter() {
+            builder = builder.add_light(light.clone());
+        }
+        for object in self.objects.iter() {
+            let generator = self.generators[object.generator].as_ref();
+            builder = builder.add_object(
+                Object::new(object.location, generator, object.rotation.clone())
+                    .with_name(object.name.clone()),
+            );
+        }
+        builder.build()
+    }
+
+    /// Moves the objects of a scene built from the script by one frame. Rotations are left to
+    /// whatever applies the rotation of objects, like the Animator
+    pub fn update(&self, scene: &mut Scene<'_>) {
+        self.update_by(scene, 1.0);
+    }
+
+    /// Moves the objects of a scene built from the script by the given number of frames,
+    /// fractions of a frame move them part of the way
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::scripting::Script;
+    ///
+    /// let script: Script = "torus donut 5 20 at 70 0 0\nmove donut 0 1 0".parse().unwrap();
+    /// let mut scene = script.scene();
+    /// script.update_by(&mut scene, 0.5);
+    ///
+    /// let donut = scene.find_by_name("donut").unwrap();
+    /// assert_eq!(scene.object(donut).unwrap().location.get(), (70.0, 0.5, 0.0));
+    /// ```
+    pub fn update_by(&self, scene: &mut Scene<'_>, frames: f64) {
+        for update in self.updates.iter() {
+            match update {
+                Update::Move { object, by } => {
+                    if let Some(object) = scene.object_mut(ObjectId(*object)) {
+                        object.location = object.location + by.mul(frames);
+                    }
+                }
+                Update::Orbit {
+                    object,
+                    center,
+                    rotator,
+                } => {
+                    let rotator = match frames == 1.0 {
+                        true => rotator.clone(),
+                        false => Rotator::identity().slerp(rotator, frames),
+                    };
+                    if let Some(object) = scene.object_mut(ObjectId(*object)) {
+                        let offset = (object.location - *center).to_vector().rotate(&rotator);
+                        object.location = *center + offset.as_coord();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Animator for a scene built from the script, which runs the script's updates before
+    /// every frame unless it is paused. They are scaled by the time scale of the animator,
+    /// like the rotations of the objects
+    #[cfg(feature = "std")]
+    pub fn animator(&self) -> Animator<'_> {
+        let mut animator = Animator::new(self.scene());
+        animator.on_pre_frame(move |animator| {
+            if !animator.is_paused() {
+                let frames = animator.time_scale();
+                self.update_by(&mut animator.scene, frames);
+            }
+        });
+        animator
+    }
+
+    fn object_index(&self, name: &str) -> Option<usize> {
+        self.objects.iter().position(|object| object.name == name)
+    }
+
+    fn run_command(&mut self, words: &mut Words) -> Result<(), &'static str> {
+        let Some(command) = words.next() else {
+            return Ok(());
+        };
+        match command {
+            "size" => {
+                let (size_x, size_y) = (words.count()?, words.count()?);
+                self.size = Some((size_x, size_y));
+            }
+            "camera" => {
+                let coord = words.coord()?;
+                words.keyword("look")?;
+                let facing = words.vector()?;
+                let fov = match words.next() {
+                    Some("fov") => words.number()?,
+                    Some(_) => return Err("expected fov"),
+                    None => 60.0,
+                };
+                self.camera = Some(Camera::new(coord, facing.normalise(), Angle::from_degree(fov)));
+            }
+            "light" => {
+                let coord = words.coord()?;
+                self.lights.push(Light3D::new(coord));
+            }
+            "torus" | "cube" => {
+                let name = words.name()?;
+                if self.object_index(name).is_some() {
+                    return Err("an object with this name exists already");
+                }
+                let generator: Box<dyn ShapeGen> = match command {
+                    "torus" => Box::new(TorusGenerator::new(words.number()?, words.number()?)),
+                    _ => {
+                        let (x, y, z) = (words.number()?, words.number()?, words.number()?);
+                        Box::new(CubeGenerator::try_new(x, y, z).map_err(|_| "invalid cube size")?)
+                    }
+                };
+                words.keyword("at")?;
+                let location = words.coord()?;
+                self.generators.push(generator);
+                self.objects.push(ScriptObject {
+                    name: name.to_string(),
+                    generator: self.generators.len() - 1,
+                    location,
+                    rotation: Rotator::identity(),
+                });
+            }
+            "rotate" => {
+                let object = words.object(self)?;
+                let rotator = words.rotator()?;
+                self.objects[object].rotation = rotator;
+            }
+            "move" => {
+                let object = words.object(self)?;
+                let by = words.coord()?;
+                self.updates.push(Update::Move { object, by });
+            }
+            "orbit" => {
+                let object = words.object(self)?;
+                let center = words.coord()?;
+                let rotator = words.rotator()?;
+                self.updates.push(Update::Orbit {
+                    object,
+                    center,
+                    rotator,
+                });
+            }
+            _ => return Err("unknown command"),
+        }
+        match words.next() {
+            Some(_) => Err("too many arguments"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Script {
+    type Err = ParseScriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut script = Script {
+            size: None,
+            camera: None,
+            lights: Vec::new(),
+            generators: Vec::new(),
+            objects: Vec::new(),
+            updates: Vec::new(),
+        };
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut words = Words(line.split_whitespace());
+            script
+                .run_command(&mut words)
+                .map_err(|reason| ParseScriptError {
+                    line: index + 1,
+                    reason,
+                })?;
+        }
+        Ok(script)
+    }
+}
+
+/// Arguments of a command
+struct Words<'s>(SplitWhitespace<'s>);
+
+impl<'s> Words<'s> {
+    fn next(&mut self) -> Option<&'s str> {
+        self.0.next()
+    }
+
+    fn name(&mut self) -> Result<&'s str, &'static str> {
+        self.next().ok_or("expected a name")
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), &'static str> {
+        match self.next() {
+            Some(word) if word == keyword => Ok(()),
+            _ => Err("expected a keyword"),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, &'static str> {
+        self.next()
+            .and_then(|word| word.parse().ok())
+            .filter(|number: &f64| number.is_finite())
+            .ok_or("expected a number")
+    }
+
+    fn count(&mut self) -> Result<usize, &'static str> {
+        self.next()
+            .and_then(|word| word.parse().ok())
+            .ok_or("expected a whole number")
+    }
+
+    fn coord(&mut self) -> Result<Coord, &'static str> {
+        Ok(Coord::new(self.number()?, self.number()?, self.number()?))
+    }
+
+    fn vector(&mut self) -> Result<Vector3D, &'static str> {
+        let (x, y, z) = (self.number()?, self.number()?, self.number()?);
+        Vector3D::try_new(x, y, z).map_err(|_| "a direction can't have all components zero")
+    }
+
+    /// Axis followed by an angle in degrees
+    fn rotator(&mut self) -> Result<Rotator, &'static str> {
+        let axis = self.vector()?;
+        let angle = Angle::from_degree(self.number()?);
+        Ok(Rotator::new(axis, angle.get()))
+    }
+
+    fn object(&mut self, script: &Script) -> Result<usize, &'static str> {
+        let name = self.name()?;
+        script.object_index(name).ok_or("no object with this name")
+    }
+}
@@ -0,0 +1,139 @@
+use alloc::vec::Vec;
+
+use crate::basetype::Coord;
+use crate::component::{Camera, ClipPlane, Light3D, Material, Object};
+use crate::out::color::{ColorSource, Colormap, Rgb};
+use crate::out::color_terminal::ColorCell;
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::scene::options::RenderOptions;
+use crate::shape::rotator::Rotator;
+use crate::shape::sdf::Sdf;
+use crate::shape::shape_base::Point;
+use crate::shape::texture::Texture;
+
+/// Everything a renderer needs to know about a single object, borrowed from the scene
+#[derive(Clone)]
+pub struct ObjectSnapshot<'s> {
+    pub location: Coord,
+    /// Rotation applied to the points when they are drawn
+    pub rotation: Rotator,
+    /// Points of the shape, relative to the location
+    pub points: &'s [Point],
+    pub material: &'s Material,
+    pub sdf: Option<&'s dyn Sdf>,
+    pub texture: Option<&'s dyn Texture>,
+    pub visible: bool,
+    pub layer_mask: u32,
+    pub depth_test: bool,
+    /// Turns directions in world space into the space of the distance function
+    to_local: Rotator,
+}
+
+impl<'s> ObjectSnapshot<'s> {
+    pub fn new(object: &'s Object) -> Self {
+        Self {
+            location: object.location,
+            rotation: object.rotation.clone(),
+            points: &object.shape.points,
+            material: &object.material,
+            sdf: object.sdf,
+            texture: object.texture,
+            visible: object.visible,
+            layer_mask: object.layer_mask,
+            depth_test: object.depth_test,
+            to_local: object
+                .rotation
+                .multiply(&object.shape.orientation)
+                .conjugate(),
+        }
+    }
+
+    /// Distance from a point in world coordinates to the surface of the object's distance
+    /// function, if it has one
+    pub fn distance(&self, point: Coord) -> Option<f64> {
+        let sdf = self.sdf?;
+        let relative = point - self.location;
+        Some(sdf.distance(self.to_local.apply(relative.to_vector()).as_coord()))
+    }
+}
+
+/// Frozen view of a scene that renderers work on. Taking one does not copy any points, and as
+/// it can't change while it is being rendered the same snapshot can be rendered several
+/// times, from other cameras or on other threads.
+/// ### Example:
+/// ```
+/// use std::thread;
+/// use shapes_rs::base::*;
+/// use shapes_rs::components::Camera;
+/// use shapes_rs::demos;
+/// use shapes_rs::renderer::pers_proj;
+///
+/// let demo = demos::tumbling_cube(40, 20);
+/// let snapshot = demo.scene.snapshot();
+/// let behind = Camera::new(Coord::new(240.0, 0.0, 0.0), Vector3D::new(-1.0, 0.0, 0.0), Angle::from_degree(60.0));
+///
+/// let (front, back) = thread::scope(|scope| {
+///     let front = scope.spawn(|| pers_proj(&snapshot));
+///     let back = scope.spawn(|| pers_proj(&snapshot.clone().with_camera(behind)));
+///     (front.join().unwrap(), back.join().unwrap())
+/// });
+///
+/// assert_eq!(front.hash(), demo.scene.render().hash());
+/// assert!(back.buffer.iter().any(|ch| *ch != ' '));
+/// ```
+#[derive(Clone)]
+pub struct RenderSnapshot<'s> {
+    pub camera: Camera,
+    pub size_x: usize,
+    pub size_y: usize,
+    pub lights: &'s [Light3D],
+    pub objects: Vec<ObjectSnapshot<'s>>,
+    /// Points removed by any of these planes are not rendered
+    pub clip_planes: &'s [ClipPlane],
+    /// Colormap used by color renderers for objects whose material has none
+    pub colormap: &'s Colormap,
+    /// Which value is used to sample colormaps
+    pub color_source: ColorSource,
+    /// What empty cells are filled with
+    pub clear: ColorCell,
+    /// Color drawn behind every cell by color renderers
+    pub background: Option<Rgb>,
+    pub options: RenderOptions,
+}
+
+impl RenderSnapshot<'_> {
+    /// Returns the size of the buffer being rendered to
+    pub fn get_buffer_size(&self) -> (usize, usize) {
+        (self.size_x, self.size_y)
+    }
+
+    /// Objects the camera sees along with their index, skipping hidden ones and those on
+    /// layers the camera does not render
+    pub fn visible_objects(&self) -> impl Iterator<Item = (usize, &ObjectSnapshot<'_>)> {
+        self.objects
+            .iter()
+            .enumerate()
+            .filter(|(_, obj)| obj.visible && obj.layer_mask & self.camera.layers != 0)
+    }
+
+    /// Renders through a different camera
+    pub fn with_camera(mut self, camera: Camera) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    /// Renders to a buffer of a different size
+    pub fn with_size(mut self, size_x: usize, size_y: usize) -> Self {
+        self.size_x = size_x;
+        self.size_y = size_y;
+        self
+    }
+
+    /// Renders the snapshot with the given renderer
+    pub fn render(
+        &self,
+        renderer: fn(view: &RenderSnapshot) -> SimpleTerminalBuffer,
+    ) -> SimpleTerminalBuffer {
+        renderer(self)
+    }
+}
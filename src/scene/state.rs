@@ -0,0 +1,59 @@
+use alloc::vec::Vec;
+
+use crate::basetype::{Angle, Coord, Vector3D};
+use crate::component::{Camera, Object};
+use crate::shape::rotator::Rotator;
+use crate::shape::shape_base::{Bounds, Point};
+
+/// Where an object was and how it was turned when a scene was checkpointed. Keeps a copy of
+/// the turned points as well, turning them back instead would be off by rounding errors
+#[derive(Clone)]
+pub struct ObjectState {
+    pub location: Coord,
+    /// Rotation applied every update
+    pub rotation: Rotator,
+    /// How far the points were turned away from how they were generated
+    pub orientation: Rotator,
+    pub points: Vec<Point>,
+    pub bounds: Option<Bounds>,
+    pub spin: Option<(Vector3D, Angle)>,
+    pub visible: bool,
+}
+
+impl ObjectState {
+    pub fn new(object: &Object) -> Self {
+        Self {
+            location: object.location,
+            rotation: object.rotation.clone(),
+            orientation: object.shape.orientation.clone(),
+            points: object.shape.points.clone(),
+            bounds: object.shape.bounds,
+            spin: object.spin,
+            visible: object.visible,
+        }
+    }
+
+    /// Puts the object back the way it was
+    pub fn apply(&self, object: &mut Object) {
+        object.shape.points.clone_from(&self.points);
+        object.shape.bounds = self.bounds;
+        object.shape.orientation = self.orientation.clone();
+        object.location = self.location;
+        object.rotation = self.rotation.clone();
+        object.spin = self.spin;
+        object.visible = self.visible;
+    }
+}
+
+/// Transforms of every object, the camera, where the lights are and the clock of a scene at
+/// one point in time, see Scene::checkpoint. Shapes, tweens and everything else stay with the
+/// scene
+#[derive(Clone)]
+pub struct SceneState {
+    pub camera: Camera,
+    pub time: f64,
+    /// In the order of the objects of the scene
+    pub objects: Vec<ObjectState>,
+    /// Where every light was, in the order of the lights of the scene
+    pub lights: Vec<Coord>,
+}
@@ -1,3 +1,8 @@
 pub mod shape_base;
+pub mod modifier;
+pub mod polyhedron;
 pub mod rotator;
+pub mod sdf;
 pub mod shape_gen;
+pub mod texture;
+pub mod voxel;
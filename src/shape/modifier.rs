@@ -0,0 +1,484 @@
+//! Modifiers change the points of an already generated shape, so generators can stay simple
+//! and be combined with any of them.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::basetype::{Coord, Vector3D};
+use crate::error::{Result, ShapesError};
+use crate::math::random::Rng;
+use crate::math::sampling::{fibonacci_sphere, hemisphere};
+use crate::out::color::Colormap;
+use crate::shape::shape_base::{Bounds, Point, Shape};
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+pub trait ShapeModifier {
+    fn modify(&self, shape: &mut Shape);
+}
+
+/// One of the three axes
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// Reflects a shape across the plane through its center which is perpendicular to the axis.
+/// By default the reflection is added to the original, so only half of a symmetric shape
+/// needs to be generated.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::modifiers::*;
+/// use shapes_rs::Object;
+///
+/// // Upper half of a torus lying in the Y-Z plane
+/// let halfgen = TorusGenerator::new(10.0, 50.0)
+///     .with_major_sweep(Angle::from_degree(0.0), Angle::from_degree(180.0));
+/// let mut half = Object::new(Coord::default(), &halfgen, Rotator::identity()).shape;
+/// let count = half.points.len();
+///
+/// half.modify(&Mirror::z());
+///
+/// assert_eq!(half.points.len(), count * 2);
+/// assert!(half.points.iter().any(|p| p.rel_coord.z < -50.0 && p.normal.z < 0.0));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Mirror {
+    pub axis: Axis,
+    /// Keeps the original points next to the reflected ones
+    pub keep_original: bool,
+}
+
+impl Mirror {
+    pub fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            keep_original: true,
+        }
+    }
+
+    /// Mirrors across the Y-Z plane
+    pub fn x() -> Self {
+        Self::new(Axis::X)
+    }
+
+    /// Mirrors across the X-Z plane
+    pub fn y() -> Self {
+        Self::new(Axis::Y)
+    }
+
+    /// Mirrors across the X-Y plane
+    pub fn z() -> Self {
+        Self::new(Axis::Z)
+    }
+
+    /// Sets whether the original points stay next to the reflected ones
+    pub fn with_keep_original(mut self, keep_original: bool) -> Self {
+        self.keep_original = keep_original;
+        self
+    }
+
+    fn reflect(&self, coord: Coord) -> Coord {
+        match self.axis {
+            Axis::X => Coord::new(-coord.x, coord.y, coord.z),
+            Axis::Y => Coord::new(coord.x, -coord.y, coord.z),
+            Axis::Z => Coord::new(coord.x, coord.y, -coord.z),
+        }
+    }
+}
+
+impl ShapeModifier for Mirror {
+    fn modify(&self, shape: &mut Shape) {
+        // Normals are reflected the same way as positions, so they keep facing outwards
+        let reflected: Vec<Point> = shape
+            .points
+            .iter()
+            .map(|point| {
+                Point {
+                    rel_coord: self.reflect(point.rel_coord),
+                    normal: self.reflect(point.normal.as_coord()).to_vector(),
+                    ..*point
+                }
+            })
+            .collect();
+        let reflected_bounds = shape.bounds.map(|bounds| {
+            let (a, b) = (self.reflect(bounds.min), self.reflect(bounds.max));
+            Bounds::new(a, a).union(&Bounds::new(b, b))
+        });
+        if self.keep_original {
+            shape.points.extend(reflected);
+            shape.bounds = shape
+                .bounds
+                .zip(reflected_bounds)
+                .map(|(own, reflected)| own.union(&reflected));
+        } else {
+            shape.points = reflected;
+            shape.bounds = reflected_bounds;
+        }
+    }
+}
+
+/// Stretches or squashes a shape along the three axes, turning tori into squashed rings or
+/// cubes into slabs. Normals are scaled by the inverse of the scale and normalised again,
+/// scaling them like the points would leave them no longer perpendicular to the surface and
+/// shade it wrong.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::modifiers::*;
+/// use shapes_rs::Object;
+///
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let mut torus = Object::new(Coord::default(), &torusgen, Rotator::identity()).shape;
+/// let original = torus.clone();
+///
+/// // Twice as thick along the axis of the ring
+/// torus.modify(&Scale::try_new(2.0, 1.0, 1.0).unwrap());
+/// assert_eq!(torus.bounds.unwrap().max.x, 20.0);
+///
+/// // On the outer edge of the ring, normals still point straight out
+/// let outer = torus.points.iter().find(|p| p.rel_coord.y > 59.99).unwrap();
+/// assert!((outer.normal.y - 1.0).abs() < 1e-3);
+/// // Elsewhere they lean away from the stretched axis, stretched normals would lean into it
+/// assert!(torus.points.iter().zip(original.points.iter()).all(|(scaled, before)| {
+///     scaled.normal.x.abs() <= before.normal.x.abs() + 1e-9
+///         && (scaled.normal.magnitude() - 1.0).abs() < 1e-9
+/// }));
+///
+/// assert!(Scale::try_new(0.0, 1.0, 1.0).is_err());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Scale {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Scale {
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Same as new, but fails if a factor is zero or not finite, which would flatten the
+    /// shape and leave it without sensible normals
+    pub fn try_new(x: f64, y: f64, z: f64) -> Result<Self> {
+        if [x, y, z].iter().any(|factor| !factor.is_finite() || *factor == 0.0) {
+            return Err(ShapesError::InvalidParameter(
+                "scale factors have to be finite and not zero",
+            ));
+        }
+        Ok(Self::new(x, y, z))
+    }
+
+    /// Same factor along every axis
+    pub const fn uniform(factor: f64) -> Self {
+        Self::new(factor, factor, factor)
+    }
+
+    fn apply(&self, coord: Coord) -> Coord {
+        Coord::new(coord.x * self.x, coord.y * self.y, coord.z * self.z)
+    }
+}
+
+impl ShapeModifier for Scale {
+    fn modify(&self, shape: &mut Shape) {
+        for point in shape.points.iter_mut() {
+            point.rel_coord = self.apply(point.rel_coord);
+            // Inverse transpose of the scale, which for a diagonal matrix is just the inverse
+            let normal = point.normal;
+            point.normal = Vector3D {
+                x: normal.x / self.x,
+                y: normal.y / self.y,
+                z: normal.z / self.z,
+            }
+            .normalise();
+        }
+        // Negative factors swap the corners
+        shape.bounds = shape.bounds.map(|bounds| {
+            let (a, b) = (self.apply(bounds.min), self.apply(bounds.max));
+            Bounds::new(a, a).union(&Bounds::new(b, b))
+        });
+    }
+}
+
+/// Moves every point along its normal by a random amount of up to `amount` in either
+/// direction, making smooth shapes look rough. The same seed always gives the same surface.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::modifiers::*;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut rock = Object::new(Coord::default(), &cubegen, Rotator::identity()).shape;
+/// let mut same_rock = rock.clone();
+///
+/// rock.modify(&Jitter::new(0.5, 3));
+/// same_rock.modify(&Jitter::new(0.5, 3));
+///
+/// assert!(rock.points.iter().all(|p| p.rel_coord.x.abs() <= 5.5));
+/// assert!(rock.points.iter().any(|p| p.rel_coord.x.abs() > 5.0));
+/// assert_eq!(rock.points[42].rel_coord.z, same_rock.points[42].rel_coord.z);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Jitter {
+    /// Largest distance a point is moved
+    pub amount: f64,
+    pub seed: u64,
+}
+
+impl Jitter {
+    pub fn new(amount: f64, seed: u64) -> Self {
+        Self { amount, seed }
+    }
+}
+
+impl ShapeModifier for Jitter {
+    fn modify(&self, shape: &mut Shape) {
+        let mut rng = Rng::new(self.seed);
+        for point in shape.points.iter_mut() {
+            let offset = point.normal.normalise().mul(rng.range(-self.amount, self.amount));
+            point.rel_coord = point.rel_coord + offset.as_coord();
+        }
+        let grow = Coord::new(self.amount, self.amount, self.amount);
+        shape.bounds = shape
+            .bounds
+            .map(|bounds| Bounds::new(bounds.min - grow, bounds.max + grow));
+    }
+}
+
+/// Value of a point that coloring modifiers can map from
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PointAttribute {
+    /// One component of the normal, between -1 and 1
+    Normal(Axis),
+    /// One component of the position relative to the shape's center, `Position(Axis::Z)` being
+    /// the height
+    Position(Axis),
+    /// Distance from the shape's center
+    Distance,
+}
+
+impl PointAttribute {
+    pub fn of(&self, point: &Point) -> f64 {
+        let pick = |axis: &Axis, x: f64, y: f64, z: f64| match axis {
+            Axis::X => x,
+            Axis::Y => y,
+            Axis::Z => z,
+        };
+        match self {
+            PointAttribute::Normal(axis) => {
+                let n = point.normal.normalise();
+                pick(axis, n.x, n.y, n.z)
+            }
+            PointAttribute::Position(axis) => {
+                let c = point.rel_coord;
+                pick(axis, c.x, c.y, c.z)
+            }
+            PointAttribute::Distance => point.rel_coord.to_vector().magnitude(),
+        }
+    }
+
+    /// Where the attribute of the point lies between the ends of the range, from 0 to 1
+    fn normalised(&self, point: &Point, (start, end): (f64, f64)) -> f64 {
+        if end == start {
+            return 0.0;
+        }
+        ((self.of(point) - start) / (end - start)).clamp(0.0, 1.0)
+    }
+}
+
+/// Colors every point by sampling a colormap with one of its attributes, mapped from the given
+/// range onto the colormap
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::color::*;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::modifiers::*;
+/// use shapes_rs::Object;
+///
+/// // The X component of a torus normal follows the angle around its minor circle
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let mut torus = Object::new(Coord::default(), &torusgen, Rotator::identity()).shape;
+/// torus.modify(&ColorBy::new(PointAttribute::Normal(Axis::X), (-1.0, 1.0), Colormap::Viridis));
+///
+/// let first = torus.points[0];
+/// assert_eq!(first.color, Some(Colormap::Viridis.sample(1.0)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ColorBy {
+    pub attribute: PointAttribute,
+    pub range: (f64, f64),
+    pub colormap: Colormap,
+}
+
+impl ColorBy {
+    pub fn new(attribute: PointAttribute, range: (f64, f64), colormap: Colormap) -> Self {
+        Self {
+            attribute,
+            range,
+            colormap,
+        }
+    }
+}
+
+impl ShapeModifier for ColorBy {
+    fn modify(&self, shape: &mut Shape) {
+        for point in shape.points.iter_mut() {
+            let t = self.attribute.normalised(point, self.range);
+            point.color = Some(self.colormap.sample(t));
+        }
+    }
+}
+
+/// Brightens or darkens every point depending on one of its attributes. Points at the start of
+/// the range get `-strength` added to their luminance, points at the end `strength`
+#[derive(Copy, Clone, Debug)]
+pub struct LuminanceBy {
+    pub attribute: PointAttribute,
+    pub range: (f64, f64),
+    pub strength: f64,
+}
+
+impl LuminanceBy {
+    pub fn new(attribute: PointAttribute, range: (f64, f64), strength: f64) -> Self {
+        Self {
+            attribute,
+            range,
+            strength,
+        }
+    }
+}
+
+impl ShapeModifier for LuminanceBy {
+    fn modify(&self, shape: &mut Shape) {
+        for point in shape.points.iter_mut() {
+            let t = self.attribute.normalised(point, self.range);
+            point.luminance_bias = (2.0 * t - 1.0) * self.strength;
+        }
+    }
+}
+
+/// Darkens points the rest of the shape hides from the light around them, like the inside of
+/// crevices or where two parts of a composite meet. Rays are cast from every point in `samples`
+/// directions around its normal, a ray is blocked when another point within `radius` lies
+/// close to it. Points on the same flat surface never block each other.
+/// Only the points are looked at, so it has to be applied again after parts are moved.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::modifiers::*;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut pair = Object::new(Coord::default(), &cubegen, Rotator::identity()).shape;
+/// // Second cube with a gap of 2 between the two
+/// let other = pair.translate(Coord::new(0.0, 12.0, 0.0));
+/// pair.merge(&other);
+///
+/// pair.modify(&AmbientOcclusion::new(4.0));
+///
+/// let facing_gap = pair.points.iter().find(|p| p.rel_coord.get() == (0.0, 5.0, 0.0) && p.normal.y == 1.0).unwrap();
+/// let facing_away = pair.points.iter().find(|p| p.rel_coord.y == -5.0 && p.normal.y == -1.0).unwrap();
+/// assert!(facing_gap.occlusion > 0.5);
+/// assert_eq!(facing_away.occlusion, 0.0);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct AmbientOcclusion {
+    /// How far away points can be to block the light
+    pub radius: f64,
+    /// Number of rays cast from every point
+    pub samples: usize,
+    /// Occlusion of points which are blocked in every direction
+    pub strength: f64,
+}
+
+impl AmbientOcclusion {
+    pub fn new(radius: f64) -> Self {
+        Self {
+            radius,
+            samples: 32,
+            strength: 1.0,
+        }
+    }
+
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn with_strength(mut self, strength: f64) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    /// Cell of the grid the neighbour search uses, as large as the radius
+    fn cell(&self, coord: Coord) -> (i64, i64, i64) {
+        let index = |v: f64| f64::floor(v / self.radius) as i64;
+        (index(coord.x), index(coord.y), index(coord.z))
+    }
+}
+
+/// Points closer to a ray than this, relative to how far along it they are, block it
+const OCCLUSION_CONE: f64 = 0.9;
+/// Neighbours closer to the tangent plane than this, as the cosine of their angle to the
+/// normal, count as part of the same surface
+const OCCLUSION_BIAS: f64 = 0.2;
+
+impl ShapeModifier for AmbientOcclusion {
+    fn modify(&self, shape: &mut Shape) {
+        if self.radius <= 0.0 || self.samples == 0 {
+            return;
+        }
+        let mut grid: BTreeMap<(i64, i64, i64), Vec<usize>> = BTreeMap::new();
+        for (index, point) in shape.points.iter().enumerate() {
+            grid.entry(self.cell(point.rel_coord)).or_default().push(index);
+        }
+        let directions: Vec<Vector3D> = fibonacci_sphere(self.samples).collect();
+        let occlusion: Vec<f64> = shape
+            .points
+            .iter()
+            .map(|point| {
+                let normal = point.normal.normalise();
+                let (cx, cy, cz) = self.cell(point.rel_coord);
+                // Directions towards every neighbour which could block a ray
+                let mut blockers = Vec::new();
+                for x in cx - 1..=cx + 1 {
+                    for y in cy - 1..=cy + 1 {
+                        for z in cz - 1..=cz + 1 {
+                            for other in grid.get(&(x, y, z)).into_iter().flatten() {
+                                let offset = (shape.points[*other].rel_coord - point.rel_coord).to_vector();
+                                let distance = offset.magnitude();
+                                if distance == 0.0 || distance > self.radius {
+                                    continue;
+                                }
+                                let direction = offset.mul(1.0 / distance);
+                                if direction.dot(normal) > OCCLUSION_BIAS {
+                                    blockers.push(direction);
+                                }
+                            }
+                        }
+                    }
+                }
+                if blockers.is_empty() {
+                    return 0.0;
+                }
+                let blocked = hemisphere(directions.iter().copied(), normal)
+                    .filter(|ray| blockers.iter().any(|b| b.dot(*ray) > OCCLUSION_CONE))
+                    .count();
+                (blocked as f64 / self.samples as f64 * self.strength).clamp(0.0, 1.0)
+            })
+            .collect();
+        for (point, occlusion) in shape.points.iter_mut().zip(occlusion) {
+            point.occlusion = occlusion;
+        }
+    }
+}
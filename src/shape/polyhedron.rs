@@ -0,0 +1,638 @@
+//! Generators for solids with flat faces. Faces are filled with evenly spaced points which all
+//! share the normal of their face, so the solids look faceted instead of smooth.
+
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use crate::basetype::{Coord, Vector3D};
+use crate::error::{Result, ShapesError};
+use crate::shape::shape_base::{Bounds, Point, Shape};
+use crate::shape::shape_gen::{CubeMode, ShapeGen};
+use crate::shape::sdf::Sdf;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Golden ratio, which the dodecahedron and icosahedron are built from
+const PHI: f64 = 1.618_033_988_749_895;
+
+/// The five regular convex solids
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Platonic {
+    /// Four triangles
+    Tetrahedron,
+    /// Six squares
+    Cube,
+    /// Eight triangles
+    Octahedron,
+    /// Twelve pentagons
+    Dodecahedron,
+    /// Twenty triangles
+    Icosahedron,
+}
+
+impl Platonic {
+    /// Corners of the solid, not normalised
+    fn vertices(&self) -> Vec<Coord> {
+        let mut vertices = Vec::new();
+        let signs = [1.0, -1.0];
+        match self {
+            Platonic::Tetrahedron => {
+                for (x, y, z) in [(1.0, 1.0, 1.0), (1.0, -1.0, -1.0), (-1.0, 1.0, -1.0)] {
+                    vertices.push(Coord::new(x, y, z));
+                }
+                vertices.push(Coord::new(-1.0, -1.0, 1.0));
+            }
+            Platonic::Cube => {
+                for x in signs {
+                    for y in signs {
+                        for z in signs {
+                            vertices.push(Coord::new(x, y, z));
+                        }
+                    }
+                }
+            }
+            Platonic::Octahedron => {
+                for sign in signs {
+                    vertices.push(Coord::new(sign, 0.0, 0.0));
+                    vertices.push(Coord::new(0.0, sign, 0.0));
+                    vertices.push(Coord::new(0.0, 0.0, sign));
+                }
+            }
+            Platonic::Dodecahedron => {
+                vertices.extend(Platonic::Cube.vertices());
+                for a in signs {
+                    for b in signs {
+                        vertices.push(Coord::new(0.0, a / PHI, b * PHI));
+                        vertices.push(Coord::new(a / PHI, b * PHI, 0.0));
+                        vertices.push(Coord::new(a * PHI, 0.0, b / PHI));
+                    }
+                }
+            }
+            Platonic::Icosahedron => {
+                for a in signs {
+                    for b in signs {
+                        // Turned so its corners point at the faces of the dodecahedron
+                        vertices.push(Coord::new(0.0, a * PHI, b));
+                        vertices.push(Coord::new(a * PHI, b, 0.0));
+                        vertices.push(Coord::new(a, 0.0, b * PHI));
+                    }
+                }
+            }
+        }
+        vertices
+    }
+
+    /// Directions the faces point in. The faces of a platonic solid point at the corners of
+    /// its dual
+    fn face_directions(&self) -> Vec<Coord> {
+        match self {
+            Platonic::Tetrahedron => Platonic::Tetrahedron
+                .vertices()
+                .into_iter()
+                .map(|vertex| vertex.mul(-1.0))
+                .collect(),
+            Platonic::Cube => Platonic::Octahedron.vertices(),
+            Platonic::Octahedron => Platonic::Cube.vertices(),
+            Platonic::Dodecahedron => Platonic::Icosahedron.vertices(),
+            Platonic::Icosahedron => Platonic::Dodecahedron.vertices(),
+        }
+    }
+}
+
+/// Flat sided face, corners in order around the normal
+pub(crate) struct Face {
+    pub corners: Vec<Coord>,
+    pub normal: Vector3D,
+}
+
+impl Face {
+    /// Face through the given corners, which have to lie in one plane, put in order around the
+    /// normal. Fails without any corners
+    pub fn new(mut corners: Vec<Coord>, normal: Vector3D) -> Result<Self> {
+        if corners.is_empty() {
+            return Err(ShapesError::InvalidParameter("a face needs at least one corner"));
+        }
+        let center = centroid(&corners);
+        let first = (corners[0] - center).to_vector();
+        let second = normal.cross(first);
+        let angle = |corner: &Coord| {
+            let offset = (*corner - center).to_vector();
+            f64::atan2(offset.dot(second), offset.dot(first))
+        };
+        corners.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+        Ok(Self { corners, normal })
+    }
+
+    /// Fills the face with points at most `node_dis` apart, as a fan of triangles around its
+    /// center
+    pub fn fill(&self, node_dis: f64, points: &mut Vec<Point>) {
+        let center = centroid(&self.corners);
+        for (i, a) in self.corners.iter().enumerate() {
+            let b = self.corners[(i + 1) % self.corners.len()];
+            let (to_a, to_b) = (*a - center, b - center);
+            let longest = [to_a, to_b, b - *a]
+                .iter()
+                .map(|side| side.to_vector().magnitude())
+                .fold(0.0, f64::max);
+            let steps = usize::max(f64::ceil(longest / node_dis) as usize, 1);
+            for j in 0..=steps {
+                for k in 0..=(steps - j) {
+                    let (j, k) = (j as f64 / steps as f64, k as f64 / steps as f64);
+                    let coord = center + to_a.mul(j) + to_b.mul(k);
+                    points.push(Point::new(coord, self.normal));
+                }
+            }
+        }
+    }
+}
+
+/// Points along the line between two corners, at most `node_dis` apart
+pub(crate) fn fill_edge(a: Coord, b: Coord, normal: Vector3D, node_dis: f64) -> Vec<Point> {
+    let steps = usize::max(f64::ceil((b - a).to_vector().magnitude() / node_dis) as usize, 1);
+    (0..=steps)
+        .map(|i| Point::new(a + (b - a).mul(i as f64 / steps as f64), normal))
+        .collect()
+}
+
+fn centroid(corners: &[Coord]) -> Coord {
+    let sum = corners.iter().fold(Coord::default(), |sum, corner| sum + *corner);
+    sum.mul(1.0 / corners.len() as f64)
+}
+
+/// Tetrahedron, cube, octahedron, dodecahedron or icosahedron with all of its corners at the
+/// given distance from its center. Points are placed at most `node_dis` apart, and like cubes
+/// either the faces, the edges or only the corners can be generated.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::{CubeMode, Platonic, PlatonicGenerator};
+/// use shapes_rs::Object;
+///
+/// let icosahedron = PlatonicGenerator::new(Platonic::Icosahedron, 20.0);
+/// let solid = Object::new(Coord::default(), &icosahedron, Rotator::identity());
+///
+/// // Twenty faces, every point facing away from the center
+/// let mut normals: Vec<_> = solid.shape.points.iter().map(|p| p.normal.get()).collect();
+/// normals.dedup();
+/// assert_eq!(normals.len(), 20);
+/// assert!(solid.shape.points.iter().all(|p| p.normal.dot(p.rel_coord.to_vector()) > 0.0));
+///
+/// let corners = PlatonicGenerator::new(Platonic::Dodecahedron, 20.0).with_mode(CubeMode::Corners);
+/// let corners = Object::new(Coord::default(), &corners, Rotator::identity());
+/// assert_eq!(corners.shape.points.len(), 20);
+/// ```
+pub struct PlatonicGenerator {
+    pub solid: Platonic,
+    /// Distance of the corners from the center
+    pub radius: f64,
+    pub node_dis: f64,
+    pub mode: CubeMode,
+}
+
+impl PlatonicGenerator {
+    pub const fn new(solid: Platonic, radius: f64) -> Self {
+        Self {
+            solid,
+            radius,
+            node_dis: 1.0,
+            mode: CubeMode::Faces,
+        }
+    }
+
+    /// Same as new, but fails if the radius is negative or not finite
+    pub fn try_new(solid: Platonic, radius: f64) -> Result<Self> {
+        let generator = Self::new(solid, radius);
+        generator.validate()?;
+        Ok(generator)
+    }
+
+    /// Sets the largest distance between two points, fails if it isn't positive and finite
+    pub fn try_with_node_dis(mut self, node_dis: f64) -> Result<Self> {
+        self.node_dis = node_dis;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Fails if the solid can't be generated with its current parameters
+    pub fn validate(&self) -> Result<()> {
+        if !self.radius.is_finite() || self.radius < 0.0 {
+            return Err(ShapesError::InvalidParameter(
+                "radius of a solid has to be finite and not negative",
+            ));
+        }
+        if !self.node_dis.is_finite() || self.node_dis <= 0.0 {
+            return Err(ShapesError::InvalidParameter(
+                "distance between points of a solid has to be finite and positive",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets which parts of the solid are generated, the wireframe look needs only the edges.
+    /// The distance function always describes the whole solid
+    pub fn with_mode(mut self, mode: CubeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Corners of the solid at the right distance from the center
+    fn corners(&self) -> Vec<Coord> {
+        self.solid
+            .vertices()
+            .into_iter()
+            .map(|vertex| vertex.to_vector().normalise().as_coord().mul(self.radius))
+            .collect()
+    }
+
+    /// Every face of the solid, made of the corners lying furthest along its direction
+    fn faces(&self) -> Result<Vec<Face>> {
+        let corners = self.corners();
+        self.solid
+            .face_directions()
+            .into_iter()
+            .map(|direction| {
+                let normal = direction.to_vector().normalise();
+                let furthest = corners
+                    .iter()
+                    .map(|corner| corner.to_vector().dot(normal))
+                    .fold(f64::MIN, f64::max);
+                let on_face = corners
+                    .iter()
+                    .filter(|corner| corner.to_vector().dot(normal) > furthest - 1e-6 * self.radius)
+                    .copied()
+                    .collect();
+                Face::new(on_face, normal)
+            })
+            .collect()
+    }
+
+    /// Distance from the center to the middle of a face
+    fn inradius(&self) -> f64 {
+        let normal = self.solid.face_directions()[0].to_vector().normalise();
+        self.corners()
+            .iter()
+            .map(|corner| corner.to_vector().dot(normal))
+            .fold(f64::MIN, f64::max)
+    }
+
+    /// Pairs of corners joined by an edge, which are the closest ones
+    fn edges(&self) -> Vec<(Coord, Coord)> {
+        let corners = self.corners();
+        let distance = |a: Coord, b: Coord| (b - a).to_vector().magnitude();
+        let shortest = corners[1..]
+            .iter()
+            .map(|corner| distance(corners[0], *corner))
+            .fold(f64::MAX, f64::min);
+        let mut edges = Vec::new();
+        for (i, a) in corners.iter().enumerate() {
+            for b in corners[i + 1..].iter() {
+                if distance(*a, *b) < shortest * (1.0 + 1e-6) {
+                    edges.push((*a, *b));
+                }
+            }
+        }
+        edges
+    }
+}
+
+impl ShapeGen for PlatonicGenerator {
+    fn bounds(&self) -> Option<Bounds> {
+        let corners = self.corners();
+        let first = Bounds::new(corners[0], corners[0]);
+        Some(corners.iter().fold(first, |bounds, corner| {
+            bounds.union(&Bounds::new(*corner, *corner))
+        }))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        // Nothing sensible can be generated, try_new catches this early
+        if self.validate().is_err() || self.radius == 0.0 {
+            return;
+        }
+        match self.mode {
+            CubeMode::Faces => {
+                for face in self.faces().unwrap_or_default() {
+                    face.fill(self.node_dis, &mut shape.points);
+                }
+            }
+            CubeMode::Edges => {
+                for (a, b) in self.edges() {
+                    // Pointing away from both faces the edge joins
+                    let normal = (a + b).to_vector().normalise();
+                    shape.points.extend(fill_edge(a, b, normal, self.node_dis));
+                }
+            }
+            CubeMode::Corners => {
+                for corner in self.corners() {
+                    shape.points.push(Point::new(corner, corner.to_vector().normalise()));
+                }
+            }
+        }
+    }
+}
+
+/// Same solid as the one generated by PlatonicGenerator. Exact inside of it, outside it never
+/// overestimates the distance, which is all raymarching needs
+/// ### Example:
+/// ```
+/// use shapes_rs::base::Coord;
+/// use shapes_rs::generators::{Platonic, PlatonicGenerator};
+/// use shapes_rs::sdf::Sdf;
+///
+/// let octahedron = PlatonicGenerator::new(Platonic::Octahedron, 30.0);
+///
+/// assert!((octahedron.distance(Coord::new(40.0, 0.0, 0.0)) - 10.0 / 3f64.sqrt()).abs() < 1e-9);
+/// assert!((octahedron.distance(Coord::default()) + 30.0 / 3f64.sqrt()).abs() < 1e-9);
+/// ```
+impl Sdf for PlatonicGenerator {
+    fn distance(&self, point: Coord) -> f64 {
+        let inradius = self.inradius();
+        self.solid
+            .face_directions()
+            .iter()
+            .map(|direction| point.to_vector().dot(direction.to_vector().normalise()) - inradius)
+            .fold(f64::MIN, f64::max)
+    }
+}
+
+/// Distance to the convex solid with the given faces, exact inside of it and never more than
+/// the real distance outside
+fn convex_distance(faces: &[Face], point: Coord) -> f64 {
+    faces
+        .iter()
+        .map(|face| (point - face.corners[0]).to_vector().dot(face.normal))
+        .fold(f64::MIN, f64::max)
+}
+
+/// Corners of a regular polygon with the given number of sides around the Z axis, at the
+/// given height
+fn polygon(sides: usize, radius: f64, z: f64) -> Vec<Coord> {
+    (0..sides)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / sides as f64;
+            Coord::new(radius * f64::cos(angle), radius * f64::sin(angle), z)
+        })
+        .collect()
+}
+
+/// Fails if a prism or pyramid with these parameters can't be generated
+fn validate_polygon_solid(sides: usize, radius: f64, height: f64, node_dis: f64) -> Result<()> {
+    if sides < 3 {
+        return Err(ShapesError::InvalidParameter("a polygon needs at least three sides"));
+    }
+    if [radius, height].iter().any(|length| !length.is_finite() || *length <= 0.0) {
+        return Err(ShapesError::InvalidParameter(
+            "radius and height of a solid have to be finite and positive",
+        ));
+    }
+    if !node_dis.is_finite() || node_dis <= 0.0 {
+        return Err(ShapesError::InvalidParameter(
+            "distance between points of a solid has to be finite and positive",
+        ));
+    }
+    Ok(())
+}
+
+/// Prism standing on a regular polygon, its axis along Z and centered on the object's
+/// location. The corners of the polygon are at the given distance from the axis, the first one
+/// along X. Points are placed at most `node_dis` apart.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::PrismGenerator;
+/// use shapes_rs::Object;
+///
+/// let hexagon = PrismGenerator::try_new(6, 10.0, 30.0).unwrap();
+/// let prism = Object::new(Coord::default(), &hexagon, Rotator::identity());
+///
+/// // Six sides facing out, plus the caps facing up and down
+/// let top = prism.shape.points.iter().filter(|p| p.normal.z == 1.0).count();
+/// assert!(top > 0 && prism.shape.points.iter().all(|p| p.rel_coord.z.abs() <= 15.0));
+/// assert!(prism.shape.points.iter().all(|p| p.normal.dot(p.rel_coord.to_vector()) > 0.0));
+///
+/// let tube = hexagon.with_caps(false);
+/// let tube = Object::new(Coord::default(), &tube, Rotator::identity());
+/// assert!(tube.shape.points.iter().all(|p| p.normal.z.abs() < 1e-9));
+/// assert!(PrismGenerator::try_new(2, 10.0, 30.0).is_err());
+/// ```
+pub struct PrismGenerator {
+    pub sides: usize,
+    /// Distance of the corners from the axis
+    pub radius: f64,
+    pub height: f64,
+    pub node_dis: f64,
+    /// Whether the top and bottom are closed
+    pub caps: bool,
+}
+
+impl PrismGenerator {
+    pub const fn new(sides: usize, radius: f64, height: f64) -> Self {
+        Self {
+            sides,
+            radius,
+            height,
+            node_dis: 1.0,
+            caps: true,
+        }
+    }
+
+    /// Same as new, but fails with fewer than three sides or lengths which aren't positive and
+    /// finite
+    pub fn try_new(sides: usize, radius: f64, height: f64) -> Result<Self> {
+        let generator = Self::new(sides, radius, height);
+        generator.validate()?;
+        Ok(generator)
+    }
+
+    /// Sets the largest distance between two points, fails if it isn't positive and finite
+    pub fn try_with_node_dis(mut self, node_dis: f64) -> Result<Self> {
+        self.node_dis = node_dis;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Fails if the prism can't be generated with its current parameters
+    pub fn validate(&self) -> Result<()> {
+        validate_polygon_solid(self.sides, self.radius, self.height, self.node_dis)
+    }
+
+    /// Leaves the top and bottom open, for tubes. The distance function always describes the
+    /// closed prism
+    pub fn with_caps(mut self, caps: bool) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    fn faces(&self) -> Result<Vec<Face>> {
+        let half = self.height / 2.0;
+        let bottom = polygon(self.sides, self.radius, -half);
+        let top = polygon(self.sides, self.radius, half);
+        let mut faces: Vec<Face> = (0..self.sides)
+            .map(|i| {
+                let j = (i + 1) % self.sides;
+                let normal = (bottom[i] + bottom[j]).to_vector();
+                let normal = Vector3D::new(normal.x, normal.y, 0.0).normalise();
+                Face::new(Vec::from([bottom[i], bottom[j], top[j], top[i]]), normal)
+            })
+            .collect::<Result<_>>()?;
+        faces.push(Face::new(top, Vector3D::new(0.0, 0.0, 1.0))?);
+        faces.push(Face::new(bottom, Vector3D::new(0.0, 0.0, -1.0))?);
+        Ok(faces)
+    }
+}
+
+impl ShapeGen for PrismGenerator {
+    fn bounds(&self) -> Option<Bounds> {
+        Some(Bounds::new(
+            Coord::new(-self.radius, -self.radius, -self.height / 2.0),
+            Coord::new(self.radius, self.radius, self.height / 2.0),
+        ))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        // Nothing sensible can be generated, try_new catches this early
+        let Ok(mut faces) = self.validate().and_then(|_| self.faces()) else {
+            return;
+        };
+        if !self.caps {
+            faces.truncate(self.sides);
+        }
+        for face in faces {
+            face.fill(self.node_dis, &mut shape.points);
+        }
+    }
+}
+
+/// Same prism as the one generated by PrismGenerator, always with its caps. Prisms which fail
+/// to validate are empty, every point infinitely far from them
+impl Sdf for PrismGenerator {
+    fn distance(&self, point: Coord) -> f64 {
+        match self.validate().and_then(|_| self.faces()) {
+            Ok(faces) => convex_distance(&faces, point),
+            Err(_) => f64::INFINITY,
+        }
+    }
+}
+
+/// Pyramid on a regular polygon, its base below the object's location and its tip above it
+/// along Z. The corners of the base are at the given distance from the axis, the first one
+/// along X. Points are placed at most `node_dis` apart.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::PyramidGenerator;
+/// use shapes_rs::sdf::Sdf;
+/// use shapes_rs::Object;
+///
+/// let square = PyramidGenerator::try_new(4, 10.0, 20.0).unwrap();
+/// let pyramid = Object::new(Coord::default(), &square, Rotator::identity());
+///
+/// let bounds = pyramid.shape.bounds.unwrap();
+/// assert_eq!((bounds.min.z, bounds.max.z), (-10.0, 10.0));
+/// // The sides lean back towards the tip
+/// let side = pyramid.shape.points.iter().find(|p| p.normal.x > 0.5).unwrap();
+/// assert!(side.normal.z > 0.0);
+/// assert!(square.distance(Coord::new(0.0, 0.0, 9.0)) < 0.0);
+/// assert!(square.distance(Coord::new(0.0, 0.0, 11.0)) > 0.0);
+/// // Without sides there is nothing to be inside of
+/// assert_eq!(PyramidGenerator::new(0, 10.0, 20.0).distance(Coord::default()), f64::INFINITY);
+/// ```
+pub struct PyramidGenerator {
+    pub sides: usize,
+    /// Distance of the corners of the base from the axis
+    pub radius: f64,
+    pub height: f64,
+    pub node_dis: f64,
+    /// Whether the base is closed
+    pub base: bool,
+}
+
+impl PyramidGenerator {
+    pub const fn new(sides: usize, radius: f64, height: f64) -> Self {
+        Self {
+            sides,
+            radius,
+            height,
+            node_dis: 1.0,
+            base: true,
+        }
+    }
+
+    /// Same as new, but fails with fewer than three sides or lengths which aren't positive and
+    /// finite
+    pub fn try_new(sides: usize, radius: f64, height: f64) -> Result<Self> {
+        let generator = Self::new(sides, radius, height);
+        generator.validate()?;
+        Ok(generator)
+    }
+
+    /// Sets the largest distance between two points, fails if it isn't positive and finite
+    pub fn try_with_node_dis(mut self, node_dis: f64) -> Result<Self> {
+        self.node_dis = node_dis;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Fails if the pyramid can't be generated with its current parameters
+    pub fn validate(&self) -> Result<()> {
+        validate_polygon_solid(self.sides, self.radius, self.height, self.node_dis)
+    }
+
+    /// Leaves the base open. The distance function always describes the closed pyramid
+    pub fn with_base(mut self, base: bool) -> Self {
+        self.base = base;
+        self
+    }
+
+    fn faces(&self) -> Result<Vec<Face>> {
+        let half = self.height / 2.0;
+        let base = polygon(self.sides, self.radius, -half);
+        let tip = Coord::new(0.0, 0.0, half);
+        let mut faces: Vec<Face> = (0..self.sides)
+            .map(|i| {
+                let (a, b) = (base[i], base[(i + 1) % self.sides]);
+                let mut normal = (b - a).to_vector().cross((tip - a).to_vector()).normalise();
+                // Whichever way around the corners are, the side has to face outwards
+                if normal.dot((a + b).to_vector()) < 0.0 {
+                    normal = normal.mul(-1.0);
+                }
+                Face::new(Vec::from([a, b, tip]), normal)
+            })
+            .collect::<Result<_>>()?;
+        faces.push(Face::new(base, Vector3D::new(0.0, 0.0, -1.0))?);
+        Ok(faces)
+    }
+}
+
+impl ShapeGen for PyramidGenerator {
+    fn bounds(&self) -> Option<Bounds> {
+        Some(Bounds::new(
+            Coord::new(-self.radius, -self.radius, -self.height / 2.0),
+            Coord::new(self.radius, self.radius, self.height / 2.0),
+        ))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        // Nothing sensible can be generated, try_new catches this early
+        let Ok(mut faces) = self.validate().and_then(|_| self.faces()) else {
+            return;
+        };
+        if !self.base {
+            faces.pop();
+        }
+        for face in faces {
+            face.fill(self.node_dis, &mut shape.points);
+        }
+    }
+}
+
+/// Same pyramid as the one generated by PyramidGenerator, always with its base. Pyramids which
+/// fail to validate are empty, every point infinitely far from them
+impl Sdf for PyramidGenerator {
+    fn distance(&self, point: Coord) -> f64 {
+        match self.validate().and_then(|_| self.faces()) {
+            Ok(faces) => convex_distance(&faces, point),
+            Err(_) => f64::INFINITY,
+        }
+    }
+}
@@ -1,6 +1,24 @@
-use crate::basetype::Angle3D;
+use core::ops::Mul;
+
+use crate::basetype::{Angle, Angle3D};
 use crate::basetype::Vector3D;
-use std::f64;
+use crate::error::{Result, ShapesError};
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Order in which the three angles of an Angle3D are applied, named after the axes they rotate
+/// around: roll around X, pitch around Y and yaw around Z
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum EulerOrder {
+    /// Roll, then pitch, then yaw. The order used by from_global
+    #[default]
+    Xyz,
+    Xzy,
+    Yxz,
+    Yzx,
+    Zxy,
+    Zyx,
+}
 
 /// ### Also known as a quaternion
 #[derive(Clone)]
@@ -12,19 +30,38 @@ pub struct Rotator {
 }
 
 impl Rotator {
-    pub fn new(axis: Vector3D, angle: f64) -> Self {        
+    /// Rotation around the given axis by the given angle in radians.
+    /// Panics if the axis has a length of zero, see try_new for a non panicking version
+    pub fn new(axis: Vector3D, angle: f64) -> Self {
+        match Self::try_new(axis, angle) {
+            Ok(rotator) => rotator,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    /// Rotation around the given axis by the given angle in radians, fails if the axis has a
+    /// length of zero
+    pub fn try_new(axis: Vector3D, angle: f64) -> Result<Self> {
         let norm = f64::sqrt(axis.x * axis.x + axis.y * axis.y + axis.z * axis.z);
         if norm == 0.0 {
-            panic!("Zero-length axis provided");
+            return Err(ShapesError::ZeroAxis);
         }
         let half_angle = angle / 2.0;
         let s = f64::sin(half_angle);
         let c = f64::cos(half_angle);
-        Rotator {
+        Ok(Rotator {
             x: (axis.x / norm) * s,
             y: (axis.y / norm) * s,
             z: (axis.z / norm) * s,
             w: c,
+        })
+    }
+    /// Rotation that does nothing
+    pub fn identity() -> Self {
+        Rotator {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
         }
     }
     pub fn conjugate(&self) -> Self {
@@ -35,11 +72,14 @@ impl Rotator {
             w: self.w,
         }
     }
+    /// Rotation made of roll around X, then pitch around Y and yaw around Z. A positive pitch
+    /// tilts X towards Z
     pub fn from_global(angle: Angle3D) -> Self {
         let cr = f64::cos(angle.roll.get() * 0.5);
         let sr = f64::sin(angle.roll.get() * 0.5);
         let cp = f64::cos(angle.pitch.get() * 0.5);
-        let sp = f64::sin(angle.pitch.get() * 0.5);
+        // Pitch is negated so that a positive pitch tilts X towards Z (up)
+        let sp = f64::sin(-angle.pitch.get() * 0.5);
         let cy = f64::cos(angle.yaw.get() * 0.5);
         let sy = f64::sin(angle.yaw.get() * 0.5);
         Rotator {
@@ -49,17 +89,90 @@ impl Rotator {
             w: cr * cp * cy + sr * sp * sy,  // w (scalar)
         }
     }
+    /// Inverse of from_global, returns the roll, pitch and yaw this rotation is made of.
+    /// Pitch stays between -90 and 90 degrees, at exactly those the roll and yaw can't be told
+    /// apart and the result is one of many equivalent angles.
+    pub fn to_angle3d(&self) -> Angle3D {
+        let q = self.normalize();
+        let roll = f64::atan2(
+            2.0 * (q.w * q.x + q.y * q.z),
+            1.0 - 2.0 * (q.x * q.x + q.y * q.y),
+        );
+        let sin_pitch = (2.0 * (q.w * q.y - q.z * q.x)).clamp(-1.0, 1.0);
+        let yaw = f64::atan2(
+            2.0 * (q.w * q.z + q.x * q.y),
+            1.0 - 2.0 * (q.y * q.y + q.z * q.z),
+        );
+        Angle3D::new(
+            Angle::from_radian(roll),
+            // Negated like in from_global
+            Angle::from_radian(-f64::asin(sin_pitch)),
+            Angle::from_radian(yaw),
+        )
+    }
+    /// Same as from_global, but the angles are applied in the given order instead of roll,
+    /// pitch and yaw
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    ///
+    /// let angle = Angle3D::new(Angle::from_degree(90.0), Angle::default(), Angle::from_degree(90.0));
+    /// let point = Vector3D::new(0.0, 1.0, 0.0);
+    ///
+    /// // Rolling first moves the point up, where the yaw can't reach it anymore
+    /// let rolled_first = Rotator::from_global_with_order(angle, EulerOrder::Xyz).apply(point);
+    /// assert!((rolled_first.z - 1.0).abs() < 1e-9);
+    ///
+    /// // Yawing first moves the point onto the roll axis
+    /// let yawed_first = Rotator::from_global_with_order(angle, EulerOrder::Zyx).apply(point);
+    /// assert!((yawed_first.x + 1.0).abs() < 1e-9);
+    /// ```
+    pub fn from_global_with_order(angle: Angle3D, order: EulerOrder) -> Self {
+        let roll = Rotator::new(Vector3D { x: 1.0, y: 0.0, z: 0.0 }, angle.roll.get());
+        // Negated for the same reason as in from_global
+        let pitch = Rotator::new(Vector3D { x: 0.0, y: 1.0, z: 0.0 }, -angle.pitch.get());
+        let yaw = Rotator::new(Vector3D { x: 0.0, y: 0.0, z: 1.0 }, angle.yaw.get());
+        // The rotation applied first goes last
+        let (first, second, third) = match order {
+            EulerOrder::Xyz => (roll, pitch, yaw),
+            EulerOrder::Xzy => (roll, yaw, pitch),
+            EulerOrder::Yxz => (pitch, roll, yaw),
+            EulerOrder::Yzx => (pitch, yaw, roll),
+            EulerOrder::Zxy => (yaw, roll, pitch),
+            EulerOrder::Zyx => (yaw, pitch, roll),
+        };
+        third * second * first
+    }
+    /// Panics if all components are zero, see try_normalize for a non panicking version
     pub fn normalize(&self) -> Self {
+        match self.try_normalize() {
+            Ok(rotator) => rotator,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    /// Scales the quaternion to a magnitude of one, fails if all components are zero
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::Rotator;
+    /// use shapes_rs::ShapesError;
+    ///
+    /// let zero = Rotator { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+    /// assert_eq!(zero.try_normalize().err(), Some(ShapesError::ZeroQuaternion));
+    ///
+    /// let scaled = Rotator { x: 0.0, y: 0.0, z: 0.0, w: 2.0 };
+    /// assert_eq!(scaled.try_normalize().unwrap().w, 1.0);
+    /// ```
+    pub fn try_normalize(&self) -> Result<Self> {
         let mag = f64::sqrt(self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z);
         if mag == 0.0 {
-            panic!("Attempted to normalize a zero-magnitude quaternion");
+            return Err(ShapesError::ZeroQuaternion);
         }
-        Rotator {
+        Ok(Rotator {
             x: self.x / mag,
             y: self.y / mag,
             z: self.z / mag,
             w: self.w / mag,
-        }
+        })
     }
     pub fn apply(&self, point: Vector3D) -> Vector3D {
         // Ensure the quaternion is normalized.
@@ -74,7 +187,12 @@ impl Rotator {
         // Rotate: p' = q * p * q_conjugate
         let q_conj = q_norm.conjugate();
         let res = q_norm.multiply(&p).multiply(&q_conj);
-        Vector3D::new(res.x, res.y, res.z)
+        // Rotating the zero vector is fine, so this can't go through Vector3D::new
+        Vector3D {
+            x: res.x,
+            y: res.y,
+            z: res.z,
+        }
     }
     pub fn multiply(&self, other: &Rotator) -> Self {
         Rotator {
@@ -84,6 +202,73 @@ impl Rotator {
             z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
         }
     }
+    /// Spherical interpolation between two rotations, t being between 0 and 1.
+    /// Always takes the shorter way around.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    ///
+    /// let quarter = Rotator::new(Vector3D::new(0.0, 0.0, 1.0), std::f64::consts::FRAC_PI_2);
+    /// let eighth = Rotator::identity().slerp(&quarter, 0.5);
+    ///
+    /// let rotated = eighth.apply(Vector3D::new(1.0, 0.0, 0.0));
+    /// assert!((rotated.x - rotated.y).abs() < 1e-9);
+    /// assert!((rotated.magnitude() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn slerp(&self, other: &Rotator, t: f64) -> Self {
+        let a = self.normalize();
+        let mut b = other.normalize();
+        let mut cos = a.w * b.w + a.x * b.x + a.y * b.y + a.z * b.z;
+        if cos < 0.0 {
+            cos = -cos;
+            b = Rotator {
+                x: -b.x,
+                y: -b.y,
+                z: -b.z,
+                w: -b.w,
+            };
+        }
+        // Nearly identical rotations, a linear blend is good enough and avoids dividing by zero
+        let (wa, wb) = if cos > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = f64::acos(cos);
+            let sin = f64::sin(theta);
+            (f64::sin((1.0 - t) * theta) / sin, f64::sin(t * theta) / sin)
+        };
+        Rotator {
+            x: a.x * wa + b.x * wb,
+            y: a.y * wa + b.y * wb,
+            z: a.z * wa + b.z * wb,
+            w: a.w * wa + b.w * wb,
+        }
+        .normalize()
+    }
+}
+
+/// Composes two rotations, the right one is applied first.
+/// Same as multiply.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+///
+/// let angle = Angle3D::new(Angle::from_degree(30.0), Angle::from_degree(20.0), Angle::from_degree(10.0));
+/// let composed = Rotator::from_global(angle) * Rotator::from_global(angle).conjugate();
+///
+/// assert!((composed.w - 1.0).abs() < 1e-9);
+/// ```
+impl Mul for Rotator {
+    type Output = Rotator;
+    fn mul(self, rhs: Rotator) -> Self::Output {
+        self.multiply(&rhs)
+    }
+}
+
+impl Mul<&Rotator> for &Rotator {
+    type Output = Rotator;
+    fn mul(self, rhs: &Rotator) -> Self::Output {
+        self.multiply(rhs)
+    }
 }
 
 impl Default for Rotator {
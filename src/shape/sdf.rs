@@ -0,0 +1,52 @@
+use crate::basetype::Coord;
+use crate::shape::shape_gen::{CubeGenerator, RingGenerator, TorusGenerator};
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Signed distance function, describes a shape by the distance of any point to its surface.
+/// Negative distances are inside of the shape. Points are given relative to the shape's center,
+/// the same way shape generators place their points.
+/// Has to be Sync so snapshots of scenes can be rendered on several threads.
+pub trait Sdf: Sync {
+    fn distance(&self, point: Coord) -> f64;
+}
+
+/// Same torus as the one generated by TorusGenerator, lying in the Y-Z plane
+/// ### Example:
+/// ```
+/// use shapes_rs::base::Coord;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::sdf::Sdf;
+///
+/// let torus = TorusGenerator::new(10.0, 50.0);
+///
+/// assert_eq!(torus.distance(Coord::new(0.0, 50.0, 0.0)), -10.0);
+/// assert_eq!(torus.distance(Coord::new(0.0, 0.0, 70.0)), 10.0);
+/// ```
+impl Sdf for TorusGenerator {
+    fn distance(&self, point: Coord) -> f64 {
+        let ring = f64::sqrt(point.y * point.y + point.z * point.z) - self.radius;
+        f64::sqrt(ring * ring + point.x * point.x) - self.thickness
+    }
+}
+
+/// Same box as the one generated by CubeGenerator
+impl Sdf for CubeGenerator {
+    fn distance(&self, point: Coord) -> f64 {
+        let qx = point.x.abs() - self.x / 2.0;
+        let qy = point.y.abs() - self.y / 2.0;
+        let qz = point.z.abs() - self.z / 2.0;
+        let outside = f64::sqrt(qx.max(0.0).powi(2) + qy.max(0.0).powi(2) + qz.max(0.0).powi(2));
+        let inside = qx.max(qy).max(qz).min(0.0);
+        outside + inside
+    }
+}
+
+/// Same flat ring as the one generated by RingGenerator, without any thickness
+impl Sdf for RingGenerator {
+    fn distance(&self, point: Coord) -> f64 {
+        let radius = f64::sqrt(point.y * point.y + point.z * point.z);
+        let off_ring = (self.inner_radius - radius).max(radius - self.outer_radius).max(0.0);
+        f64::sqrt(off_ring * off_ring + point.x * point.x)
+    }
+}
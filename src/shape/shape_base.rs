@@ -1,4 +1,12 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
 use crate::basetype::{Coord, Vector3D};
+use crate::component::Lerp;
+use crate::error::{Result, ShapesError};
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+use crate::out::color::Rgb;
+use crate::shape::modifier::ShapeModifier;
 use crate::shape::shape_gen::ShapeGen;
 
 use super::rotator::Rotator;
@@ -10,9 +18,98 @@ use super::rotator::Rotator;
 pub struct Shape<'a> {
     pub points: Vec<Point>,
     pub shape_generator: &'a dyn ShapeGen,
+    /// Every rotation applied to the points since they were generated, combined
+    pub orientation: Rotator,
+    /// Box around every point, if the generator could tell. Follows rotations, but may grow
+    /// larger than the points once rotated
+    pub bounds: Option<Bounds>,
     generated: bool,
 }
 
+/// Summary of the points of a shape, see Shape::stats
+#[derive(Copy, Clone)]
+pub struct ShapeStats {
+    pub points: usize,
+    /// Box around every point with a finite position, None if there is none
+    pub bounds: Option<Bounds>,
+    /// Average distance from every point to the closest other one, points sharing their
+    /// position are left out as generators often repeat the points along edges
+    pub average_spacing: f64,
+    /// Number of points whose normal has a length of zero or isn't finite
+    pub degenerate_normals: usize,
+}
+
+/// Axis aligned box given by its lowest and highest corner
+#[derive(Copy, Clone)]
+pub struct Bounds {
+    pub min: Coord,
+    pub max: Coord,
+}
+
+impl Bounds {
+    pub fn new(min: Coord, max: Coord) -> Self {
+        Self { min, max }
+    }
+
+    /// Every corner of the box
+    pub fn corners(&self) -> [Coord; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            Coord::new(min.x, min.y, min.z),
+            Coord::new(max.x, min.y, min.z),
+            Coord::new(min.x, max.y, min.z),
+            Coord::new(max.x, max.y, min.z),
+            Coord::new(min.x, min.y, max.z),
+            Coord::new(max.x, min.y, max.z),
+            Coord::new(min.x, max.y, max.z),
+            Coord::new(max.x, max.y, max.z),
+        ]
+    }
+
+    /// Whether the point is inside of the box or on its surface
+    pub fn contains(&self, point: Coord) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// Smallest box containing both boxes
+    pub fn union(&self, other: &Bounds) -> Self {
+        Self {
+            min: Coord::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Coord::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    /// Same box moved by the given offset
+    pub fn translated(&self, offset: Coord) -> Self {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// Smallest axis aligned box around the rotated corners of this one
+    pub fn rotated(&self, rotator: &Rotator) -> Self {
+        let mut min = Coord::new(f64::MAX, f64::MAX, f64::MAX);
+        let mut max = Coord::new(f64::MIN, f64::MIN, f64::MIN);
+        for corner in self.corners() {
+            let c = rotator.apply(corner.to_vector()).as_coord();
+            min = Coord::new(min.x.min(c.x), min.y.min(c.y), min.z.min(c.z));
+            max = Coord::new(max.x.max(c.x), max.y.max(c.y), max.z.max(c.z));
+        }
+        Self { min, max }
+    }
+}
+
 impl<'a> Shape<'a> {
     /// Default value for function argument is a function that always returns a null
     /// The points_vec is just an empty vector unless given an argument (probably not needed?)
@@ -22,6 +119,8 @@ impl<'a> Shape<'a> {
         let mut ret = Self {
             points,
             shape_generator,
+            orientation: Rotator::identity(),
+            bounds: None,
             generated: false,
         };
         ret.generate();
@@ -30,9 +129,12 @@ impl<'a> Shape<'a> {
 
     /// Rotate the shape around itself
     pub fn rotate(&self, rotator: &Rotator) -> Self {
+        phase_span!("transform", points = self.points.len());
         let mut new_shape = Shape {
             points: Vec::new(),
             shape_generator: self.shape_generator,
+            orientation: rotator.multiply(&self.orientation),
+            bounds: self.bounds.map(|bounds| bounds.rotated(rotator)),
             generated: true,
         };
         for point in self.points.iter() {
@@ -43,13 +145,21 @@ impl<'a> Shape<'a> {
     }
 
     pub fn rotate_self(&mut self, rotator: &Rotator) {
+        phase_span!("transform", points = self.points.len());
         for point in self.points.iter_mut() {
             point.rotate_self(rotator)
         }
+        self.orientation = rotator.multiply(&self.orientation);
+        self.bounds = self.bounds.map(|bounds| bounds.rotated(rotator));
     }
 
     fn generate_if_not(&mut self) {
         if !self.generated {
+            phase_span!("generate");
+            if let Some(count) = self.shape_generator.point_count_hint() {
+                self.points.reserve(count);
+            }
+            self.bounds = self.shape_generator.bounds();
             self.shape_generator.generate_shape(self);
             self.generated = true;
         }
@@ -62,23 +172,550 @@ impl<'a> Shape<'a> {
     pub fn generated(&self) -> bool {
         return self.generated;
     }
+
+    /// Changes the points with the given modifier
+    pub fn modify(&mut self, modifier: &dyn ShapeModifier) {
+        modifier.modify(self);
+    }
+
+    /// Shape without any points, to assemble compound shapes in
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let cube = Object::new(Coord::default(), &cubegen, Rotator::identity()).shape;
+    ///
+    /// // Two cubes stacked on top of each other
+    /// let mut tower = Shape::empty();
+    /// tower.merge(&cube.translate(Coord::new(0.0, 0.0, -5.0)));
+    /// tower.merge(&cube.translate(Coord::new(0.0, 0.0, 5.0)));
+    ///
+    /// assert_eq!(tower.points.len(), cube.points.len() * 2);
+    /// assert_eq!(tower.bounds.unwrap().max.z, 10.0);
+    /// ```
+    pub fn empty() -> Self {
+        Self {
+            points: Vec::new(),
+            shape_generator: &NoShape,
+            orientation: Rotator::identity(),
+            bounds: None,
+            generated: true,
+        }
+    }
+
+    /// Adds every point of the other shape to this one. The orientation stays the one of this
+    /// shape
+    pub fn merge(&mut self, other: &Shape) {
+        self.bounds = match (self.points.is_empty(), self.bounds, other.bounds) {
+            (true, _, bounds) => bounds,
+            (false, Some(own), Some(other)) => Some(own.union(&other)),
+            _ => None,
+        };
+        self.points.extend_from_slice(&other.points);
+    }
+
+    /// Adds the given points, growing the bounds to fit them
+    pub fn extend_from_points(&mut self, points: &[Point]) {
+        if self.points.is_empty() && self.bounds.is_none() {
+            if let Some(first) = points.first() {
+                self.bounds = Some(Bounds::new(first.rel_coord, first.rel_coord));
+            }
+        }
+        if let Some(bounds) = self.bounds.as_mut() {
+            for point in points {
+                *bounds = bounds.union(&Bounds::new(point.rel_coord, point.rel_coord));
+            }
+        }
+        self.points.extend_from_slice(points);
+    }
+
+    /// Copy of the shape with every point moved by the given offset, for placing shapes before
+    /// merging them
+    pub fn translate(&self, offset: Coord) -> Self {
+        let mut new_shape = self.clone();
+        new_shape.translate_self(offset);
+        new_shape
+    }
+
+    pub fn translate_self(&mut self, offset: Coord) {
+        for point in self.points.iter_mut() {
+            point.rel_coord = point.rel_coord + offset;
+        }
+        self.bounds = self.bounds.map(|bounds| bounds.translated(offset));
+    }
+
+    /// Turns every normal around, so the inside of a hollow shape is lit instead of the outside.
+    /// For looking at a box or a torus from within, like a room or a tunnel
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let mut room = Object::new(Coord::default(), &cubegen, Rotator::identity()).shape;
+    /// room.flip_normals();
+    ///
+    /// // Every normal points back towards the center
+    /// assert!(room.points.iter().all(|p| p.normal.dot(p.rel_coord.to_vector()) < 0.0));
+    /// ```
+    pub fn flip_normals(&mut self) {
+        for point in self.points.iter_mut() {
+            point.normal = point.normal.mul(-1.0);
+        }
+    }
+
+    /// Blends this shape into the other one, this shape at 0 and the other at 1. Points are
+    /// paired up by their index, so shapes made by the same generator with other sizes blend
+    /// evenly. If one shape has fewer points, its points are repeated evenly until both have
+    /// as many, bring both to the same count with resample first for a closer match. Values
+    /// of t outside of 0 to 1 carry on past either shape, for easings which overshoot, and
+    /// leave the bounds unknown. The result keeps the generator and orientation of this shape
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::{CubeGenerator, TorusGenerator};
+    /// use shapes_rs::Object;
+    ///
+    /// let small = CubeGenerator::new(10.0, 10.0, 10.0);
+    /// let large = CubeGenerator::new(20.0, 20.0, 20.0).try_with_node_dis(2.0).unwrap();
+    /// let small = Object::new(Coord::default(), &small, Rotator::identity()).shape;
+    /// let large = Object::new(Coord::default(), &large, Rotator::identity()).shape;
+    ///
+    /// let halfway = small.morph(&large, 0.5);
+    /// assert_eq!(halfway.points.len(), small.points.len());
+    /// assert_eq!(halfway.bounds.unwrap().max.x, 10.0);
+    /// assert!(halfway.points.iter().all(|p| p.rel_coord.x.abs() <= 7.5 + 1e-9));
+    /// assert!(halfway.points.iter().any(|p| p.rel_coord.x == 7.5));
+    ///
+    /// // A torus has more points, some of the cube's are used twice
+    /// let torusgen = TorusGenerator::new(5.0, 20.0);
+    /// let torus = Object::new(Coord::default(), &torusgen, Rotator::identity()).shape;
+    /// let done = small.morph(&torus, 1.0);
+    /// assert_eq!(done.points.len(), torus.points.len());
+    /// let arrived = |(a, b): (&Point, &Point)| (a.rel_coord - b.rel_coord).to_vector().magnitude() < 1e-9;
+    /// assert!(done.points.iter().zip(torus.points.iter()).all(arrived));
+    ///
+    /// // Overshooting past the large cube, as an elastic easing does
+    /// let past = small.morph(&large, 1.5);
+    /// assert!(past.bounds.is_none());
+    /// assert!(past.points.iter().any(|p| p.rel_coord.x == 12.5));
+    /// ```
+    pub fn morph(&self, other: &Shape, t: f64) -> Self {
+        let count = usize::max(self.points.len(), other.points.len());
+        let from = stretched(&self.points, count);
+        let to = stretched(&other.points, count);
+        Shape {
+            points: from.zip(to).map(|(a, b)| a.lerp(b, t)).collect(),
+            shape_generator: self.shape_generator,
+            orientation: self.orientation.clone(),
+            // Every blended point lies between two points inside of the union, unless t
+            // overshoots
+            bounds: match (self.bounds, other.bounds) {
+                (Some(own), Some(other)) if (0.0..=1.0).contains(&t) => Some(own.union(&other)),
+                _ => None,
+            },
+            generated: true,
+        }
+    }
+
+    /// Brings the shape to about the given number of points, spread over its surface as evenly
+    /// as before. Points are sorted into cubes, sized so about that many of them hold points,
+    /// and the point closest to the middle of the points in each cube is kept as it is. Points
+    /// in a cube facing clearly different ways are kept apart, so sharp edges stay. Asking
+    /// for more points than there are repeats points evenly instead, which adds no detail but
+    /// lets shapes be morphed into ones with more points
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0).try_with_node_dis(0.2).unwrap();
+    /// let mut cube = Object::new(Coord::default(), &cubegen, Rotator::identity()).shape;
+    /// assert!(cube.points.len() > 15000);
+    ///
+    /// cube.resample(600);
+    /// assert!((500..=700).contains(&cube.points.len()));
+    /// // Every face is still covered from edge to edge
+    /// for normal in [Vector3D::new(0.0, 0.0, 1.0), Vector3D::new(-1.0, 0.0, 0.0)] {
+    ///     let face: Vec<&Point> = cube.points.iter().filter(|p| p.normal.dot(normal) == 1.0).collect();
+    ///     assert!(face.len() > 60);
+    ///     assert!(face.iter().any(|p| p.rel_coord.y > 4.0) && face.iter().any(|p| p.rel_coord.y < -4.0));
+    /// }
+    ///
+    /// cube.resample(1000);
+    /// assert_eq!(cube.points.len(), 1000);
+    /// ```
+    pub fn resample(&mut self, count: usize) {
+        if count >= self.points.len() {
+            self.points = stretched(&self.points, count).copied().collect();
+            return;
+        }
+        let finite = self.points.iter().map(|p| p.rel_coord).filter(|c| coord_finite(*c));
+        let Some(bounds) = finite.map(|c| Bounds::new(c, c)).reduce(|a, b| a.union(&b)) else {
+            self.points.clear();
+            return;
+        };
+        let extent = (bounds.max - bounds.min).get();
+        let extent = f64::max(extent.0, f64::max(extent.1, extent.2));
+        // Larger cubes hold points more rarely, search for the size giving the closest count
+        let (mut small, mut large) = (extent * 1e-6, extent.max(f64::MIN_POSITIVE) * 2.0);
+        let mut best = (usize::MAX, large);
+        for _ in 0..40 {
+            let size = f64::sqrt(small * large);
+            let filled = self.cells(bounds.min, size).len();
+            if filled.abs_diff(count) < best.0 {
+                best = (filled.abs_diff(count), size);
+            }
+            match filled > count {
+                true => small = size,
+                false => large = size,
+            }
+        }
+
+        let mut kept: Vec<usize> = self
+            .cells(bounds.min, best.1)
+            .into_values()
+            .map(|indices| {
+                let sum = indices
+                    .iter()
+                    .fold(Coord::default(), |sum, index| sum + self.points[*index].rel_coord);
+                let middle = sum.mul(1.0 / indices.len() as f64);
+                let distance = |index: &usize| {
+                    (self.points[*index].rel_coord - middle).to_vector().magnitude()
+                };
+                indices
+                    .into_iter()
+                    .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+                    .unwrap_or(0)
+            })
+            .collect();
+        // Keeps the order the generator made them in
+        kept.sort_unstable();
+        self.points = kept.into_iter().map(|index| self.points[index]).collect();
+    }
+
+    /// Indices of the finite points in every cube of the given size holding any, the cubes
+    /// lined up with the given corner. The points of a cube are split up further by the axis
+    /// their normal is closest to
+    fn cells(&self, corner: Coord, cell_size: f64) -> BTreeMap<(i64, i64, i64, u8), Vec<usize>> {
+        let cell = |value: f64| f64::floor(value / cell_size) as i64;
+        let mut cells: BTreeMap<(i64, i64, i64, u8), Vec<usize>> = BTreeMap::new();
+        for (index, point) in self.points.iter().enumerate() {
+            if !coord_finite(point.rel_coord) {
+                continue;
+            }
+            let (x, y, z) = (point.rel_coord - corner).get();
+            let facing = facing_axis(point.normal);
+            cells.entry((cell(x), cell(y), cell(z), facing)).or_default().push(index);
+        }
+        cells
+    }
+
+    /// Thins the points out to at most one per cube of the given size, for dense scans or
+    /// shapes merged from many generators. The points falling into the same cube are replaced
+    /// by one at their average position with their average normal, everything else is taken
+    /// from the first of them. Sizes which aren't positive and finite leave the shape as it is
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0).try_with_node_dis(0.1).unwrap();
+    /// let mut cube = Object::new(Coord::default(), &cubegen, Rotator::identity()).shape;
+    /// let dense = cube.points.len();
+    ///
+    /// cube.voxel_downsample(1.0);
+    /// assert!(cube.points.len() * 50 < dense);
+    /// // Away from the edges, the points of a face are only averaged with each other
+    /// let top = cube.points.iter().filter(|p| p.normal.z == 1.0).count();
+    /// assert_eq!(top, 9 * 9);
+    /// assert!(cube.points.iter().all(|p| (p.normal.magnitude() - 1.0).abs() < 1e-9));
+    /// ```
+    pub fn voxel_downsample(&mut self, cell_size: f64) {
+        if !cell_size.is_finite() || cell_size <= 0.0 {
+            return;
+        }
+        let cell = |value: f64| f64::floor(value / cell_size) as i64;
+        // Index of the kept point of every cube, with the sums of the positions and normals of
+        // the points in it
+        let mut cells: BTreeMap<(i64, i64, i64), usize> = BTreeMap::new();
+        let mut kept: Vec<(Point, Coord, Vector3D, usize)> = Vec::new();
+        for point in self.points.iter() {
+            let (x, y, z) = point.rel_coord.get();
+            let index = *cells.entry((cell(x), cell(y), cell(z))).or_insert_with(|| {
+                let zero = Vector3D { x: 0.0, y: 0.0, z: 0.0 };
+                kept.push((*point, Coord::default(), zero, 0));
+                kept.len() - 1
+            });
+            let (_, coords, normals, count) = &mut kept[index];
+            *coords = *coords + point.rel_coord;
+            *normals = *normals + point.normal;
+            *count += 1;
+        }
+        self.points = kept
+            .into_iter()
+            .map(|(mut point, coords, normals, count)| {
+                point.rel_coord = coords.mul(1.0 / count as f64);
+                // Opposite normals cancel out, the first one is as good as any then
+                if normals.magnitude() > 1e-9 {
+                    point.normal = normals.normalise();
+                }
+                point
+            })
+            .collect();
+    }
+
+    /// Counts the points and measures how they are spread out, for checking the output of a
+    /// generator. Points with a position which isn't finite are only counted
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0).try_with_node_dis(0.5).unwrap();
+    /// let cube = Object::new(Coord::default(), &cubegen, Rotator::identity()).shape;
+    ///
+    /// let stats = cube.stats();
+    /// assert_eq!(stats.points, cube.points.len());
+    /// assert_eq!(stats.bounds.map(|b| (b.min.x, b.max.z)), Some((-5.0, 5.0)));
+    /// assert!((stats.average_spacing - 0.5).abs() < 1e-9);
+    /// assert_eq!(stats.degenerate_normals, 0);
+    /// ```
+    pub fn stats(&self) -> ShapeStats {
+        let coords: Vec<Coord> = self
+            .points
+            .iter()
+            .map(|point| point.rel_coord)
+            .filter(|coord| coord_finite(*coord))
+            .collect();
+        let bounds = coords.first().map(|first| {
+            coords.iter().fold(Bounds::new(*first, *first), |bounds, coord| {
+                bounds.union(&Bounds::new(*coord, *coord))
+            })
+        });
+        let degenerate_normals = self
+            .points
+            .iter()
+            .filter(|point| normal_problem(point.normal).is_some())
+            .count();
+        ShapeStats {
+            points: self.points.len(),
+            bounds,
+            average_spacing: bounds.map_or(0.0, |bounds| average_spacing(&coords, bounds)),
+            degenerate_normals,
+        }
+    }
+
+    /// Fails on the first point which can't be rendered, either because its position or normal
+    /// isn't finite or its normal has a length of zero. The error tells which point it is
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::selfmade::ShapeGen;
+    /// use shapes_rs::ShapesError;
+    ///
+    /// // A generator forgetting to give its last point a normal
+    /// struct Broken;
+    /// impl ShapeGen for Broken {
+    ///     fn generate_shape(&self, shape: &mut Shape) {
+    ///         let up = Vector3D::new(0.0, 0.0, 1.0);
+    ///         shape.points.push(Point::new(Coord::default(), up));
+    ///         let zero = Vector3D { x: 0.0, y: 0.0, z: 0.0 };
+    ///         shape.points.push(Point::new(Coord::new(1.0, 0.0, 0.0), zero));
+    ///     }
+    /// }
+    ///
+    /// let mut shape = Shape::new(&Broken);
+    /// shape.generate();
+    /// assert_eq!(shape.stats().degenerate_normals, 1);
+    /// assert!(matches!(shape.validate(), Err(ShapesError::InvalidPoint { index: 1, .. })));
+    ///
+    /// shape.points.pop();
+    /// assert!(shape.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        for (index, point) in self.points.iter().enumerate() {
+            if !coord_finite(point.rel_coord) {
+                return Err(ShapesError::InvalidPoint {
+                    index,
+                    reason: "position isn't finite",
+                });
+            }
+            if let Some(reason) = normal_problem(point.normal) {
+                return Err(ShapesError::InvalidPoint { index, reason });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The points with some of them repeated so there are as many as given, spread evenly. Empty
+/// when there are no points
+fn stretched(points: &[Point], count: usize) -> impl Iterator<Item = &Point> + '_ {
+    let len = points.len();
+    let count = if len == 0 { 0 } else { count };
+    (0..count).map(move |index| &points[index * len / count])
+}
+
+/// Which of the six directions along the axes the normal is closest to
+fn facing_axis(normal: Vector3D) -> u8 {
+    let (x, y, z) = (normal.x.abs(), normal.y.abs(), normal.z.abs());
+    let (axis, value) = match (x >= y && x >= z, y >= z) {
+        (true, _) => (0, normal.x),
+        (false, true) => (2, normal.y),
+        (false, false) => (4, normal.z),
+    };
+    axis + u8::from(value < 0.0)
+}
+
+fn coord_finite(coord: Coord) -> bool {
+    coord.x.is_finite() && coord.y.is_finite() && coord.z.is_finite()
+}
+
+/// Why the normal can't be used for shading, None if it can
+fn normal_problem(normal: Vector3D) -> Option<&'static str> {
+    if !(normal.x.is_finite() && normal.y.is_finite() && normal.z.is_finite()) {
+        return Some("normal isn't finite");
+    }
+    if normal.magnitude() < 1e-9 {
+        return Some("normal has a length of zero");
+    }
+    None
+}
+
+/// Average distance from every coordinate to the closest other one at a different position.
+/// The coordinates are sorted into a k-d tree first, so stray points far from the rest are
+/// found as quickly as any other
+fn average_spacing(coords: &[Coord], bounds: Bounds) -> f64 {
+    let extent = (bounds.max - bounds.min).get();
+    if f64::max(extent.0, f64::max(extent.1, extent.2)) == 0.0 {
+        return 0.0;
+    }
+    let mut tree: Vec<usize> = (0..coords.len()).collect();
+    build_tree(coords, &mut tree, 0);
+
+    let mut total = 0.0;
+    let mut counted = 0;
+    for coord in coords {
+        let mut closest = f64::INFINITY;
+        closest_in_tree(coords, &tree, 0, *coord, &mut closest);
+        if closest.is_finite() {
+            total += closest;
+            counted += 1;
+        }
+    }
+    match counted {
+        0 => 0.0,
+        _ => total / counted as f64,
+    }
+}
+
+/// Sorts the indices into a k-d tree in place. The middle of every range splits the rest of it
+/// along one axis, the axes taking turns from one level to the next
+fn build_tree(coords: &[Coord], indices: &mut [usize], depth: usize) {
+    if indices.len() <= 1 {
+        return;
+    }
+    let middle = indices.len() / 2;
+    indices.select_nth_unstable_by(middle, |a, b| {
+        along(coords[*a], depth).total_cmp(&along(coords[*b], depth))
+    });
+    let (lower, upper) = indices.split_at_mut(middle);
+    build_tree(coords, lower, depth + 1);
+    build_tree(coords, &mut upper[1..], depth + 1);
 }
 
+/// Lowers closest to the distance from the coordinate to the closest one in the tree at a
+/// different position, if that is closer
+fn closest_in_tree(
+    coords: &[Coord],
+    tree: &[usize],
+    depth: usize,
+    coord: Coord,
+    closest: &mut f64,
+) {
+    if tree.is_empty() {
+        return;
+    }
+    let middle = tree.len() / 2;
+    let split = coords[tree[middle]];
+    let distance = (split - coord).to_vector().magnitude();
+    if distance > 0.0 && distance < *closest {
+        *closest = distance;
+    }
+    let offset = along(coord, depth) - along(split, depth);
+    let (near, far) = match offset < 0.0 {
+        true => (&tree[..middle], &tree[middle + 1..]),
+        false => (&tree[middle + 1..], &tree[..middle]),
+    };
+    closest_in_tree(coords, near, depth + 1, coord, closest);
+    // The other side can only hold something closer if the split is close enough
+    if offset.abs() < *closest {
+        closest_in_tree(coords, far, depth + 1, coord, closest);
+    }
+}
+
+/// Position of the coordinate along the axis a level of the tree splits on
+fn along(coord: Coord, depth: usize) -> f64 {
+    match depth % 3 {
+        0 => coord.x,
+        1 => coord.y,
+        _ => coord.z,
+    }
+}
+
+/// Generator of empty shapes
+struct NoShape;
+
+impl ShapeGen for NoShape {
+    fn generate_shape(&self, _shape: &mut Shape) {}
+}
+
+/// Single point of a shape. More attributes may be added, so build points with Point::new
 #[derive(Copy, Clone)]
+#[non_exhaustive]
 pub struct Point {
     pub rel_coord: Coord,
     pub normal: Vector3D,
+    /// Color of this point for color renderers, takes precedence over the material
+    pub color: Option<Rgb>,
+    /// Added to the luminance of the point when it is shaded
+    pub luminance_bias: f64,
+    /// Where the point lies on the surface, used to sample textures. Set by generators that
+    /// know how to unwrap their surface
+    pub uv: Option<(f64, f64)>,
+    /// How much of the light around the point is blocked by the rest of the shape, from 0 to 1.
+    /// Its luminance is scaled down by as much, see AmbientOcclusion
+    pub occlusion: f64,
 }
 
 impl Point {
     pub fn new(rel_coord: Coord, normal: Vector3D) -> Self {
-        return Self { rel_coord, normal };
+        return Self {
+            rel_coord,
+            normal,
+            color: None,
+            luminance_bias: 0.0,
+            uv: None,
+            occlusion: 0.0,
+        };
+    }
+    /// Sets the texture coordinate of the point
+    pub fn with_uv(mut self, u: f64, v: f64) -> Self {
+        self.uv = Some((u, v));
+        self
     }
     pub fn rotate(&self, rotator: &Rotator) -> Self {
-        Point::new(
-            rotator.apply(self.rel_coord.to_vector()).as_coord(),
-            rotator.apply(self.normal),
-        )
+        let mut point = *self;
+        point.rotate_self(rotator);
+        point
     }
     pub fn rotate_self(&mut self, rotator: &Rotator) {
         self.rel_coord = rotator.apply(self.rel_coord.to_vector()).as_coord();
@@ -25,14 +25,40 @@
  * }
  */
 
-use crate::basetype::{Coord, Vector3D};
+use alloc::vec::Vec;
+
+use crate::basetype::{Angle, Coord, Vector3D};
+use crate::error::{Result, ShapesError};
+use crate::math::projection::char_luminance;
+use crate::math::random::Rng;
+use crate::out::color::Rgb;
 use crate::shape::shape_base::Point;
-use std::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+use core::f64::consts::PI;
 
-use super::shape_base::Shape;
+use super::shape_base::{Bounds, Shape};
 
 pub trait ShapeGen {
     fn generate_shape(&self, shape: &mut Shape);
+
+    /// Roughly how many points generate_shape will add, used to allocate them up front
+    fn point_count_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Box around every point the generator creates, relative to the shape's center
+    fn bounds(&self) -> Option<Bounds> {
+        None
+    }
+}
+
+/// How many times `while angle < end { angle += step }` runs
+fn sweep_steps((start, end): (f64, f64), step: f64) -> usize {
+    if end <= start || step <= 0.0 {
+        return 0;
+    }
+    f64::ceil((end - start) / step) as usize
 }
 
 /// Basic binding class that constitutes a scene
@@ -85,27 +111,108 @@ pub struct TorusGenerator {
     pub thickness: f64,
     pub radius: f64,
     pub angle_iter: (f64, f64),
+    /// Start and end of the major circle in radians, the whole ring by default
+    pub major_sweep: (f64, f64),
+    /// Start and end of the minor circle in radians, the whole tube by default
+    pub minor_sweep: (f64, f64),
+    /// Turns the generated part of the ring around its axis, in radians
+    pub phase: f64,
+    /// Normals point into the tube instead of out of it, for flying through it
+    pub inward_normals: bool,
 }
 
 impl TorusGenerator {
-    pub fn new(thickness: f64, size: f64) -> Self {
+    pub const fn new(thickness: f64, size: f64) -> Self {
         TorusGenerator {
             thickness,
             radius: size,
             angle_iter: (0.04, 0.04),
+            major_sweep: (0.0, 2.0 * PI),
+            minor_sweep: (0.0, 2.0 * PI),
+            phase: 0.0,
+            inward_normals: false,
         }
     }
+
+    /// Only generates the ring between the given angles, for arcs and C-shapes.
+    /// The distance function still describes the whole torus.
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let full = TorusGenerator::new(10.0, 50.0);
+    /// let half = TorusGenerator::new(10.0, 50.0)
+    ///     .with_major_sweep(Angle::from_degree(0.0), Angle::from_degree(180.0));
+    ///
+    /// let full = Object::new(Coord::default(), &full, Rotator::identity());
+    /// let half = Object::new(Coord::default(), &half, Rotator::identity());
+    /// assert!(half.shape.points.len() * 2 <= full.shape.points.len() + 200);
+    /// // The ring lies in the Y-Z plane, the first half stays above it
+    /// assert!(half.shape.points.iter().all(|p| p.rel_coord.z >= -1e-9));
+    /// ```
+    pub fn with_major_sweep(mut self, start: Angle, end: Angle) -> Self {
+        self.major_sweep = (start.get(), end.get());
+        self
+    }
+
+    /// Only generates the tube between the given angles, for half pipes and gutters
+    pub fn with_minor_sweep(mut self, start: Angle, end: Angle) -> Self {
+        self.minor_sweep = (start.get(), end.get());
+        self
+    }
+
+    /// Turns the generated part of the ring around the axis of the torus
+    pub fn with_phase(mut self, phase: Angle) -> Self {
+        self.phase = phase.get();
+        self
+    }
+
+    /// Lights the inside of the tube instead of the outside, see Shape::flip_normals
+    pub fn with_inward_normals(mut self, inward: bool) -> Self {
+        self.inward_normals = inward;
+        self
+    }
 }
 
 impl ShapeGen for TorusGenerator {
+    fn point_count_hint(&self) -> Option<usize> {
+        Some(
+            sweep_steps(self.major_sweep, self.angle_iter.0)
+                * sweep_steps(self.minor_sweep, self.angle_iter.1),
+        )
+    }
+
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::TorusGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let torusgen = TorusGenerator::new(10.0, 50.0);
+    /// let torus = Object::new(Coord::default(), &torusgen, Rotator::identity());
+    ///
+    /// let bounds = torus.shape.bounds.unwrap();
+    /// assert_eq!(bounds.max.y, 60.0);
+    /// assert!(torus.shape.points.iter().all(|p| bounds.contains(p.rel_coord)));
+    /// ```
+    fn bounds(&self) -> Option<Bounds> {
+        let outer = self.radius + self.thickness;
+        Some(Bounds::new(
+            Coord::new(-self.thickness, -outer, -outer),
+            Coord::new(self.thickness, outer, outer),
+        ))
+    }
+
     fn generate_shape(&self, shape: &mut Shape) {
-        let mut a = 0.0;
-        while a < 2.0 * PI {
+        let mut a = self.major_sweep.0;
+        while a < self.major_sweep.1 {
             // Major circle
-            let sin_a = f64::sin(a);
-            let cos_a = f64::cos(a);
-            let mut b = 0.0;
-            while b < 2.0 * PI {
+            let sin_a = f64::sin(a + self.phase);
+            let cos_a = f64::cos(a + self.phase);
+            let mut b = self.minor_sweep.0;
+            while b < self.minor_sweep.1 {
                 // Minor circle
                 let sin_b = f64::sin(b);
                 let cos_b = f64::cos(b);
@@ -118,9 +225,14 @@ impl ShapeGen for TorusGenerator {
                 let normal_x: f64 = cos_b;
                 let normal_y: f64 = sin_b * cos_a;
                 let normal_z: f64 = sin_b * sin_a;
-                let normal = Vector3D::new(normal_x, normal_y, normal_z);
+                let mut normal = Vector3D::new(normal_x, normal_y, normal_z);
+                if self.inward_normals {
+                    normal = normal.mul(-1.0);
+                }
 
-                let new_point: Point = Point::new(point_coord, normal);
+                // Wraps once around both circles, partial sweeps keep their part of the texture
+                let new_point: Point = Point::new(point_coord, normal)
+                    .with_uv(a / (2.0 * PI), b / (2.0 * PI));
                 shape.points.push(new_point);
                 b += self.angle_iter.1;
             }
@@ -129,100 +241,666 @@ impl ShapeGen for TorusGenerator {
     }
 }
 
+/// Which parts of a cube are turned into points
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum CubeMode {
+    /// Solid looking sides
+    #[default]
+    Faces,
+    /// Only the twelve edges, like a wireframe
+    Edges,
+    /// Only the eight corners
+    Corners,
+}
+
+/// One of the six sides of a box, named by the axis its outside faces along
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CubeFace {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl CubeFace {
+    /// Face on the given axis, 0 being X, on the side of the sign
+    fn on_axis(axis: usize, sign: f64) -> Self {
+        match (axis, sign > 0.0) {
+            (0, true) => CubeFace::PosX,
+            (0, false) => CubeFace::NegX,
+            (1, true) => CubeFace::PosY,
+            (1, false) => CubeFace::NegY,
+            (_, true) => CubeFace::PosZ,
+            (_, false) => CubeFace::NegZ,
+        }
+    }
+}
+
+/// Box with the given side lengths, centered on the object's location.
+/// Points are placed at most `node_dis` apart, spread evenly so the edges and corners are
+/// always included.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::{CubeGenerator, CubeMode};
+/// use shapes_rs::Object;
+///
+/// let wireframe = CubeGenerator::new(10.0, 10.0, 10.0).with_mode(CubeMode::Edges);
+/// let cube = Object::new(Coord::default(), &wireframe, Rotator::identity());
+///
+/// // Twelve edges with eleven points each
+/// assert_eq!(cube.shape.points.len(), 12 * 11);
+/// // Every point lies on two faces at once
+/// assert!(cube.shape.points.iter().all(|p| {
+///     let c = p.rel_coord;
+///     [c.x, c.y, c.z].iter().filter(|v| v.abs() == 5.0).count() >= 2
+/// }));
+/// ```
 pub struct CubeGenerator {
     pub x: f64,
     pub y: f64,
     pub z: f64,
     pub node_dis: f64,
+    pub mode: CubeMode,
+    /// Normals point into the box instead of out of it, for rooms seen from within
+    pub inward_normals: bool,
+    /// Faces left out, indexed by CubeFace. Only used by CubeMode::Faces
+    pub open_faces: [bool; 6],
 }
 
 impl CubeGenerator {
-    pub fn new(x: f64, y: f64, z: f64) -> Self {
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
         Self {
             x,
             y,
             z,
             node_dis: 1.0,
+            mode: CubeMode::Faces,
+            inward_normals: false,
+            open_faces: [false; 6],
         }
     }
-}
 
-impl ShapeGen for CubeGenerator {
-    fn generate_shape(&self, shape: &mut Shape) {
-        let edge_z = self.z / 2.0;
-        let edge_y = self.y / 2.0;
-        let edge_x = self.x / 2.0;
-        let node_dis = self.node_dis;
-        // Generate +X facing
-        for z in 0..(self.z / self.node_dis) as usize - 1 {
-            for y in 0..(self.y / self.node_dis) as usize - 1 {
-                let normal = Vector3D::new(1.0, 0.0, 0.0);
-                let coord = Coord::new(
-                    edge_x,
-                    edge_y - y as f64 * node_dis,
-                    edge_z - z as f64 * node_dis,
-                );
-                shape.points.push(Point::new(coord, normal));
+    /// Box lit from within, for scenes set inside of a room. Same as new with inward normals
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::components::*;
+    /// use shapes_rs::generators::selfmade::ShapeGen;
+    /// use shapes_rs::generators::{CubeFace, CubeGenerator};
+    /// use shapes_rs::{Object, SceneBuilder};
+    ///
+    /// // Room without a ceiling, the camera standing in it
+    /// let roomgen = CubeGenerator::room(200.0, 200.0, 100.0).with_open_face(CubeFace::PosZ);
+    /// let room = Object::new(Coord::default(), &roomgen, Rotator::identity());
+    /// // No ceiling looking down into the room
+    /// assert!(room.shape.points.iter().all(|p| p.normal.z >= 0.0));
+    /// assert_eq!(roomgen.point_count_hint(), Some(room.shape.points.len()));
+    ///
+    /// let scene = SceneBuilder::new()
+    ///     .camera(Camera::new(
+    ///         Coord::new(-50.0, 0.0, 0.0),
+    ///         Vector3D::new(1.0, 0.0, 0.0),
+    ///         Angle::from_degree(90.0),
+    ///     ))
+    ///     .add_light(Light3D::new(Coord::new(-50.0, 0.0, 40.0)))
+    ///     .add_object(room)
+    ///     .buffer_size(40, 20)
+    ///     .build();
+    ///
+    /// // The far wall is lit, so it shows up
+    /// let frame = scene.render().to_string();
+    /// assert!(frame.lines().nth(10).unwrap().chars().any(|c| c != ' '));
+    /// ```
+    pub fn room(x: f64, y: f64, z: f64) -> Self {
+        Self::new(x, y, z).with_inward_normals(true)
+    }
+
+    /// Same as new, but fails if a side length is negative or not finite
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// assert!(CubeGenerator::try_new(1.0, -1.0, 1.0).is_err());
+    /// assert!(CubeGenerator::try_new(1.0, 1.0, 1.0).unwrap().try_with_node_dis(0.0).is_err());
+    ///
+    /// // Sides shorter than the distance between points still get both of their ends
+    /// let small = CubeGenerator::try_new(0.5, 0.5, 0.5).unwrap();
+    /// let cube = Object::new(Coord::default(), &small, Rotator::identity());
+    /// assert_eq!(cube.shape.points.len(), 6 * 4);
+    /// assert!(cube.shape.points.iter().any(|p| p.rel_coord.y == -0.25));
+    /// ```
+    pub fn try_new(x: f64, y: f64, z: f64) -> Result<Self> {
+        let generator = Self::new(x, y, z);
+        generator.validate()?;
+        Ok(generator)
+    }
+
+    /// Sets the largest distance between two points, fails if it isn't positive and finite
+    pub fn try_with_node_dis(mut self, node_dis: f64) -> Result<Self> {
+        self.node_dis = node_dis;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Fails if the cube can't be generated with its current parameters
+    pub fn validate(&self) -> Result<()> {
+        if [self.x, self.y, self.z].iter().any(|side| !side.is_finite() || *side < 0.0) {
+            return Err(ShapesError::InvalidParameter(
+                "cube sides have to be finite and not negative",
+            ));
+        }
+        if !self.node_dis.is_finite() || self.node_dis <= 0.0 {
+            return Err(ShapesError::InvalidParameter(
+                "distance between cube points has to be finite and positive",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets which parts of the cube are generated. The distance function always describes
+    /// the whole box.
+    pub fn with_mode(mut self, mode: CubeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Lights the inside of the box instead of the outside, for rooms with the camera in them.
+    /// See Shape::flip_normals for shapes which have no such option
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::generators::CubeGenerator;
+    /// use shapes_rs::Object;
+    ///
+    /// let roomgen = CubeGenerator::new(100.0, 100.0, 40.0).with_inward_normals(true);
+    /// let room = Object::new(Coord::default(), &roomgen, Rotator::identity());
+    ///
+    /// // The floor faces up, into the room
+    /// let mut floor = room.shape.points.iter().filter(|p| p.rel_coord.z == -20.0);
+    /// assert!(floor.all(|p| p.normal.z >= 0.0));
+    /// ```
+    pub fn with_inward_normals(mut self, inward: bool) -> Self {
+        self.inward_normals = inward;
+        self
+    }
+
+    /// Leaves out the given face, for rooms with an open side or boxes without a lid.
+    /// Only faces are left out, edges and corners are always generated whole
+    pub fn with_open_face(mut self, face: CubeFace) -> Self {
+        self.open_faces[face as usize] = true;
+        self
+    }
+
+    /// Whether the given face is left out
+    pub fn is_open(&self, face: CubeFace) -> bool {
+        self.open_faces[face as usize]
+    }
+
+    /// Positions along a side of the given length, from one end to the other
+    fn samples(&self, length: f64) -> impl Iterator<Item = f64> {
+        let steps = f64::ceil(length / self.node_dis) as usize;
+        // Evenly spread, so fractional lengths still end exactly on the edge
+        let step = if steps == 0 { 0.0 } else { length / steps as f64 };
+        (0..=steps).map(move |i| length / 2.0 - i as f64 * step)
+    }
+
+    /// Every face is textured on its own, from one edge to the other
+    fn generate_faces(&self, shape: &mut Shape) {
+        let face_uv = |position: f64, length: f64| match length {
+            0.0 => 0.5,
+            _ => position / length + 0.5,
+        };
+        let (edge_x, edge_y, edge_z) = (self.x / 2.0, self.y / 2.0, self.z / 2.0);
+        for sign in [1.0, -1.0] {
+            // X facing
+            if !self.is_open(CubeFace::on_axis(0, sign)) {
+                for z in self.samples(self.z) {
+                    for y in self.samples(self.y) {
+                        let normal = Vector3D::new(sign, 0.0, 0.0);
+                        let point = Point::new(Coord::new(sign * edge_x, y, z), normal);
+                        shape.points.push(point.with_uv(face_uv(y, self.y), face_uv(z, self.z)));
+                    }
+                }
+            }
+            // Y facing
+            if !self.is_open(CubeFace::on_axis(1, sign)) {
+                for z in self.samples(self.z) {
+                    for x in self.samples(self.x) {
+                        let normal = Vector3D::new(0.0, sign, 0.0);
+                        let point = Point::new(Coord::new(x, sign * edge_y, z), normal);
+                        shape.points.push(point.with_uv(face_uv(x, self.x), face_uv(z, self.z)));
+                    }
+                }
+            }
+            // Z facing
+            if !self.is_open(CubeFace::on_axis(2, sign)) {
+                for y in self.samples(self.y) {
+                    for x in self.samples(self.x) {
+                        let normal = Vector3D::new(0.0, 0.0, sign);
+                        let point = Point::new(Coord::new(x, y, sign * edge_z), normal);
+                        shape.points.push(point.with_uv(face_uv(x, self.x), face_uv(y, self.y)));
+                    }
+                }
             }
         }
-        // Generate -X facing
-        for z in 0..(self.z / self.node_dis) as usize - 1 {
-            for y in 0..(self.y / self.node_dis) as usize - 1 {
-                let normal = Vector3D::new(-1.0, 0.0, 0.0);
-                let coord = Coord::new(
-                    -edge_x,
-                    edge_y - y as f64 * node_dis,
-                    edge_z - z as f64 * node_dis,
-                );
-                shape.points.push(Point::new(coord, normal));
+    }
+
+    fn generate_edges(&self, shape: &mut Shape) {
+        let (edge_x, edge_y, edge_z) = (self.x / 2.0, self.y / 2.0, self.z / 2.0);
+        for a in [1.0, -1.0] {
+            for b in [1.0, -1.0] {
+                // Edges along X, Y and Z, pointing away from both faces they join
+                for x in self.samples(self.x) {
+                    let normal = Vector3D::new(0.0, a, b).normalise();
+                    shape.points.push(Point::new(Coord::new(x, a * edge_y, b * edge_z), normal));
+                }
+                for y in self.samples(self.y) {
+                    let normal = Vector3D::new(a, 0.0, b).normalise();
+                    shape.points.push(Point::new(Coord::new(a * edge_x, y, b * edge_z), normal));
+                }
+                for z in self.samples(self.z) {
+                    let normal = Vector3D::new(a, b, 0.0).normalise();
+                    shape.points.push(Point::new(Coord::new(a * edge_x, b * edge_y, z), normal));
+                }
             }
         }
-        // Generate +Y facing
-        for z in 0..(self.z / self.node_dis) as usize - 1 {
-            for x in 0..(self.x / self.node_dis) as usize - 1 {
-                let normal = Vector3D::new(0.0, 1.0, 0.0);
-                let coord = Coord::new(
-                    edge_x - x as f64 * node_dis,
-                    edge_y,
-                    edge_z - z as f64 * node_dis,
-                );
-                shape.points.push(Point::new(coord, normal));
+    }
+
+    fn generate_corners(&self, shape: &mut Shape) {
+        for x in [1.0, -1.0] {
+            for y in [1.0, -1.0] {
+                for z in [1.0, -1.0] {
+                    let coord = Coord::new(x * self.x / 2.0, y * self.y / 2.0, z * self.z / 2.0);
+                    let normal = Vector3D::new(x, y, z).normalise();
+                    shape.points.push(Point::new(coord, normal));
+                }
             }
         }
-        // Generate -Y facing
-        for z in 0..(self.z / self.node_dis) as usize - 1 {
-            for x in 0..(self.x / self.node_dis) as usize - 1 {
-                let normal = Vector3D::new(0.0, -1.0, 0.0);
-                let coord = Coord::new(
-                    edge_x - x as f64 * node_dis,
-                    -edge_y,
-                    edge_z - z as f64 * node_dis,
-                );
-                shape.points.push(Point::new(coord, normal));
+    }
+}
+
+impl ShapeGen for CubeGenerator {
+    fn point_count_hint(&self) -> Option<usize> {
+        let count = |length: f64| self.samples(length).count();
+        let (x, y, z) = (count(self.x), count(self.y), count(self.z));
+        // Points on both faces along the axis which aren't left out
+        let faces = |axis: usize, points: usize| {
+            let closed = [1.0, -1.0].into_iter().filter(|sign| {
+                !self.is_open(CubeFace::on_axis(axis, *sign))
+            });
+            closed.count() * points
+        };
+        Some(match self.mode {
+            CubeMode::Faces => faces(0, y * z) + faces(1, z * x) + faces(2, y * x),
+            CubeMode::Edges => 4 * (x + y + z),
+            CubeMode::Corners => 8,
+        })
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        let half = Coord::new(self.x / 2.0, self.y / 2.0, self.z / 2.0);
+        Some(Bounds::new(half.mul(-1.0), half))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        // Nothing sensible can be generated, try_new catches this early
+        if self.validate().is_err() {
+            return;
+        }
+        let start = shape.points.len();
+        match self.mode {
+            CubeMode::Faces => self.generate_faces(shape),
+            CubeMode::Edges => self.generate_edges(shape),
+            CubeMode::Corners => self.generate_corners(shape),
+        }
+        if self.inward_normals {
+            for point in shape.points[start..].iter_mut() {
+                point.normal = point.normal.mul(-1.0);
             }
         }
-        // Generate +Z facing
-        for y in 0..(self.y / self.node_dis) as usize - 1 {
-            for x in 0..(self.x / self.node_dis) as usize - 1 {
-                let normal = Vector3D::new(0.0, 0.0, 1.0);
-                let coord = Coord::new(
-                    edge_x - x as f64 * node_dis,
-                    edge_y - y as f64 * node_dis,
-                    edge_z,
-                );
-                shape.points.push(Point::new(coord, normal));
+    }
+}
+
+/// Flat ring between two radii, lying in the Y-Z plane like TorusGenerator. Points are placed
+/// at most `node_dis` apart. Normals point along X, give the ring a two sided material to light
+/// it from both sides. For the rings of planets or discs, an inner radius of 0 fills the middle.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::RingGenerator;
+/// use shapes_rs::Object;
+///
+/// let ringgen = RingGenerator::try_new(20.0, 30.0).unwrap();
+/// let ring = Object::new(Coord::default(), &ringgen, Rotator::identity());
+///
+/// assert!(ring.shape.points.iter().all(|p| {
+///     let radius = (p.rel_coord.y.powi(2) + p.rel_coord.z.powi(2)).sqrt();
+///     p.rel_coord.x == 0.0 && radius > 20.0 - 1e-9 && radius < 30.0 + 1e-9
+/// }));
+/// assert!(RingGenerator::try_new(30.0, 20.0).is_err());
+/// ```
+pub struct RingGenerator {
+    pub inner_radius: f64,
+    pub outer_radius: f64,
+    pub node_dis: f64,
+}
+
+impl RingGenerator {
+    pub const fn new(inner_radius: f64, outer_radius: f64) -> Self {
+        Self {
+            inner_radius,
+            outer_radius,
+            node_dis: 1.0,
+        }
+    }
+
+    /// Same as new, but fails if the radii are negative, not finite or the wrong way around
+    pub fn try_new(inner_radius: f64, outer_radius: f64) -> Result<Self> {
+        let generator = Self::new(inner_radius, outer_radius);
+        generator.validate()?;
+        Ok(generator)
+    }
+
+    /// Sets the largest distance between two points, fails if it isn't positive and finite
+    pub fn try_with_node_dis(mut self, node_dis: f64) -> Result<Self> {
+        self.node_dis = node_dis;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Fails if the ring can't be generated with its current parameters
+    pub fn validate(&self) -> Result<()> {
+        let (inner, outer) = (self.inner_radius, self.outer_radius);
+        if !inner.is_finite() || !outer.is_finite() || inner < 0.0 || outer < inner {
+            return Err(ShapesError::InvalidParameter(
+                "ring radii have to be finite, not negative and the inner one not the larger one",
+            ));
+        }
+        if !self.node_dis.is_finite() || self.node_dis <= 0.0 {
+            return Err(ShapesError::InvalidParameter(
+                "distance between ring points has to be finite and positive",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Radii of the circles points are placed on, from the inner edge to the outer one
+    fn radii(&self) -> impl Iterator<Item = f64> {
+        let width = self.outer_radius - self.inner_radius;
+        let steps = f64::ceil(width / self.node_dis) as usize;
+        let step = if steps == 0 { 0.0 } else { width / steps as f64 };
+        let inner = self.inner_radius;
+        (0..=steps).map(move |i| inner + i as f64 * step)
+    }
+
+    /// How many points go on the circle with the given radius, a single one in the very middle
+    fn circle_points(&self, radius: f64) -> usize {
+        usize::max(f64::ceil(2.0 * PI * radius / self.node_dis) as usize, 1)
+    }
+}
+
+impl ShapeGen for RingGenerator {
+    fn point_count_hint(&self) -> Option<usize> {
+        Some(self.radii().map(|radius| self.circle_points(radius)).sum())
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        let outer = self.outer_radius;
+        Some(Bounds::new(Coord::new(0.0, -outer, -outer), Coord::new(0.0, outer, outer)))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        if self.validate().is_err() {
+            return;
+        }
+        let width = self.outer_radius - self.inner_radius;
+        for radius in self.radii() {
+            let count = self.circle_points(radius);
+            // Wraps once around the ring, from the inner edge to the outer one
+            let v = match width {
+                0.0 => 0.5,
+                _ => (radius - self.inner_radius) / width,
+            };
+            for i in 0..count {
+                let angle = 2.0 * PI * i as f64 / count as f64;
+                let coord = Coord::new(0.0, radius * f64::cos(angle), radius * f64::sin(angle));
+                let point = Point::new(coord, Vector3D::new(1.0, 0.0, 0.0));
+                shape.points.push(point.with_uv(angle / (2.0 * PI), v));
             }
         }
-        // Generate -Z facing
-        for y in 0..(self.y / self.node_dis) as usize - 1 {
-            for x in 0..(self.x / self.node_dis) as usize - 1 {
-                let normal = Vector3D::new(0.0, 0.0, -1.0);
-                let coord = Coord::new(
-                    edge_x - x as f64 * node_dis,
-                    edge_y - y as f64 * node_dis,
-                    -edge_z,
-                );
-                shape.points.push(Point::new(coord, normal));
+    }
+}
+
+/// Stars scattered over a sphere around the object's location, seeded so the same stars come
+/// out every time. Put the camera inside of it and give it an emissive material.
+/// Normals point towards the center.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::StarfieldGenerator;
+/// use shapes_rs::Object;
+///
+/// let stars = StarfieldGenerator::new(200, 1000.0, 7);
+/// let a = Object::new(Coord::default(), &stars, Rotator::identity());
+/// let b = Object::new(Coord::default(), &stars, Rotator::identity());
+///
+/// assert_eq!(a.shape.points.len(), 200);
+/// assert_eq!(a.shape.points[10].rel_coord.x, b.shape.points[10].rel_coord.x);
+/// ```
+pub struct StarfieldGenerator {
+    pub count: usize,
+    pub radius: f64,
+    pub seed: u64,
+}
+
+impl StarfieldGenerator {
+    pub const fn new(count: usize, radius: f64, seed: u64) -> Self {
+        Self {
+            count,
+            radius,
+            seed,
+        }
+    }
+}
+
+impl ShapeGen for StarfieldGenerator {
+    fn point_count_hint(&self) -> Option<usize> {
+        Some(self.count)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        let radius = Coord::new(self.radius, self.radius, self.radius);
+        Some(Bounds::new(radius.mul(-1.0), radius))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        let mut rng = Rng::new(self.seed);
+        for _ in 0..self.count {
+            let direction = rng.unit_vector();
+            shape
+                .points
+                .push(Point::new(direction.mul(self.radius).as_coord(), direction.mul(-1.0)));
+        }
+    }
+}
+
+/// How a ReliefGenerator turns its image into points
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ReliefMode {
+    /// Bright pixels are raised towards the viewer, normals follow the slope of the surface
+    #[default]
+    Relief,
+    /// Flat sheet facing the viewer, every point colored with the gray of its pixel
+    Billboard,
+}
+
+/// Turns a grayscale image or text art into a surface in the Y-Z plane, facing along negative X
+/// like the default camera looks at it. Columns run along Y and rows down along Z, so the image
+/// shows up the right way around from a camera looking along positive X.
+/// Every pixel becomes one point, which also gets the pixel's position as UV coordinate.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::{ReliefGenerator, ReliefMode};
+/// use shapes_rs::Object;
+///
+/// let logo = ReliefGenerator::from_text(" @ \n@@@\n . ").unwrap().with_depth(4.0);
+/// let relief = Object::new(Coord::default(), &logo, Rotator::identity());
+///
+/// // Spaces are left out
+/// assert_eq!(relief.shape.points.len(), 5);
+/// // The '@' is raised all the way towards the viewer, the '.' stays flat
+/// let lowest = relief.shape.points.iter().map(|p| p.rel_coord.x).fold(f64::MAX, f64::min);
+/// assert_eq!(lowest, -4.0);
+/// assert!(relief.shape.points.iter().any(|p| p.rel_coord.x == 0.0));
+///
+/// // Flat billboards keep the gray of the image as color instead
+/// let flat = logo.with_mode(ReliefMode::Billboard);
+/// let billboard = Object::new(Coord::default(), &flat, Rotator::identity());
+/// assert!(billboard.shape.points.iter().all(|p| p.rel_coord.x == 0.0 && p.color.is_some()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReliefGenerator {
+    pub width: usize,
+    pub height: usize,
+    /// Brightness of every pixel from 0 to 1, row by row starting at the top left.
+    /// Pixels without one are left out
+    pub pixels: Vec<Option<f64>>,
+    /// Distance between neighbouring pixels along Y and Z
+    pub cell_size: (f64, f64),
+    /// How far the brightest pixels are raised
+    pub depth: f64,
+    pub mode: ReliefMode,
+}
+
+impl ReliefGenerator {
+    /// Builds a relief from 8 bit grayscale pixels, row by row starting at the top left.
+    /// Returns None if the number of pixels does not match the size or the image is empty
+    pub fn new(width: usize, height: usize, gray: &[u8]) -> Option<Self> {
+        let pixels = gray.iter().map(|g| Some(*g as f64 / 255.0)).collect();
+        Self::from_pixels(width, height, pixels)
+    }
+
+    /// Builds a relief from text art, reading every character as its place on the luminance
+    /// ramp of the renderers. Cells are twice as tall as wide like terminal characters, the
+    /// image is as wide as its longest line. Returns None if there is no text
+    pub fn from_text(text: &str) -> Option<Self> {
+        let width = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let height = text.lines().count();
+        let mut pixels = Vec::with_capacity(width * height);
+        for line in text.lines() {
+            let length = line.chars().count();
+            pixels.extend(line.chars().map(char_luminance));
+            pixels.extend(core::iter::repeat_n(None, width - length));
+        }
+        Some(Self::from_pixels(width, height, pixels)?.with_cell_size(1.0, 2.0))
+    }
+
+    fn from_pixels(width: usize, height: usize, pixels: Vec<Option<f64>>) -> Option<Self> {
+        if width == 0 || height == 0 || pixels.len() != width * height {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            pixels,
+            cell_size: (1.0, 1.0),
+            depth: 1.0,
+            mode: ReliefMode::Relief,
+        })
+    }
+
+    /// Sets the distance between neighbouring pixels along Y and Z
+    pub fn with_cell_size(mut self, y: f64, z: f64) -> Self {
+        self.cell_size = (y, z);
+        self
+    }
+
+    /// Sets how far the brightest pixels are raised towards the viewer
+    pub fn with_depth(mut self, depth: f64) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ReliefMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Brightness of a pixel, missing pixels and those outside of the image count as dark
+    fn brightness(&self, column: isize, row: isize) -> f64 {
+        if column < 0 || row < 0 || column as usize >= self.width || row as usize >= self.height {
+            return 0.0;
+        }
+        self.pixels[row as usize * self.width + column as usize].unwrap_or(0.0)
+    }
+
+    /// Position of a pixel on the Y-Z plane, the image being centered on it
+    fn plane_coord(&self, column: usize, row: usize) -> (f64, f64) {
+        let y = (column as f64 - (self.width - 1) as f64 / 2.0) * self.cell_size.0;
+        let z = ((self.height - 1) as f64 / 2.0 - row as f64) * self.cell_size.1;
+        (y, z)
+    }
+}
+
+impl ShapeGen for ReliefGenerator {
+    fn point_count_hint(&self) -> Option<usize> {
+        Some(self.pixels.iter().filter(|pixel| pixel.is_some()).count())
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        let (y, z) = self.plane_coord(0, 0);
+        let raised = match self.mode {
+            ReliefMode::Relief => self.depth,
+            ReliefMode::Billboard => 0.0,
+        };
+        Some(Bounds::new(
+            Coord::new((-raised).min(0.0), -y.abs(), -z.abs()),
+            Coord::new((-raised).max(0.0), y.abs(), z.abs()),
+        ))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        let uv = |index: usize, size: usize| match size {
+            1 => 0.5,
+            _ => index as f64 / (size - 1) as f64,
+        };
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let Some(value) = self.pixels[row * self.width + column] else {
+                    continue;
+                };
+                let (y, z) = self.plane_coord(column, row);
+                let mut point = match self.mode {
+                    ReliefMode::Relief => {
+                        // Slope of the surface x = -depth * brightness from its neighbours
+                        let (c, r) = (column as isize, row as isize);
+                        let slope_y = (self.brightness(c + 1, r) - self.brightness(c - 1, r))
+                            / (2.0 * self.cell_size.0);
+                        // Rows grow downwards, Z grows upwards
+                        let slope_z = (self.brightness(c, r - 1) - self.brightness(c, r + 1))
+                            / (2.0 * self.cell_size.1);
+                        let normal = Vector3D::new(
+                            -1.0,
+                            -self.depth * slope_y,
+                            -self.depth * slope_z,
+                        );
+                        Point::new(Coord::new(-self.depth * value, y, z), normal.normalise())
+                    }
+                    ReliefMode::Billboard => {
+                        let mut point = Point::new(Coord::new(0.0, y, z), Vector3D::new(-1.0, 0.0, 0.0));
+                        let gray = (value.clamp(0.0, 1.0) * 255.0) as u8;
+                        point.color = Some(Rgb::new(gray, gray, gray));
+                        point
+                    }
+                };
+                point.uv = Some((uv(column, self.width), uv(row, self.height)));
+                shape.points.push(point);
             }
         }
     }
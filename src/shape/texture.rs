@@ -0,0 +1,135 @@
+//! Textures are sampled with the UV coordinates generators give their points, letting color
+//! and brightness vary over the surface of a shape without any extra points.
+
+use alloc::vec::Vec;
+
+use crate::out::color::Rgb;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+/// Color of a surface at a given UV coordinate. Coordinates are usually between 0 and 1 but
+/// textures should accept any and repeat themselves.
+/// When an object has a texture, color renderers use the sampled color for its points and every
+/// renderer scales their luminance with its brightness.
+/// Has to be Sync for the same reason as Sdf. Closures taking `u` and `v` are textures too.
+/// ### Example:
+/// ```
+/// use shapes_rs::color::Rgb;
+/// use shapes_rs::texture::Texture;
+///
+/// let gradient = |u: f64, _v: f64| Rgb::new((u * 255.0) as u8, 0, 0);
+/// assert_eq!(gradient.sample(1.0, 0.0), Rgb::new(255, 0, 0));
+/// ```
+pub trait Texture: Sync {
+    fn sample(&self, u: f64, v: f64) -> Rgb;
+}
+
+impl<F: Fn(f64, f64) -> Rgb + Sync> Texture for F {
+    fn sample(&self, u: f64, v: f64) -> Rgb {
+        self(u, v)
+    }
+}
+
+/// Alternating squares of two colors
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::color::Rgb;
+/// use shapes_rs::generators::TorusGenerator;
+/// use shapes_rs::renderer::pers_proj;
+/// use shapes_rs::texture::Checkerboard;
+/// use shapes_rs::testing::fixture_scene;
+/// use shapes_rs::Object;
+///
+/// let torusgen = TorusGenerator::new(10.0, 50.0);
+/// let checkers = Checkerboard::new(16, 4, Rgb::new(255, 255, 255), Rgb::new(40, 40, 40));
+/// let torus = Object::new(Coord::new(150.0, 0.0, 0.0), &torusgen, Rotator::identity());
+///
+/// let mut plain = fixture_scene(60, 30);
+/// plain.add_object(torus.clone());
+/// let mut checkered = fixture_scene(60, 30);
+/// checkered.add_object(torus.with_texture(&checkers));
+///
+/// // Same cells are drawn, the dark squares are dimmer
+/// let plain = plain.render();
+/// let checkered = checkered.render();
+/// assert_ne!(plain.hash(), checkered.hash());
+/// assert!(plain.buffer.iter().zip(checkered.buffer.iter()).all(|(a, b)| (*a == ' ') == (*b == ' ')));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Checkerboard {
+    /// Number of squares along U
+    pub cells_u: u32,
+    /// Number of squares along V
+    pub cells_v: u32,
+    pub even: Rgb,
+    pub odd: Rgb,
+}
+
+impl Checkerboard {
+    pub fn new(cells_u: u32, cells_v: u32, even: Rgb, odd: Rgb) -> Self {
+        Self {
+            cells_u,
+            cells_v,
+            even,
+            odd,
+        }
+    }
+}
+
+impl Texture for Checkerboard {
+    fn sample(&self, u: f64, v: f64) -> Rgb {
+        let cell_u = f64::floor(u * self.cells_u as f64) as i64;
+        let cell_v = f64::floor(v * self.cells_v as f64) as i64;
+        match (cell_u + cell_v).rem_euclid(2) {
+            0 => self.even,
+            _ => self.odd,
+        }
+    }
+}
+
+/// Texture made of pixels, stored row by row starting at the top left. Sampled with the
+/// nearest pixel and repeated outside of 0 to 1.
+/// ### Example:
+/// ```
+/// use shapes_rs::color::Rgb;
+/// use shapes_rs::texture::{ImageTexture, Texture};
+///
+/// let red = Rgb::new(255, 0, 0);
+/// let blue = Rgb::new(0, 0, 255);
+/// let image = ImageTexture::new(2, 1, vec![red, blue]).unwrap();
+///
+/// assert_eq!(image.sample(0.25, 0.5), red);
+/// assert_eq!(image.sample(0.75, 0.5), blue);
+/// assert_eq!(image.sample(1.25, 0.5), red);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ImageTexture {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl ImageTexture {
+    /// Returns None if the number of pixels does not match the size or the image is empty
+    pub fn new(width: usize, height: usize, pixels: Vec<Rgb>) -> Option<Self> {
+        if width == 0 || height == 0 || pixels.len() != width * height {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+impl Texture for ImageTexture {
+    fn sample(&self, u: f64, v: f64) -> Rgb {
+        let wrap = |t: f64, size: usize| {
+            let t = t - f64::floor(t);
+            ((t * size as f64) as usize).min(size - 1)
+        };
+        self.pixels[wrap(v, self.height) * self.width + wrap(u, self.width)]
+    }
+}
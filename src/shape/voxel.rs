@@ -0,0 +1,300 @@
+//! Shapes made of equally sized cubes on a grid, such as blocky terrain or voxel art.
+
+use alloc::vec::Vec;
+
+use crate::basetype::{Coord, Vector3D};
+use crate::error::{Result, ShapesError};
+use crate::out::color::Rgb;
+use crate::shape::shape_base::{Bounds, Point, Shape};
+use crate::shape::shape_gen::ShapeGen;
+#[cfg(not(feature = "std"))]
+use crate::math::float::FloatExt;
+
+#[cfg(feature = "vox")]
+mod vox;
+
+/// Which faces of the voxels are turned into points
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum VoxelMode {
+    /// Only faces between a filled and an empty voxel, the outside of the shape
+    #[default]
+    Surface,
+    /// Every face of every filled voxel, as if each one was a cube of its own
+    Cubes,
+}
+
+/// Offsets to the six neighbours of a voxel, which are also the normals of its faces
+const NEIGHBOURS: [(i64, i64, i64); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// Grid of voxels which are either filled or empty, centered on the object's location with Z
+/// pointing up. Voxels are addressed by their position on the grid, starting at the corner
+/// with the smallest coordinates. Every face is filled with points at most `node_dis` apart.
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::VoxelGrid;
+/// use shapes_rs::Object;
+///
+/// // Rolling hills, one voxel tall at least
+/// let terrain = VoxelGrid::from_fn(32, 32, 8, 2.0, |x, y, z| {
+///     let height = 3.0 + 2.0 * (x as f64 / 4.0).sin() + 2.0 * (y as f64 / 5.0).cos();
+///     (z as f64) < height.max(1.0)
+/// });
+/// assert!(terrain.get(0, 0, 0) && !terrain.get(0, 0, 7));
+///
+/// let hills = Object::new(Coord::default(), &terrain, Rotator::identity());
+/// let bounds = hills.shape.bounds.unwrap();
+/// assert_eq!((bounds.min.x, bounds.max.z), (-32.0, 8.0));
+/// // Only the outside is generated, no point faces down into the ground
+/// assert!(hills.shape.points.iter().all(|p| p.normal.z >= 0.0 || p.rel_coord.z == -8.0));
+/// ```
+#[derive(Clone)]
+pub struct VoxelGrid {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub size_z: usize,
+    /// Length of the sides of a voxel
+    pub cell_size: f64,
+    pub node_dis: f64,
+    pub mode: VoxelMode,
+    filled: Vec<bool>,
+    /// Color of every voxel which has one, given to the points of its faces
+    colors: Vec<Option<Rgb>>,
+}
+
+impl VoxelGrid {
+    /// Empty grid with the given number of voxels along each axis
+    pub fn new(size_x: usize, size_y: usize, size_z: usize, cell_size: f64) -> Self {
+        let mut filled = Vec::new();
+        filled.resize(size_x * size_y * size_z, false);
+        let mut colors = Vec::new();
+        colors.resize(filled.len(), None);
+        Self {
+            size_x,
+            size_y,
+            size_z,
+            cell_size,
+            node_dis: 1.0,
+            mode: VoxelMode::Surface,
+            filled,
+            colors,
+        }
+    }
+
+    /// Grid with every voxel filled for which the function returns true
+    pub fn from_fn(
+        size_x: usize,
+        size_y: usize,
+        size_z: usize,
+        cell_size: f64,
+        filled: impl Fn(usize, usize, usize) -> bool,
+    ) -> Self {
+        let mut grid = Self::new(size_x, size_y, size_z, cell_size);
+        for z in 0..size_z {
+            for y in 0..size_y {
+                for x in 0..size_x {
+                    let index = grid.index(x, y, z);
+                    grid.filled[index] = filled(x, y, z);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Sets the largest distance between two points, fails if it isn't positive and finite
+    pub fn try_with_node_dis(mut self, node_dis: f64) -> Result<Self> {
+        self.node_dis = node_dis;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Fails if the grid can't be generated with its current parameters
+    pub fn validate(&self) -> Result<()> {
+        if !self.cell_size.is_finite() || self.cell_size <= 0.0 {
+            return Err(ShapesError::InvalidParameter(
+                "voxel size has to be finite and positive",
+            ));
+        }
+        if !self.node_dis.is_finite() || self.node_dis <= 0.0 {
+            return Err(ShapesError::InvalidParameter(
+                "distance between voxel points has to be finite and positive",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets which faces of the voxels are generated
+    pub fn with_mode(mut self, mode: VoxelMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Whether the voxel is filled, voxels outside of the grid are always empty
+    pub fn get(&self, x: usize, y: usize, z: usize) -> bool {
+        self.in_grid(x, y, z) && self.filled[self.index(x, y, z)]
+    }
+
+    /// Fills the voxel, fails if it is outside of the grid
+    pub fn set(&mut self, x: usize, y: usize, z: usize) -> Result<()> {
+        self.put(x, y, z, true)
+    }
+
+    /// Empties the voxel, fails if it is outside of the grid
+    pub fn unset(&mut self, x: usize, y: usize, z: usize) -> Result<()> {
+        self.put(x, y, z, false)
+    }
+
+    /// Color of the voxel, None if it has none or is outside of the grid
+    pub fn color(&self, x: usize, y: usize, z: usize) -> Option<Rgb> {
+        match self.in_grid(x, y, z) {
+            true => self.colors[self.index(x, y, z)],
+            false => None,
+        }
+    }
+
+    /// Colors the voxel, for color renderers. Empty voxels keep their color until they are
+    /// filled again. Fails if the voxel is outside of the grid
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::base::*;
+    /// use shapes_rs::color::Rgb;
+    /// use shapes_rs::generators::VoxelGrid;
+    /// use shapes_rs::Object;
+    ///
+    /// let mut grid = VoxelGrid::new(2, 1, 1, 4.0);
+    /// grid.set(0, 0, 0).unwrap();
+    /// grid.set(1, 0, 0).unwrap();
+    /// grid.set_color(1, 0, 0, Some(Rgb::new(255, 0, 0))).unwrap();
+    ///
+    /// let pair = Object::new(Coord::default(), &grid, Rotator::identity());
+    /// let points = &pair.shape.points;
+    /// assert!(points.iter().filter(|p| p.rel_coord.x < 0.0).all(|p| p.color.is_none()));
+    /// assert!(points.iter().filter(|p| p.rel_coord.x > 0.0).all(|p| p.color.is_some()));
+    /// assert!(grid.set_color(2, 0, 0, None).is_err());
+    /// ```
+    pub fn set_color(&mut self, x: usize, y: usize, z: usize, color: Option<Rgb>) -> Result<()> {
+        if !self.in_grid(x, y, z) {
+            return Err(ShapesError::InvalidParameter("voxel is outside of the grid"));
+        }
+        let index = self.index(x, y, z);
+        self.colors[index] = color;
+        Ok(())
+    }
+
+    /// Number of filled voxels
+    pub fn count(&self) -> usize {
+        self.filled.iter().filter(|filled| **filled).count()
+    }
+
+    /// Positions of every filled voxel
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        (0..self.filled.len())
+            .filter(|index| self.filled[*index])
+            .map(|index| self.position(index))
+    }
+
+    /// Center of the voxel relative to the center of the grid
+    pub fn center(&self, x: usize, y: usize, z: usize) -> Coord {
+        let along = |position: usize, size: usize| {
+            (position as f64 + 0.5 - size as f64 / 2.0) * self.cell_size
+        };
+        Coord::new(along(x, self.size_x), along(y, self.size_y), along(z, self.size_z))
+    }
+
+    fn put(&mut self, x: usize, y: usize, z: usize, filled: bool) -> Result<()> {
+        if !self.in_grid(x, y, z) {
+            return Err(ShapesError::InvalidParameter("voxel is outside of the grid"));
+        }
+        let index = self.index(x, y, z);
+        self.filled[index] = filled;
+        Ok(())
+    }
+
+    fn in_grid(&self, x: usize, y: usize, z: usize) -> bool {
+        x < self.size_x && y < self.size_y && z < self.size_z
+    }
+
+    fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        (z * self.size_y + y) * self.size_x + x
+    }
+
+    fn position(&self, index: usize) -> (usize, usize, usize) {
+        let x = index % self.size_x;
+        let y = index / self.size_x % self.size_y;
+        (x, y, index / (self.size_x * self.size_y))
+    }
+
+    /// Whether the neighbour of the voxel at the given offset is filled
+    fn neighbour(&self, (x, y, z): (usize, usize, usize), (dx, dy, dz): (i64, i64, i64)) -> bool {
+        let step = |position: usize, offset: i64| (position as i64 + offset).try_into().ok();
+        match (step(x, dx), step(y, dy), step(z, dz)) {
+            (Some(x), Some(y), Some(z)) => self.get(x, y, z),
+            _ => false,
+        }
+    }
+
+    /// Points covering the face of the voxel with the given center facing along the offset
+    fn face_points(
+        &self,
+        center: Coord,
+        (dx, dy, dz): (i64, i64, i64),
+        color: Option<Rgb>,
+    ) -> Vec<Point> {
+        let normal = Vector3D::new(dx as f64, dy as f64, dz as f64);
+        let half = self.cell_size / 2.0;
+        let steps = usize::max(f64::ceil(self.cell_size / self.node_dis) as usize, 1);
+        let along = |i: usize| i as f64 * self.cell_size / steps as f64 - half;
+        let mut points = Vec::with_capacity((steps + 1) * (steps + 1));
+        for i in 0..=steps {
+            for j in 0..=steps {
+                let (a, b) = (along(i), along(j));
+                let offset = match (dx, dy) {
+                    (0, 0) => Coord::new(a, b, dz as f64 * half),
+                    (0, _) => Coord::new(a, dy as f64 * half, b),
+                    _ => Coord::new(dx as f64 * half, a, b),
+                };
+                let u = (a + half) / self.cell_size;
+                let v = (b + half) / self.cell_size;
+                let mut point = Point::new(center + offset, normal).with_uv(u, v);
+                point.color = color;
+                points.push(point);
+            }
+        }
+        points
+    }
+}
+
+impl ShapeGen for VoxelGrid {
+    fn bounds(&self) -> Option<Bounds> {
+        let half = Coord::new(
+            self.size_x as f64 * self.cell_size / 2.0,
+            self.size_y as f64 * self.cell_size / 2.0,
+            self.size_z as f64 * self.cell_size / 2.0,
+        );
+        Some(Bounds::new(half.mul(-1.0), half))
+    }
+
+    fn generate_shape(&self, shape: &mut Shape) {
+        // Nothing sensible can be generated
+        if self.validate().is_err() {
+            return;
+        }
+        for voxel in self.iter() {
+            let center = self.center(voxel.0, voxel.1, voxel.2);
+            let color = self.color(voxel.0, voxel.1, voxel.2);
+            for offset in NEIGHBOURS {
+                if self.mode == VoxelMode::Surface && self.neighbour(voxel, offset) {
+                    continue;
+                }
+                shape.points.extend(self.face_points(center, offset, color));
+            }
+        }
+    }
+}
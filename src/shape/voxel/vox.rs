@@ -0,0 +1,139 @@
+//! Loading voxel art saved by MagicaVoxel. Files are made of chunks, each starting with a four
+//! byte id and the sizes of its content and of its children as little endian u32. Only the
+//! size, voxels and palette of the first model are read, everything else is skipped.
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use super::VoxelGrid;
+use crate::binary::{invalid_data, read_or_end, read_u32};
+use crate::out::color::Rgb;
+
+const MAGIC: &[u8; 4] = b"VOX ";
+/// Largest model MagicaVoxel makes along every axis
+const MAX_SIZE: u32 = 256;
+
+impl VoxelGrid {
+    /// Reads the first model of a MagicaVoxel file, every voxel colored from the palette of
+    /// the file. Files without a palette leave the voxels uncolored. Voxels are one unit wide,
+    /// see cell_size. Fails with invalid data if it is not a .vox file or the model is larger
+    /// than MagicaVoxel allows, 256 voxels along every axis
+    /// ### Example:
+    /// ```
+    /// use shapes_rs::generators::VoxelGrid;
+    ///
+    /// // Two voxels next to each other, using the first two colors of the palette
+    /// let mut file = Vec::new();
+    /// let chunk = |file: &mut Vec<u8>, id: &[u8], content: &[u8], children: u32| {
+    ///     file.extend_from_slice(id);
+    ///     file.extend_from_slice(&(content.len() as u32).to_le_bytes());
+    ///     file.extend_from_slice(&children.to_le_bytes());
+    ///     file.extend_from_slice(content);
+    /// };
+    /// let size = [2u32, 1, 1].map(u32::to_le_bytes).concat();
+    /// let voxels = [&2u32.to_le_bytes()[..], &[0, 0, 0, 1], &[1, 0, 0, 2]].concat();
+    /// let mut palette = vec![255; 256 * 4];
+    /// palette[..8].copy_from_slice(&[255, 0, 0, 255, 0, 0, 255, 255]);
+    ///
+    /// file.extend_from_slice(b"VOX ");
+    /// file.extend_from_slice(&150u32.to_le_bytes());
+    /// chunk(&mut file, b"MAIN", &[], (3 * 12 + size.len() + voxels.len() + palette.len()) as u32);
+    /// chunk(&mut file, b"SIZE", &size, 0);
+    /// chunk(&mut file, b"XYZI", &voxels, 0);
+    /// chunk(&mut file, b"RGBA", &palette, 0);
+    ///
+    /// let grid = VoxelGrid::read_vox(file.as_slice()).unwrap();
+    /// assert_eq!((grid.size_x, grid.count()), (2, 2));
+    /// assert_eq!(grid.color(0, 0, 0).map(|c| (c.r, c.b)), Some((255, 0)));
+    /// assert_eq!(grid.color(1, 0, 0).map(|c| (c.r, c.b)), Some((0, 255)));
+    /// assert!(VoxelGrid::read_vox(&b"PNG "[..]).is_err());
+    ///
+    /// // Sizes MagicaVoxel can't make are rejected before any voxels are allocated
+    /// let mut huge = b"VOX ".to_vec();
+    /// huge.extend_from_slice(&150u32.to_le_bytes());
+    /// chunk(&mut huge, b"SIZE", &[u32::MAX; 3].map(u32::to_le_bytes).concat(), 0);
+    /// assert!(VoxelGrid::read_vox(huge.as_slice()).is_err());
+    /// ```
+    pub fn read_vox(reader: impl Read) -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a MagicaVoxel file"));
+        }
+        // Version, every known one stores models the same way
+        read_u32(&mut reader)?;
+
+        let mut size = None;
+        let mut voxels: Vec<[u8; 4]> = Vec::new();
+        let mut palette = None;
+        let mut id = [0; 4];
+        while read_or_end(&mut reader, &mut id)? {
+            let content_size = read_u32(&mut reader)? as usize;
+            // Children follow as chunks of their own
+            read_u32(&mut reader)?;
+            let mut content = Vec::new();
+            reader
+                .by_ref()
+                .take(content_size as u64)
+                .read_to_end(&mut content)?;
+            if content.len() < content_size {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+            match &id {
+                // Later models are skipped
+                b"SIZE" if size.is_none() => {
+                    let [x, y, z] = [0, 4, 8].map(|at| u32_at(&content, at));
+                    let (x, y, z) = (x?, y?, z?);
+                    if x.max(y).max(z) > MAX_SIZE {
+                        return Err(invalid_data("model larger than 256 voxels along an axis"));
+                    }
+                    size = Some((x, y, z));
+                }
+                b"XYZI" if voxels.is_empty() => {
+                    let count = u32_at(&content, 0)? as usize;
+                    let data = count
+                        .checked_mul(4)
+                        .and_then(|length| content.get(4..4 + length))
+                        .ok_or_else(|| invalid_data("voxel chunk shorter than its count"))?;
+                    voxels = data.chunks_exact(4).map(|v| [v[0], v[1], v[2], v[3]]).collect();
+                }
+                b"RGBA" => {
+                    let colors: Vec<Rgb> = content
+                        .chunks_exact(4)
+                        .map(|c| Rgb::new(c[0], c[1], c[2]))
+                        .collect();
+                    palette = Some(colors);
+                }
+                _ => {}
+            }
+        }
+
+        let (size_x, size_y, size_z) = size.ok_or_else(|| invalid_data("file has no model"))?;
+        let mut grid = Self::new(size_x as usize, size_y as usize, size_z as usize, 1.0);
+        for [x, y, z, color] in voxels {
+            let (x, y, z) = (x as usize, y as usize, z as usize);
+            grid.set(x, y, z)
+                .map_err(|_| invalid_data("voxel outside of its model"))?;
+            // Color indices start at 1, the palette at the first of them
+            let color = palette
+                .as_ref()
+                .and_then(|palette| palette.get((color as usize).wrapping_sub(1)).copied());
+            let _ = grid.set_color(x, y, z, color);
+        }
+        Ok(grid)
+    }
+
+    /// Reads the first model of a MagicaVoxel file, see read_vox
+    pub fn load_vox<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read_vox(File::open(path)?)
+    }
+}
+
+fn u32_at(content: &[u8], at: usize) -> io::Result<u32> {
+    let bytes = content
+        .get(at..at + 4)
+        .ok_or_else(|| invalid_data("chunk shorter than its fields"))?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
@@ -0,0 +1,113 @@
+//! Helpers for writing golden image regression tests of renders.
+//!
+//! Renders are compared either against a stored hash or against a text fixture file. Fixture
+//! files can be (re)generated by running the tests with the `SHAPES_UPDATE_GOLDEN` environment
+//! variable set.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::basetype::{Angle, Coord, Vector3D};
+use crate::component::{Camera, Light3D};
+use crate::math::projection::pers_proj;
+use crate::out::terminal::SimpleTerminalBuffer;
+use crate::scene::Scene;
+
+/// Environment variable which makes `assert_golden` write fixtures instead of comparing them
+pub const UPDATE_GOLDEN_VAR: &str = "SHAPES_UPDATE_GOLDEN";
+
+/// Camera at the origin looking towards positive X with a 60 degree field of view
+pub fn fixture_camera() -> Camera {
+    Camera::new(
+        Coord::default(),
+        Vector3D::new(1.0, 0.0, 0.0),
+        Angle::from_degree(60.0),
+    )
+}
+
+/// Scene with the fixture camera, a single light above and behind the camera and the
+/// perspective renderer, ready to have objects added to it
+/// ### Example:
+/// ```
+/// use shapes_rs::base::*;
+/// use shapes_rs::generators::CubeGenerator;
+/// use shapes_rs::testing::*;
+/// use shapes_rs::Object;
+///
+/// let cubegen = CubeGenerator::new(10.0, 10.0, 10.0);
+/// let mut scene = fixture_scene(30, 15);
+/// scene.add_object(Object::new(
+///     Coord::new(30.0, 0.0, 0.0),
+///     &cubegen,
+///     Rotator::from_global(Angle3D::new(Angle::default(), Angle::from_degree(20.0), Angle::from_degree(30.0))),
+/// ));
+///
+/// assert_render_hash(&scene.render(), 0xcc68_3126_05e9_d234);
+/// ```
+pub fn fixture_scene<'a>(size_x: usize, size_y: usize) -> Scene<'a> {
+    let mut scene = Scene::new(
+        fixture_camera(),
+        SimpleTerminalBuffer::new(size_x, size_y),
+        pers_proj,
+    );
+    scene.add_light(Light3D::new(Coord::new(-100.0, 0.0, 500.0)));
+    scene
+}
+
+/// Panics with the actual render and both hashes printed if the buffer does not hash to the
+/// expected value
+pub fn assert_render_hash(actual: &SimpleTerminalBuffer, expected: u64) {
+    let hash = actual.hash();
+    if hash != expected {
+        panic!(
+            "Render hash mismatch, expected {:#018x} but got {:#018x}:\n{}",
+            expected, hash, actual
+        );
+    }
+}
+
+/// Panics with both renders printed if the buffer does not match the expected text, rows
+/// separated by newlines
+/// ### Example:
+/// ```
+/// use shapes_rs::buffer::*;
+/// use shapes_rs::testing::assert_render_eq;
+///
+/// let mut buffer = SimpleTerminalBuffer::new(2, 2);
+/// buffer.set(0, 1, '#');
+///
+/// assert_render_eq(&buffer, " #\n  ");
+/// ```
+pub fn assert_render_eq(actual: &SimpleTerminalBuffer, expected: &str) {
+    let actual = actual.to_string();
+    if actual != expected {
+        panic!(
+            "Render mismatch\n--- expected ---\n{}\n--- actual ---\n{}\n",
+            expected, actual
+        );
+    }
+}
+
+/// Compares the buffer with the fixture file at the given path. If `SHAPES_UPDATE_GOLDEN` is
+/// set, the file is written instead. Panics if the fixture does not exist and the variable is
+/// not set, so a missing or misnamed fixture can't make a test pass
+pub fn assert_golden<P: AsRef<Path>>(actual: &SimpleTerminalBuffer, path: P) {
+    let path = path.as_ref();
+    if env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("Failed to create fixture directory");
+        }
+        fs::write(path, actual.to_string()).expect("Failed to write fixture");
+        return;
+    }
+    if !path.exists() {
+        panic!(
+            "Fixture {} does not exist, run with {} set to create it",
+            path.display(),
+            UPDATE_GOLDEN_VAR
+        );
+    }
+    let expected = fs::read_to_string(path).expect("Failed to read fixture");
+    assert_render_eq(actual, &expected);
+}
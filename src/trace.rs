@@ -0,0 +1,10 @@
+//! Hooks for the optional tracing feature
+
+/// Enters a span which lasts until the end of the enclosing block, when the tracing feature is
+/// enabled. Does nothing otherwise, fields are not even evaluated.
+macro_rules! phase_span {
+    ($name:literal $(, $field:ident = $value:expr)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $field = $value)*).entered();
+    };
+}